@@ -0,0 +1,21 @@
+-- Staff photos are kept out of the staff row so list and search queries
+-- don't carry every base64 image across IPC.
+CREATE TABLE IF NOT EXISTS staff_photos (
+    staff_id TEXT PRIMARY KEY NOT NULL REFERENCES staff(id) ON DELETE CASCADE,
+
+    -- Full size image (base64 JPEG)
+    image_data TEXT NOT NULL,
+
+    -- Small preview for list views (base64 JPEG), filled in by the app
+    thumbnail_data TEXT,
+
+    updated_at TEXT NOT NULL
+);
+
+-- Move existing photos across; thumbnails are generated on startup
+INSERT OR IGNORE INTO staff_photos (staff_id, image_data, updated_at)
+SELECT id, image_data, updated_at
+FROM staff
+WHERE image_data IS NOT NULL AND image_data <> '';
+
+ALTER TABLE staff DROP COLUMN image_data;
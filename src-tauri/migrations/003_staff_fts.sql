@@ -0,0 +1,55 @@
+-- Full-text index over the searchable staff fields. Rows are keyed by
+-- staff_id rather than rowid because staff has a TEXT primary key and its
+-- rowids are not stable across VACUUM.
+CREATE VIRTUAL TABLE IF NOT EXISTS staff_fts USING fts5(
+    staff_id UNINDEXED,
+    full_name,
+    appointment_number,
+    nic_number,
+    nic_number_old,
+    designation,
+    address,
+    email,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS staff_fts_insert
+    AFTER INSERT ON staff
+    FOR EACH ROW
+    BEGIN
+        INSERT INTO staff_fts (staff_id, full_name, appointment_number, nic_number, nic_number_old, designation, address, email)
+        VALUES (
+            NEW.id, NEW.full_name, NEW.appointment_number, NEW.nic_number, NEW.nic_number_old, NEW.designation,
+            TRIM(COALESCE(NEW.address_line1, '') || ' ' || COALESCE(NEW.address_line2, '') || ' ' || COALESCE(NEW.address_line3, '')),
+            NEW.email
+        );
+    END;
+
+CREATE TRIGGER IF NOT EXISTS staff_fts_delete
+    AFTER DELETE ON staff
+    FOR EACH ROW
+    BEGIN
+        DELETE FROM staff_fts WHERE staff_id = OLD.id;
+    END;
+
+-- Only re-index when an indexed column changes (not on every timestamp bump)
+CREATE TRIGGER IF NOT EXISTS staff_fts_update
+    AFTER UPDATE OF full_name, appointment_number, nic_number, nic_number_old, designation,
+                    address_line1, address_line2, address_line3, email ON staff
+    FOR EACH ROW
+    BEGIN
+        DELETE FROM staff_fts WHERE staff_id = OLD.id;
+        INSERT INTO staff_fts (staff_id, full_name, appointment_number, nic_number, nic_number_old, designation, address, email)
+        VALUES (
+            NEW.id, NEW.full_name, NEW.appointment_number, NEW.nic_number, NEW.nic_number_old, NEW.designation,
+            TRIM(COALESCE(NEW.address_line1, '') || ' ' || COALESCE(NEW.address_line2, '') || ' ' || COALESCE(NEW.address_line3, '')),
+            NEW.email
+        );
+    END;
+
+-- Index the existing records
+INSERT INTO staff_fts (staff_id, full_name, appointment_number, nic_number, nic_number_old, designation, address, email)
+SELECT id, full_name, appointment_number, nic_number, nic_number_old, designation,
+       TRIM(COALESCE(address_line1, '') || ' ' || COALESCE(address_line2, '') || ' ' || COALESCE(address_line3, '')),
+       email
+FROM staff;
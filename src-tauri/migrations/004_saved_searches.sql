@@ -0,0 +1,32 @@
+-- Named filter combinations clerks re-run regularly
+CREATE TABLE IF NOT EXISTS saved_searches (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+
+    -- StaffSearchParams serialized as JSON
+    params TEXT NOT NULL,
+
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Hand-picked lists of staff
+CREATE TABLE IF NOT EXISTS staff_groups (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS staff_group_members (
+    group_id TEXT NOT NULL REFERENCES staff_groups(id) ON DELETE CASCADE,
+    staff_id TEXT NOT NULL REFERENCES staff(id) ON DELETE CASCADE,
+
+    -- Order the members were added in; used when printing the group
+    position INTEGER NOT NULL,
+
+    PRIMARY KEY (group_id, staff_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_staff_group_members_staff ON staff_group_members(staff_id);
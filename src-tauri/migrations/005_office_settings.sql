@@ -0,0 +1,26 @@
+-- Letterhead and signatory details printed on every report.
+-- Always exactly one row, so each office can brand the same build.
+CREATE TABLE IF NOT EXISTS office_settings (
+    id INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+
+    office_name TEXT NOT NULL,
+    division TEXT NOT NULL,
+    address TEXT NOT NULL,
+    phone TEXT,
+
+    -- Base64 JPEG on a white square canvas, see utils::photo::process_logo
+    logo TEXT,
+
+    certifying_officer_name TEXT,
+    certifying_officer_designation TEXT NOT NULL,
+
+    updated_at TEXT NOT NULL
+);
+
+INSERT OR IGNORE INTO office_settings (
+    id, office_name, division, address, phone, logo,
+    certifying_officer_name, certifying_officer_designation, updated_at
+) VALUES (
+    1, 'Divisional Forest Office', 'Vavuniya', 'Vavuniya, North Central Province', NULL, NULL,
+    NULL, 'Divisional Forest Officer', strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now')
+);
@@ -0,0 +1,11 @@
+-- Column mappings for staff imports, reused when the same spreadsheet layout comes in again
+CREATE TABLE IF NOT EXISTS import_mappings (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+
+    -- Vec<ImportColumnMapping> serialized as JSON
+    columns TEXT NOT NULL,
+
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
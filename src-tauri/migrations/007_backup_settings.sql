@@ -0,0 +1,31 @@
+-- Automatic backup schedule and retention. Always exactly one row.
+CREATE TABLE IF NOT EXISTS backup_settings (
+    id INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+
+    enabled INTEGER NOT NULL,
+
+    -- Backup folder, e.g. on a USB drive; NULL means <app data>/backups
+    folder TEXT,
+
+    -- Hours between scheduled backups; one is also taken at every startup
+    interval_hours INTEGER NOT NULL,
+
+    -- How many of the newest daily, weekly and monthly backups to keep
+    keep_daily INTEGER NOT NULL,
+    keep_weekly INTEGER NOT NULL,
+    keep_monthly INTEGER NOT NULL,
+
+    -- Outcome of the most recent backup attempt
+    last_backup_at TEXT,
+    last_error TEXT,
+
+    updated_at TEXT NOT NULL
+);
+
+INSERT OR IGNORE INTO backup_settings (
+    id, enabled, folder, interval_hours, keep_daily, keep_weekly, keep_monthly,
+    last_backup_at, last_error, updated_at
+) VALUES (
+    1, 1, NULL, 24, 7, 4, 12,
+    NULL, NULL, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now')
+);
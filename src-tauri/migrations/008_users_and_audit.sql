@@ -0,0 +1,44 @@
+-- Accounts that can log in to the app
+CREATE TABLE IF NOT EXISTS users (
+    id TEXT PRIMARY KEY NOT NULL,
+    username TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    full_name TEXT NOT NULL,
+
+    -- pbkdf2_sha256$<iterations>$<salt>$<hash>, base64 encoded
+    password_hash TEXT NOT NULL,
+
+    role TEXT NOT NULL CHECK (role IN ('viewer', 'clerk', 'admin')),
+
+    -- Deactivated accounts are kept so their audit entries still name them
+    active INTEGER NOT NULL DEFAULT 1,
+
+    last_login_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Who did what and when. Entries copy the username rather than referencing
+-- users, so they survive an account being renamed.
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id TEXT,
+    username TEXT NOT NULL,
+
+    -- e.g. staff_update, login_failed
+    action TEXT NOT NULL,
+
+    -- The record acted on, e.g. ('staff', <staff id>)
+    entity_type TEXT,
+    entity_id TEXT,
+
+    description TEXT,
+
+    -- Vec<FieldChange> serialized as JSON
+    changes TEXT NOT NULL DEFAULT '[]',
+
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON audit_log(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_audit_log_user ON audit_log(user_id);
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log(created_at);
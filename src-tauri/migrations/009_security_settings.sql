@@ -0,0 +1,12 @@
+-- Session security. Always exactly one row.
+CREATE TABLE IF NOT EXISTS security_settings (
+    id INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+
+    -- Minutes without activity before the session locks; 0 never locks
+    idle_timeout_minutes INTEGER NOT NULL,
+
+    updated_at TEXT NOT NULL
+);
+
+INSERT OR IGNORE INTO security_settings (id, idle_timeout_minutes, updated_at)
+VALUES (1, 15, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now'));
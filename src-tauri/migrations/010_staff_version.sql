@@ -0,0 +1,7 @@
+-- Edit counter for optimistic concurrency: every write to a staff row bumps
+-- it, and an update only goes through if the caller read the current version.
+ALTER TABLE staff ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+
+-- The version a staff change produced, so a rejected update can list the
+-- fields changed since the caller's copy
+ALTER TABLE audit_log ADD COLUMN entity_version INTEGER;
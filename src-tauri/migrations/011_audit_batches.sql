@@ -0,0 +1,5 @@
+-- Groups the entries written by one bulk edit, so the whole edit can be
+-- found and reviewed together
+ALTER TABLE audit_log ADD COLUMN batch_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_batch ON audit_log(batch_id);
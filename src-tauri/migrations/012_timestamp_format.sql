@@ -0,0 +1,60 @@
+-- Every timestamp is stored as RFC3339 in UTC, e.g. 2024-05-01T09:30:00+00:00.
+--
+-- The staff trigger from 001 wrote CURRENT_TIMESTAMP (2024-05-01 09:30:00),
+-- which the app couldn't read back. Replace it, then rewrite any timestamp
+-- stored as 'YYYY-MM-DD HH:MM:SS' or 'YYYY-MM-DDTHH:MM:SS' (both UTC, no
+-- offset) in the RFC3339 form.
+
+-- Dropped first so the rewrite below doesn't bump updated_at
+DROP TRIGGER IF EXISTS update_staff_timestamp;
+
+UPDATE staff SET created_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', created_at) WHERE created_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+UPDATE staff SET updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at) WHERE updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+
+UPDATE staff_photos SET updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at) WHERE updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+
+UPDATE saved_searches SET created_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', created_at) WHERE created_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+UPDATE saved_searches SET updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at) WHERE updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+
+UPDATE staff_groups SET created_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', created_at) WHERE created_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+UPDATE staff_groups SET updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at) WHERE updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+
+UPDATE office_settings SET updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at) WHERE updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+
+UPDATE import_mappings SET created_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', created_at) WHERE created_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+UPDATE import_mappings SET updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at) WHERE updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+
+UPDATE backup_settings SET last_backup_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', last_backup_at) WHERE last_backup_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+UPDATE backup_settings SET updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at) WHERE updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+
+UPDATE users SET last_login_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', last_login_at) WHERE last_login_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+UPDATE users SET created_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', created_at) WHERE created_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+UPDATE users SET updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at) WHERE updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+
+UPDATE audit_log SET created_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', created_at) WHERE created_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+
+UPDATE security_settings SET updated_at = strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at) WHERE updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]';
+
+-- The app sets updated_at itself; only fill it in for updates that didn't
+CREATE TRIGGER IF NOT EXISTS update_staff_timestamp
+    AFTER UPDATE ON staff
+    FOR EACH ROW
+    WHEN NEW.updated_at IS OLD.updated_at
+    BEGIN
+        UPDATE staff SET updated_at = strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now') WHERE id = NEW.id;
+    END;
+
+-- The staff columns still default to CURRENT_TIMESTAMP, which can't be
+-- changed without rebuilding the table, so convert it on insert
+CREATE TRIGGER IF NOT EXISTS staff_timestamp_format
+    AFTER INSERT ON staff
+    FOR EACH ROW
+    WHEN NEW.created_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]' OR NEW.updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]'
+    BEGIN
+        UPDATE staff SET
+            created_at = CASE WHEN created_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]'
+                THEN strftime('%Y-%m-%dT%H:%M:%S+00:00', created_at) ELSE created_at END,
+            updated_at = CASE WHEN updated_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9][ T][0-9][0-9]:[0-9][0-9]:[0-9][0-9]'
+                THEN strftime('%Y-%m-%dT%H:%M:%S+00:00', updated_at) ELSE updated_at END
+        WHERE id = NEW.id;
+    END;
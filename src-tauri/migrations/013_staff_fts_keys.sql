@@ -0,0 +1,63 @@
+-- Key the full-text index by rowid so the triggers can remove a record's
+-- entry directly instead of scanning staff_fts for its staff_id.
+--
+-- staff's own rowids can change on VACUUM (it has a TEXT primary key), so
+-- each record gets a fixed key here; staff_fts rows use it as their rowid.
+CREATE TABLE IF NOT EXISTS staff_fts_keys (
+    rowid INTEGER PRIMARY KEY,
+    staff_id TEXT NOT NULL UNIQUE
+);
+
+INSERT OR IGNORE INTO staff_fts_keys (staff_id) SELECT id FROM staff;
+
+DROP TRIGGER IF EXISTS staff_fts_insert;
+DROP TRIGGER IF EXISTS staff_fts_delete;
+DROP TRIGGER IF EXISTS staff_fts_update;
+
+CREATE TRIGGER IF NOT EXISTS staff_fts_insert
+    AFTER INSERT ON staff
+    FOR EACH ROW
+    BEGIN
+        INSERT INTO staff_fts_keys (staff_id) VALUES (NEW.id);
+        INSERT INTO staff_fts (rowid, staff_id, full_name, appointment_number, nic_number, nic_number_old, designation, address, email)
+        VALUES (
+            (SELECT rowid FROM staff_fts_keys WHERE staff_id = NEW.id),
+            NEW.id, NEW.full_name, NEW.appointment_number, NEW.nic_number, NEW.nic_number_old, NEW.designation,
+            TRIM(COALESCE(NEW.address_line1, '') || ' ' || COALESCE(NEW.address_line2, '') || ' ' || COALESCE(NEW.address_line3, '')),
+            NEW.email
+        );
+    END;
+
+CREATE TRIGGER IF NOT EXISTS staff_fts_delete
+    AFTER DELETE ON staff
+    FOR EACH ROW
+    BEGIN
+        DELETE FROM staff_fts WHERE rowid = (SELECT rowid FROM staff_fts_keys WHERE staff_id = OLD.id);
+        DELETE FROM staff_fts_keys WHERE staff_id = OLD.id;
+    END;
+
+-- Only re-index when an indexed column changes (not on every timestamp bump)
+CREATE TRIGGER IF NOT EXISTS staff_fts_update
+    AFTER UPDATE OF id, full_name, appointment_number, nic_number, nic_number_old, designation,
+                    address_line1, address_line2, address_line3, email ON staff
+    FOR EACH ROW
+    BEGIN
+        UPDATE staff_fts_keys SET staff_id = NEW.id WHERE staff_id = OLD.id;
+        DELETE FROM staff_fts WHERE rowid = (SELECT rowid FROM staff_fts_keys WHERE staff_id = NEW.id);
+        INSERT INTO staff_fts (rowid, staff_id, full_name, appointment_number, nic_number, nic_number_old, designation, address, email)
+        VALUES (
+            (SELECT rowid FROM staff_fts_keys WHERE staff_id = NEW.id),
+            NEW.id, NEW.full_name, NEW.appointment_number, NEW.nic_number, NEW.nic_number_old, NEW.designation,
+            TRIM(COALESCE(NEW.address_line1, '') || ' ' || COALESCE(NEW.address_line2, '') || ' ' || COALESCE(NEW.address_line3, '')),
+            NEW.email
+        );
+    END;
+
+-- Re-index the existing records under their new keys
+DELETE FROM staff_fts;
+INSERT INTO staff_fts (rowid, staff_id, full_name, appointment_number, nic_number, nic_number_old, designation, address, email)
+SELECT k.rowid, s.id, s.full_name, s.appointment_number, s.nic_number, s.nic_number_old, s.designation,
+       TRIM(COALESCE(s.address_line1, '') || ' ' || COALESCE(s.address_line2, '') || ' ' || COALESCE(s.address_line3, '')),
+       s.email
+FROM staff s
+JOIN staff_fts_keys k ON k.staff_id = s.id;
@@ -0,0 +1,215 @@
+//! Hiding sensitive staff details from users who may not see them.
+//!
+//! Viewers can look people up but not see salaries, full NIC numbers or home
+//! addresses. Commands pass what they return, and the search parameters they
+//! are given, through [`mask_for`], which blanks those fields and sets
+//! `masked` on each record so the frontend, reports and exports can show
+//! them as restricted.
+use crate::database::schema::{
+    PrintStaffBulkParams, Staff, StaffBulkPreview, StaffBulkPreviewRow, StaffPage, StaffPageParams, StaffSearchParams,
+    StaffSortColumn, StaffSummary, User, UserRole,
+};
+
+pub const RESTRICTED: &str = "Restricted";
+
+/// NIC digits left showing, counted from the end.
+const VISIBLE_NIC_DIGITS: usize = 3;
+
+pub fn can_see_sensitive(role: UserRole) -> bool {
+    role >= UserRole::Clerk
+}
+
+pub trait Mask {
+    fn mask(&mut self);
+}
+
+/// `value` as `user` may see it.
+pub fn mask_for<T: Mask>(user: &User, mut value: T) -> T {
+    if !can_see_sensitive(user.role) {
+        value.mask();
+    }
+    value
+}
+
+/// Replace every digit but the last few, keeping the letters, so
+/// `198512345678` becomes `*********678` and `851234567V` becomes `******567V`.
+pub fn mask_nic(nic: &str) -> String {
+    let digits = nic.chars().filter(char::is_ascii_digit).count();
+    let mut seen = 0;
+
+    nic.chars()
+        .map(|c| {
+            if !c.is_ascii_digit() {
+                return c;
+            }
+            seen += 1;
+            if seen + VISIBLE_NIC_DIGITS > digits { c } else { '*' }
+        })
+        .collect()
+}
+
+impl Mask for Staff {
+    fn mask(&mut self) {
+        self.nic_number = mask_nic(&self.nic_number);
+        self.nic_number_old = self.nic_number_old.as_deref().map(mask_nic);
+        self.address_line1 = None;
+        self.address_line2 = None;
+        self.address_line3 = None;
+        self.basic_salary = 0.0;
+        self.increment_amount = 0.0;
+        self.masked = true;
+    }
+}
+
+impl Mask for StaffSummary {
+    fn mask(&mut self) {
+        self.nic_number = mask_nic(&self.nic_number);
+        self.nic_number_old = self.nic_number_old.as_deref().map(mask_nic);
+        self.basic_salary = 0.0;
+        // The matched text can come from the NIC or address
+        self.snippet = None;
+        self.masked = true;
+    }
+}
+
+impl Mask for StaffBulkPreviewRow {
+    fn mask(&mut self) {
+        self.staff.mask();
+        self.changes.retain(|change| !change.field.starts_with("address_line"));
+    }
+}
+
+impl Mask for StaffBulkPreview {
+    fn mask(&mut self) {
+        self.rows.mask();
+    }
+}
+
+// Filtering, matching or sorting on a hidden field would let a viewer work
+// it out from which rows come back and in what order, so their searches
+// leave those fields out.
+
+impl Mask for StaffSearchParams {
+    fn mask(&mut self) {
+        self.nic_number = None;
+        self.basic_salary_min = None;
+        self.basic_salary_max = None;
+        self.public_columns_only = true;
+    }
+}
+
+fn mask_sort(sort_by: &mut Option<StaffSortColumn>) {
+    if matches!(sort_by, Some(StaffSortColumn::BasicSalary | StaffSortColumn::NicNumber)) {
+        *sort_by = None;
+    }
+}
+
+impl Mask for StaffPageParams {
+    fn mask(&mut self) {
+        mask_sort(&mut self.sort_by);
+    }
+}
+
+impl Mask for PrintStaffBulkParams {
+    fn mask(&mut self) {
+        self.filters.mask();
+        mask_sort(&mut self.sort_by);
+    }
+}
+
+impl<T: Mask> Mask for Vec<T> {
+    fn mask(&mut self) {
+        self.iter_mut().for_each(Mask::mask);
+    }
+}
+
+impl<T: Mask> Mask for Option<T> {
+    fn mask(&mut self) {
+        if let Some(value) = self {
+            value.mask();
+        }
+    }
+}
+
+impl<T: Mask> Mask for StaffPage<T> {
+    fn mask(&mut self) {
+        self.items.mask();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::operations::search_staff_summaries;
+    use crate::database::test_support::{insert_staff, test_database, user};
+
+    fn search(dir: &tempfile::TempDir, viewer: &User, term: &str) -> Vec<StaffSummary> {
+        let params = mask_for(viewer, StaffSearchParams { search_term: Some(term.to_string()), ..Default::default() });
+        search_staff_summaries(&dir.path().to_path_buf(), &params, &StaffPageParams::default()).unwrap().items
+    }
+
+    #[test]
+    fn mask_nic_keeps_letters_and_last_digits() {
+        assert_eq!(mask_nic("198512345678"), "*********678");
+        assert_eq!(mask_nic("851234567V"), "******567V");
+        assert_eq!(mask_nic("12"), "12");
+    }
+
+    #[test]
+    fn viewers_get_staff_without_sensitive_fields() {
+        let dir = test_database();
+        let staff = insert_staff(&dir, 1);
+
+        let clerk_copy = mask_for(&user(UserRole::Clerk), staff.clone());
+        assert!(!clerk_copy.masked);
+        assert_eq!(clerk_copy.nic_number, staff.nic_number);
+
+        let viewer_copy = mask_for(&user(UserRole::Viewer), staff);
+        assert!(viewer_copy.masked);
+        assert_eq!(viewer_copy.nic_number, "*********001");
+        assert_eq!(viewer_copy.address_line1, None);
+        assert_eq!((viewer_copy.basic_salary, viewer_copy.increment_amount), (0.0, 0.0));
+    }
+
+    #[test]
+    fn viewer_searches_only_match_visible_fields() {
+        let dir = test_database();
+        insert_staff(&dir, 1);
+
+        let clerk = user(UserRole::Clerk);
+        assert_eq!(search(&dir, &clerk, "198010").len(), 1);
+        assert_eq!(search(&dir, &clerk, "Station").len(), 1);
+
+        let viewer = user(UserRole::Viewer);
+        assert!(search(&dir, &viewer, "198010").is_empty());
+        assert!(search(&dir, &viewer, "Station").is_empty());
+        assert_eq!(search(&dir, &viewer, "Kumar").len(), 1);
+        assert_eq!(search(&dir, &viewer, "APP").len(), 1);
+    }
+
+    #[test]
+    fn viewer_searches_drop_hidden_filters() {
+        let params = StaffSearchParams {
+            nic_number: Some("19801".to_string()),
+            basic_salary_min: Some(40000.0),
+            ..Default::default()
+        };
+        let masked = mask_for(&user(UserRole::Viewer), params.clone());
+        assert_eq!(masked.nic_number, None);
+        assert_eq!(masked.basic_salary_min, None);
+
+        let clerk_copy = mask_for(&user(UserRole::Clerk), params);
+        assert_eq!(clerk_copy.nic_number.as_deref(), Some("19801"));
+    }
+
+    #[test]
+    fn viewer_sorts_drop_hidden_columns() {
+        let viewer = user(UserRole::Viewer);
+        for column in [StaffSortColumn::BasicSalary, StaffSortColumn::NicNumber] {
+            let page = mask_for(&viewer, StaffPageParams { sort_by: Some(column), ..Default::default() });
+            assert_eq!(page.sort_by, None);
+        }
+        let page = mask_for(&viewer, StaffPageParams { sort_by: Some(StaffSortColumn::Age), ..Default::default() });
+        assert_eq!(page.sort_by, Some(StaffSortColumn::Age));
+    }
+}
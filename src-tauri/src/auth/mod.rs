@@ -0,0 +1,162 @@
+//! Login sessions and role checks.
+//!
+//! The app has one session at a time, held in Tauri state. Commands call
+//! `require_role` first; it fails until someone has logged in, and returns
+//! the logged in user for tagging audit entries and for masking what they
+//! may not see (see `masking`).
+//!
+//! Every checked command also counts as activity. A session left idle for
+//! longer than the configured timeout is locked: commands are refused until
+//! the user enters their password again. A background watcher locks idle
+//! sessions even when no command arrives, and every change is announced to
+//! the frontend with the `session-lock-changed` event.
+pub mod masking;
+pub mod password;
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use crate::database::schema::{SessionLockChanged, User, UserRole};
+
+pub const SESSION_LOCK_EVENT: &str = "session-lock-changed";
+
+const LOCKED_MESSAGE: &str = "The session is locked; enter your password to continue";
+
+/// How often the watcher looks for an idle session.
+const WATCH_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub user: User,
+    pub last_activity: Instant,
+    pub locked: bool,
+}
+
+#[derive(Default)]
+pub struct SessionState {
+    session: Mutex<Option<Session>>,
+    // None never locks
+    idle_timeout: Mutex<Option<Duration>>,
+}
+
+impl SessionState {
+    pub fn current(&self) -> Option<Session> {
+        self.session.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    pub fn start(&self, user: User) {
+        *self.session.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) =
+            Some(Session { user, last_activity: Instant::now(), locked: false });
+    }
+
+    /// End the session, returning who was logged in.
+    pub fn end(&self) -> Option<Session> {
+        self.session.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take()
+    }
+
+    /// Keep the session's copy of the user in step after their account is edited.
+    pub fn refresh_user(&self, user: &User) {
+        let mut session = self.session.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(session) = session.as_mut().filter(|session| session.user.id == user.id) {
+            session.user = user.clone();
+        }
+    }
+
+    pub fn set_idle_timeout(&self, minutes: u32) {
+        *self.idle_timeout.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) =
+            (minutes > 0).then(|| Duration::from_secs(minutes as u64 * 60));
+    }
+
+    /// Lock the session if it has been idle too long. Returns whether this call locked it.
+    fn lock_if_idle(&self) -> bool {
+        let idle_timeout = *self.idle_timeout.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut session = self.session.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        match (session.as_mut(), idle_timeout) {
+            (Some(session), Some(timeout)) if !session.locked && session.last_activity.elapsed() >= timeout => {
+                session.locked = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Note activity on an unlocked session, returning the session as it now stands.
+    fn record_activity(&self) -> Option<Session> {
+        let mut session = self.session.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(session) = session.as_mut().filter(|session| !session.locked) {
+            session.last_activity = Instant::now();
+        }
+        session.clone()
+    }
+
+    /// Unlock the session after the password has been checked.
+    pub fn unlock(&self) {
+        let mut session = self.session.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(session) = session.as_mut() {
+            session.locked = false;
+            session.last_activity = Instant::now();
+        }
+    }
+}
+
+pub fn role_label(role: UserRole) -> &'static str {
+    match role {
+        UserRole::Viewer => "Viewer",
+        UserRole::Clerk => "Clerk",
+        UserRole::Admin => "Administrator",
+    }
+}
+
+pub fn emit_lock_changed(app_handle: &AppHandle, locked: bool) {
+    // Nothing to do if the window has gone; the next status check will show it
+    let _ = app_handle.emit(SESSION_LOCK_EVENT, SessionLockChanged { locked });
+}
+
+fn lock_if_idle(app_handle: &AppHandle) {
+    if app_handle.state::<SessionState>().lock_if_idle() {
+        emit_lock_changed(app_handle, true);
+    }
+}
+
+/// The current session, after locking it if it has been idle too long.
+/// Doesn't count as activity.
+pub fn current_session(app_handle: &AppHandle) -> Option<Session> {
+    lock_if_idle(app_handle);
+    app_handle.state::<SessionState>().current()
+}
+
+/// Count user activity that doesn't otherwise reach the backend, such as
+/// reading a record already on screen. Does nothing once the session is locked.
+pub fn touch_session(app_handle: &AppHandle) {
+    lock_if_idle(app_handle);
+    app_handle.state::<SessionState>().record_activity();
+}
+
+/// The logged in user, if their role is at least `role` and the session isn't locked.
+pub fn require_role(app_handle: &AppHandle, role: UserRole) -> Result<User, String> {
+    lock_if_idle(app_handle);
+
+    let session = app_handle
+        .state::<SessionState>()
+        .record_activity()
+        .ok_or("Please log in first")?;
+
+    if session.locked {
+        return Err(LOCKED_MESSAGE.to_string());
+    }
+    if session.user.role < role {
+        return Err(format!("This needs {} access", role_label(role)));
+    }
+
+    Ok(session.user)
+}
+
+/// Lock idle sessions in the background, so an unattended PC locks on time
+/// even if nobody touches it.
+pub fn start_idle_watcher(app_handle: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(WATCH_INTERVAL);
+        lock_if_idle(&app_handle);
+    });
+}
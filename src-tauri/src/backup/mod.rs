@@ -0,0 +1,376 @@
+//! Database backups.
+//!
+//! Backups are taken with SQLite's online backup API, so each file is a
+//! consistent copy even while the app is writing. They are named after the
+//! local time they were taken (`staff_database_2025-01-31_17-05-00.db`) and
+//! written under a temporary name first, so a half-written copy is never
+//! listed. After every backup the folder is pruned to the newest backup of
+//! each of the last `keep_daily` days, `keep_weekly` weeks and `keep_monthly`
+//! months; the newest backup is always kept.
+//!
+//! Restoring checks the chosen file first, snapshots the current database as
+//! an ordinary backup and only then copies the file over the live database,
+//! so a bad restore can itself be undone. A backup with no active
+//! administrator, such as one from before user accounts, is restored with the
+//! current database's administrators, so it can't leave the app open to
+//! whoever sets up the first account.
+//!
+//! Backups of an encrypted database are encrypted with the same passphrase.
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone, Utc};
+use rusqlite::backup::Backup;
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use crate::database::encryption::{is_keyed, open_database_file};
+use crate::database::operations::{get_connection, get_database_path, initialize_database, run_migrations, schema_version};
+use crate::database::schema::{BackupInfo, BackupSettings};
+use crate::database::settings::{get_backup_settings, record_backup_result};
+
+const FILE_PREFIX: &str = "staff_database_";
+const FILE_EXTENSION: &str = "db";
+const NAME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+/// How often the scheduler checks whether a backup is due.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Pages copied per step of the backup API, and the pause between steps that
+/// lets other connections write while a copy is in progress.
+const PAGES_PER_STEP: i32 = 256;
+const STEP_PAUSE: Duration = Duration::from_millis(50);
+
+/// Held while a backup is written or the folder pruned, so a manual backup
+/// and a scheduled one never run at the same time.
+static BACKUP_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn default_backup_folder(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("backups")
+}
+
+pub fn backup_folder(app_data_dir: &Path, settings: &BackupSettings) -> PathBuf {
+    match settings.folder.as_deref().map(str::trim) {
+        Some(folder) if !folder.is_empty() => PathBuf::from(folder),
+        _ => default_backup_folder(app_data_dir),
+    }
+}
+
+/// Local time a backup was taken, read from its file name.
+fn backup_time(file_name: &str) -> Option<NaiveDateTime> {
+    let stamp = file_name
+        .strip_prefix(FILE_PREFIX)?
+        .strip_suffix(FILE_EXTENSION)?
+        .strip_suffix('.')?;
+    NaiveDateTime::parse_from_str(stamp, NAME_FORMAT).ok()
+}
+
+fn backup_info(path: &Path) -> Option<(NaiveDateTime, BackupInfo)> {
+    let file_name = path.file_name()?.to_str()?.to_string();
+    let taken_at = backup_time(&file_name)?;
+    let created_at = Local
+        .from_local_datetime(&taken_at)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| DateTime::from_naive_utc_and_offset(taken_at, Utc));
+    let size_bytes = fs::metadata(path).ok()?.len();
+
+    Some((taken_at, BackupInfo { file_name, path: path.display().to_string(), size_bytes, created_at }))
+}
+
+fn read_backups(folder: &Path) -> Result<Vec<(NaiveDateTime, BackupInfo)>, String> {
+    if !folder.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(folder)
+        .map_err(|e| format!("Failed to read backup folder {}: {}", folder.display(), e))?;
+
+    let mut backups: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| backup_info(&entry.path()))
+        .collect();
+    backups.sort_by_key(|(taken_at, _)| std::cmp::Reverse(*taken_at));
+
+    Ok(backups)
+}
+
+/// Backups in `folder`, newest first.
+pub fn list_backups(folder: &Path) -> Result<Vec<BackupInfo>, String> {
+    Ok(read_backups(folder)?.into_iter().map(|(_, info)| info).collect())
+}
+
+/// Take a backup into the configured folder now, then apply the retention
+/// rules. The outcome is recorded in the backup settings either way.
+pub fn create_backup(app_data_dir: &PathBuf) -> Result<BackupInfo, String> {
+    let settings = get_backup_settings(app_data_dir)
+        .map_err(|e| format!("Failed to get backup settings: {}", e))?;
+
+    let result = {
+        let _guard = BACKUP_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        write_backup(app_data_dir, &settings)
+    };
+
+    // A failed record only loses the status line, so it doesn't hide the backup's own result
+    let _ = record_backup_result(app_data_dir, result.as_ref().err().map(String::as_str));
+
+    result
+}
+
+fn write_backup(app_data_dir: &PathBuf, settings: &BackupSettings) -> Result<BackupInfo, String> {
+    let folder = backup_folder(app_data_dir, settings);
+    fs::create_dir_all(&folder)
+        .map_err(|e| format!("Failed to create backup folder {}: {}", folder.display(), e))?;
+
+    let file_name = format!("{}{}.{}", FILE_PREFIX, Local::now().format(NAME_FORMAT), FILE_EXTENSION);
+    let path = folder.join(&file_name);
+    let partial_path = folder.join(format!("{}.partial", file_name));
+
+    let conn = get_connection(app_data_dir)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    copy_database(&conn, &partial_path)
+        .map_err(|e| {
+            let _ = fs::remove_file(&partial_path);
+            format!("Failed to back up database: {}", e)
+        })?;
+    fs::rename(&partial_path, &path)
+        .map_err(|e| format!("Failed to save backup {}: {}", path.display(), e))?;
+
+    prune_backups(&folder, settings)?;
+
+    backup_info(&path)
+        .map(|(_, info)| info)
+        .ok_or_else(|| format!("Backup {} could not be read back", path.display()))
+}
+
+/// Copy a whole database into `dst_path`, keyed like the live database.
+fn copy_database(source: &Connection, dst_path: &Path) -> rusqlite::Result<()> {
+    let mut dst = open_database_file(dst_path, OpenFlags::default())?;
+    let backup = Backup::new(source, &mut dst)?;
+    backup.run_to_completion(PAGES_PER_STEP, STEP_PAUSE, None)
+}
+
+/// Delete backups not kept by the retention rules.
+fn prune_backups(folder: &Path, settings: &BackupSettings) -> Result<(), String> {
+    let backups = read_backups(folder)?;
+
+    // Newest first, so the first backup seen in each period is the one kept
+    let mut keep: HashSet<usize> = HashSet::from([0]);
+    let mut keep_newest_per_period = |limit: u32, period: &dyn Fn(&NaiveDateTime) -> (i32, u32)| {
+        let mut periods = HashSet::new();
+        for (index, (taken_at, _)) in backups.iter().enumerate() {
+            if periods.len() >= limit as usize {
+                break;
+            }
+            if periods.insert(period(taken_at)) {
+                keep.insert(index);
+            }
+        }
+    };
+    keep_newest_per_period(settings.keep_daily, &|time| (time.year(), time.ordinal()));
+    keep_newest_per_period(settings.keep_weekly, &|time| (time.iso_week().year(), time.iso_week().week()));
+    keep_newest_per_period(settings.keep_monthly, &|time| (time.year(), time.month()));
+
+    for (index, (_, info)) in backups.iter().enumerate() {
+        if !keep.contains(&index) {
+            fs::remove_file(&info.path)
+                .map_err(|e| format!("Failed to delete old backup {}: {}", info.path, e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace the database with the backup at `path` and bring it up to the
+/// current schema. Returns the backup taken of the database it replaced.
+pub fn restore_backup(app_data_dir: &PathBuf, path: &Path) -> Result<BackupInfo, String> {
+    let settings = get_backup_settings(app_data_dir)
+        .map_err(|e| format!("Failed to get backup settings: {}", e))?;
+
+    let _guard = BACKUP_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let live_path = get_database_path(app_data_dir);
+    if fs::canonicalize(path).ok() == fs::canonicalize(&live_path).ok() {
+        return Err("That file is the current database".to_string());
+    }
+
+    // Work on a copy: pruning after the safety backup may delete the very file
+    // being restored, and checking FTS tables needs a writable database
+    let staged_path = app_data_dir.join("staff_database.db.restore");
+    let result = stage_backup(path, &staged_path)
+        .and_then(|_| carry_over_admins(app_data_dir, &staged_path))
+        .and_then(|_| {
+            write_backup(app_data_dir, &settings)
+                .map_err(|e| format!("The current database could not be backed up, so nothing was restored: {}", e))
+        })
+        .and_then(|safety_backup| {
+            let mut conn = get_connection(app_data_dir)
+                .map_err(|e| format!("Failed to open database: {}", e))?;
+            let staged = open_database_file(&staged_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+                .map_err(|e| format!("Failed to open backup copy: {}", e))?;
+            Backup::new(&staged, &mut conn)
+                .and_then(|restore| restore.run_to_completion(PAGES_PER_STEP, STEP_PAUSE, None))
+                .map_err(|e| format!("Failed to restore database: {}", e))?;
+            Ok(safety_backup)
+        });
+    let _ = fs::remove_file(&staged_path);
+    let safety_backup = result?;
+
+    initialize_database(app_data_dir).map_err(|e| {
+        format!("The backup was restored but could not be upgraded ({}); restore {} to undo", e, safety_backup.file_name)
+    })?;
+
+    Ok(safety_backup)
+}
+
+/// Bring the staged copy up to the current schema and, if it has no active
+/// administrator, copy in the current database's administrators.
+fn carry_over_admins(app_data_dir: &PathBuf, staged_path: &Path) -> Result<(), String> {
+    const ACTIVE_ADMINS: &str = "FROM users WHERE role = 'admin' AND active = 1";
+    const USER_COLUMNS: &str =
+        "id, username, full_name, password_hash, role, active, last_login_at, created_at, updated_at";
+
+    let mut staged = open_database_file(staged_path, OpenFlags::default())
+        .map_err(|e| format!("Failed to open backup copy: {}", e))?;
+    run_migrations(&mut staged)
+        .map_err(|e| format!("The backup could not be upgraded, so nothing was restored: {}", e))?;
+
+    let admins: i64 = staged
+        .query_row(&format!("SELECT COUNT(*) {}", ACTIVE_ADMINS), [], |row| row.get(0))
+        .map_err(|e| format!("Failed to check backup accounts: {}", e))?;
+    if admins > 0 {
+        return Ok(());
+    }
+
+    let live = get_connection(app_data_dir)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut stmt = live
+        .prepare(&format!("SELECT {} {}", USER_COLUMNS, ACTIVE_ADMINS))
+        .map_err(|e| format!("Failed to read administrator accounts: {}", e))?;
+    let rows = stmt
+        .query_map([], |row| (0..9).map(|i| row.get::<_, rusqlite::types::Value>(i)).collect::<rusqlite::Result<Vec<_>>>())
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| format!("Failed to read administrator accounts: {}", e))?;
+    if rows.is_empty() {
+        return Err("The backup has no administrator account and there is none to carry over, so nothing was restored".to_string());
+    }
+
+    // An inactive account of the same name in the backup is replaced
+    let insert = format!("INSERT OR REPLACE INTO users ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)", USER_COLUMNS);
+    for row in rows {
+        staged
+            .execute(&insert, rusqlite::params_from_iter(row))
+            .map_err(|e| format!("Failed to carry over administrator accounts: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Copy a backup to `staged_path` and check the copy.
+fn stage_backup(path: &Path, staged_path: &Path) -> Result<(), String> {
+    let source = open_database_file(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    // Reading the schema is what reports a file that isn't a database at all
+    source.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))
+        .map_err(|e| {
+            if is_keyed() {
+                format!(
+                    "The backup can't be read with the current passphrase; backups taken before the database was encrypted, or under another passphrase, can't be restored ({})",
+                    e
+                )
+            } else {
+                format!("The file is not a readable database: {}", e)
+            }
+        })?;
+    copy_database(&source, staged_path)
+        .map_err(|e| format!("Failed to copy backup: {}", e))?;
+
+    let staged = open_database_file(staged_path, OpenFlags::default())
+        .map_err(|e| format!("Failed to open backup copy: {}", e))?;
+    verify_backup(&staged)
+}
+
+/// Check a backup is an intact database from this app that this version can open.
+fn verify_backup(conn: &Connection) -> Result<(), String> {
+    let problems = conn
+        .prepare("PRAGMA integrity_check")
+        .and_then(|mut stmt| stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| format!("The file is not a readable database: {}", e))?;
+    if problems.first().map(String::as_str) != Some("ok") {
+        return Err(format!("The backup is damaged: {}", problems.into_iter().take(5).collect::<Vec<_>>().join("; ")));
+    }
+
+    let has_staff: bool = conn
+        .query_row("SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'staff')", [], |row| row.get(0))
+        .map_err(|e| format!("The file is not a readable database: {}", e))?;
+    let version: u32 = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to read backup schema version: {}", e))?;
+    if !has_staff || version == 0 {
+        return Err("The file is not a staff database backup".to_string());
+    }
+    if version > schema_version() {
+        return Err(format!(
+            "The backup was made by a newer version of the app (schema {}, this version supports up to {}); update the app first",
+            version, schema_version()
+        ));
+    }
+
+    Ok(())
+}
+
+fn backup_due(settings: &BackupSettings) -> bool {
+    let interval = chrono::Duration::hours(settings.interval_hours.max(1) as i64);
+    settings.last_backup_at.is_none_or(|last| Utc::now() - last >= interval)
+}
+
+/// Take a backup now and then whenever one is due, on a background thread.
+/// Failures are recorded in the backup settings and retried at the next check,
+/// so a backup folder on a USB drive that isn't plugged in catches up later.
+pub fn start_scheduler(app_data_dir: PathBuf) {
+    std::thread::spawn(move || {
+        let mut startup = true;
+        loop {
+            // Settings can't be read while an encrypted database is still locked,
+            // so the startup backup waits until it has been unlocked
+            if let Ok(settings) = get_backup_settings(&app_data_dir) {
+                if settings.enabled && (startup || backup_due(&settings)) {
+                    let _ = create_backup(&app_data_dir);
+                }
+                startup = false;
+            }
+            std::thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::{CreateUser, UserRole};
+    use crate::database::test_support::{insert_staff, test_database};
+    use crate::database::users::{create_user, list_users};
+
+    #[test]
+    fn restoring_a_backup_without_admins_keeps_the_current_ones() {
+        let live = test_database();
+        let live_dir = live.path().to_path_buf();
+        let admin = CreateUser {
+            username: "admin".to_string(),
+            full_name: "Office Admin".to_string(),
+            password: String::new(),
+            role: UserRole::Admin,
+        };
+        create_user(&live_dir, &admin, "stored-hash").unwrap();
+
+        let old = test_database();
+        let old_staff = insert_staff(&old, 1);
+
+        restore_backup(&live_dir, &get_database_path(&old.path().to_path_buf())).unwrap();
+
+        let users = list_users(&live_dir).unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!((users[0].username.as_str(), users[0].role), ("admin", UserRole::Admin));
+        assert!(crate::database::operations::get_staff_by_id(&live_dir, &old_staff.id).is_ok());
+    }
+}
@@ -0,0 +1,17 @@
+use tauri::{command, AppHandle};
+use crate::auth;
+use crate::database::{
+    audit::list_audit_log as db_list_audit_log,
+    schema::{AuditEntry, AuditLogParams, UserRole},
+};
+use crate::utils::get_app_data_dir;
+
+/// Audit entries, newest first, optionally for one record or user.
+#[command]
+pub async fn list_audit_log(app_handle: AppHandle, params: Option<AuditLogParams>) -> Result<Vec<AuditEntry>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    auth::require_role(&app_handle, UserRole::Admin)?;
+
+    db_list_audit_log(&app_data_dir, &params.unwrap_or_default())
+        .map_err(|e| format!("Failed to get audit log: {}", e))
+}
@@ -0,0 +1,335 @@
+use tauri::{command, AppHandle, State};
+use crate::auth::{self, password, SessionState};
+use crate::database::{
+    audit::{record_audit_entry as db_record_audit_entry, AuditAction, NewAuditEntry},
+    users::{
+        count_users as db_count_users,
+        count_active_admins as db_count_active_admins,
+        create_user as db_create_user,
+        get_user as db_get_user,
+        list_users as db_list_users,
+        get_user_login as db_get_user_login,
+        get_password_hash as db_get_password_hash,
+        record_login as db_record_login,
+        update_user as db_update_user,
+        set_user_password as db_set_user_password,
+    },
+    settings::{
+        get_security_settings as db_get_security_settings,
+        update_security_settings as db_update_security_settings,
+    },
+    schema::{AuthStatus, CreateUser, UpdateUser, User, UserRole, SecuritySettings, UpdateSecuritySettings},
+};
+use crate::utils::get_app_data_dir;
+use std::path::PathBuf;
+
+// A working day
+const MAX_IDLE_TIMEOUT_MINUTES: u32 = 8 * 60;
+
+fn validate_new_user(data: &CreateUser) -> Result<(), String> {
+    let username = data.username.trim();
+    if username.is_empty() {
+        return Err("Username is required".to_string());
+    }
+    if username.chars().any(char::is_whitespace) {
+        return Err("Username cannot contain spaces".to_string());
+    }
+    if data.full_name.trim().is_empty() {
+        return Err("Full name is required".to_string());
+    }
+    password::validate_password(&data.password)
+}
+
+fn record_audit(app_data_dir: &PathBuf, entry: NewAuditEntry) -> Result<(), String> {
+    db_record_audit_entry(app_data_dir, &entry)
+        .map_err(|e| format!("Failed to record audit entry: {}", e))
+}
+
+/// Start a session with the configured idle timeout.
+fn start_session(app_data_dir: &PathBuf, session: &SessionState, user: User) -> Result<(), String> {
+    let settings = db_get_security_settings(app_data_dir)
+        .map_err(|e| format!("Failed to get security settings: {}", e))?;
+
+    session.set_idle_timeout(settings.idle_timeout_minutes);
+    session.start(user);
+    Ok(())
+}
+
+fn login_failed(app_data_dir: &PathBuf, username: &str) -> Result<(), String> {
+    record_audit(app_data_dir, NewAuditEntry {
+        user_id: None,
+        username: username.trim().to_string(),
+        action: AuditAction::LoginFailed,
+        entity_type: None,
+        entity_id: None,
+        entity_version: None,
+        description: None,
+        changes: Vec::new(),
+        batch_id: None,
+    })
+}
+
+fn has_users(app_data_dir: &PathBuf) -> Result<bool, String> {
+    db_count_users(app_data_dir)
+        .map(|count| count > 0)
+        .map_err(|e| format!("Failed to check user accounts: {}", e))
+}
+
+#[command]
+pub async fn get_auth_status(app_handle: AppHandle) -> Result<AuthStatus, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let session = auth::current_session(&app_handle);
+
+    Ok(AuthStatus {
+        has_users: has_users(&app_data_dir)?,
+        locked: session.as_ref().is_some_and(|session| session.locked),
+        user: session.map(|session| session.user),
+    })
+}
+
+/// Create the first administrator on a fresh install and log them in.
+/// Refused once any account exists.
+#[command]
+pub async fn create_first_admin(
+    app_handle: AppHandle,
+    session: State<'_, SessionState>,
+    mut data: CreateUser,
+) -> Result<User, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+
+    if has_users(&app_data_dir)? {
+        return Err("An administrator has already been set up; log in instead".to_string());
+    }
+    data.role = UserRole::Admin;
+    validate_new_user(&data)?;
+
+    let user = db_create_user(&app_data_dir, &data, &password::hash_password(&data.password)?)
+        .map_err(|e| format!("Failed to create user: {}", e))?;
+    let user = db_record_login(&app_data_dir, &user.id)
+        .map_err(|e| format!("Failed to record login: {}", e))?;
+
+    start_session(&app_data_dir, &session, user.clone())?;
+    record_audit(&app_data_dir, NewAuditEntry::new(&user, AuditAction::UserCreate).on("user", &user.id))?;
+
+    Ok(user)
+}
+
+#[command]
+pub async fn login(
+    app_handle: AppHandle,
+    session: State<'_, SessionState>,
+    username: String,
+    password: String,
+) -> Result<User, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+
+    let login = db_get_user_login(&app_data_dir, &username)
+        .map_err(|e| format!("Failed to log in: {}", e))?;
+
+    // Hash the password whether or not the account exists, and only then look
+    // at whether it's active, so neither the time taken nor the message
+    // shows which usernames exist
+    let (user, hash) = match login {
+        Some((user, hash)) => (Some(user), hash),
+        None => (None, password::DUMMY_HASH.to_string()),
+    };
+    let verified = password::verify_password(&password, &hash);
+    let user = match user {
+        Some(user) if verified && user.active => user,
+        _ => {
+            login_failed(&app_data_dir, &username)?;
+            return Err("Incorrect username or password".to_string());
+        }
+    };
+
+    let user = db_record_login(&app_data_dir, &user.id)
+        .map_err(|e| format!("Failed to record login: {}", e))?;
+
+    start_session(&app_data_dir, &session, user.clone())?;
+    record_audit(&app_data_dir, NewAuditEntry::new(&user, AuditAction::Login))?;
+
+    Ok(user)
+}
+
+/// Count activity in the window, such as typing into a form, that hasn't
+/// reached the backend yet, so the session doesn't lock mid-edit.
+#[command]
+pub async fn touch_session(app_handle: AppHandle) -> Result<(), String> {
+    auth::touch_session(&app_handle);
+    Ok(())
+}
+
+/// Unlock an idle-locked session with the logged in user's password.
+#[command]
+pub async fn unlock_session(
+    app_handle: AppHandle,
+    session: State<'_, SessionState>,
+    password: String,
+) -> Result<User, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = session.current().ok_or("Please log in first")?.user;
+
+    let hash = db_get_password_hash(&app_data_dir, &user.id)
+        .map_err(|e| format!("Failed to check password: {}", e))?;
+    if !password::verify_password(&password, &hash) {
+        login_failed(&app_data_dir, &user.username)?;
+        return Err("Incorrect password".to_string());
+    }
+
+    session.unlock();
+    auth::emit_lock_changed(&app_handle, false);
+    record_audit(&app_data_dir, NewAuditEntry::new(&user, AuditAction::SessionUnlock))?;
+
+    Ok(user)
+}
+
+#[command]
+pub async fn logout(app_handle: AppHandle, session: State<'_, SessionState>) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+
+    if let Some(ended) = session.end() {
+        record_audit(&app_data_dir, NewAuditEntry::new(&ended.user, AuditAction::Logout))?;
+    }
+
+    Ok(())
+}
+
+/// Change the logged in user's own password.
+#[command]
+pub async fn change_password(
+    app_handle: AppHandle,
+    current_password: String,
+    new_password: String,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = auth::require_role(&app_handle, UserRole::Viewer)?;
+
+    let hash = db_get_password_hash(&app_data_dir, &user.id)
+        .map_err(|e| format!("Failed to check password: {}", e))?;
+    if !password::verify_password(&current_password, &hash) {
+        return Err("Current password is incorrect".to_string());
+    }
+    password::validate_password(&new_password)?;
+
+    db_set_user_password(&app_data_dir, &user.id, &password::hash_password(&new_password)?)
+        .map_err(|e| format!("Failed to change password: {}", e))?;
+
+    record_audit(&app_data_dir, NewAuditEntry::new(&user, AuditAction::PasswordChange).on("user", &user.id))
+}
+
+#[command]
+pub async fn list_users(app_handle: AppHandle) -> Result<Vec<User>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    auth::require_role(&app_handle, UserRole::Admin)?;
+
+    db_list_users(&app_data_dir)
+        .map_err(|e| format!("Failed to get users: {}", e))
+}
+
+#[command]
+pub async fn create_user(app_handle: AppHandle, data: CreateUser) -> Result<User, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let admin = auth::require_role(&app_handle, UserRole::Admin)?;
+    validate_new_user(&data)?;
+
+    let user = db_create_user(&app_data_dir, &data, &password::hash_password(&data.password)?)
+        .map_err(|e| format!("Failed to create user: {}", e))?;
+
+    record_audit(
+        &app_data_dir,
+        NewAuditEntry::new(&admin, AuditAction::UserCreate)
+            .on("user", &user.id)
+            .with_description(format!("{} ({})", user.username, auth::role_label(user.role))),
+    )?;
+
+    Ok(user)
+}
+
+/// Change an account's name, role or active flag. The last active
+/// administrator can't be demoted or deactivated.
+#[command]
+pub async fn update_user(
+    app_handle: AppHandle,
+    session: State<'_, SessionState>,
+    data: UpdateUser,
+) -> Result<User, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let admin = auth::require_role(&app_handle, UserRole::Admin)?;
+
+    if data.full_name.trim().is_empty() {
+        return Err("Full name is required".to_string());
+    }
+
+    let existing = db_get_user(&app_data_dir, &data.id)
+        .map_err(|e| format!("Failed to get user: {}", e))?;
+    let loses_admin = existing.role == UserRole::Admin && existing.active
+        && (data.role != UserRole::Admin || !data.active);
+    if loses_admin {
+        let admins = db_count_active_admins(&app_data_dir)
+            .map_err(|e| format!("Failed to check administrators: {}", e))?;
+        if admins <= 1 {
+            return Err("At least one active administrator is needed".to_string());
+        }
+    }
+
+    let user = db_update_user(&app_data_dir, &data)
+        .map_err(|e| format!("Failed to update user: {}", e))?;
+    session.refresh_user(&user);
+
+    record_audit(
+        &app_data_dir,
+        NewAuditEntry::new(&admin, AuditAction::UserUpdate)
+            .on("user", &user.id)
+            .with_description(format!(
+                "{} ({}{})",
+                user.username,
+                auth::role_label(user.role),
+                if user.active { "" } else { ", inactive" }
+            )),
+    )?;
+
+    Ok(user)
+}
+
+/// Set a new password for someone who has forgotten theirs.
+#[command]
+pub async fn reset_user_password(app_handle: AppHandle, id: String, new_password: String) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let admin = auth::require_role(&app_handle, UserRole::Admin)?;
+    password::validate_password(&new_password)?;
+
+    db_set_user_password(&app_data_dir, &id, &password::hash_password(&new_password)?)
+        .map_err(|e| format!("Failed to reset password: {}", e))?;
+
+    record_audit(&app_data_dir, NewAuditEntry::new(&admin, AuditAction::PasswordChange).on("user", &id))
+}
+
+#[command]
+pub async fn get_security_settings(app_handle: AppHandle) -> Result<SecuritySettings, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    auth::require_role(&app_handle, UserRole::Viewer)?;
+
+    db_get_security_settings(&app_data_dir)
+        .map_err(|e| format!("Failed to get security settings: {}", e))
+}
+
+/// Change how long a session may sit idle before it locks; 0 never locks.
+#[command]
+pub async fn update_security_settings(
+    app_handle: AppHandle,
+    session: State<'_, SessionState>,
+    data: UpdateSecuritySettings,
+) -> Result<SecuritySettings, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    auth::require_role(&app_handle, UserRole::Admin)?;
+
+    if data.idle_timeout_minutes > MAX_IDLE_TIMEOUT_MINUTES {
+        return Err(format!("Idle timeout can be at most {} minutes", MAX_IDLE_TIMEOUT_MINUTES));
+    }
+
+    let settings = db_update_security_settings(&app_data_dir, data)
+        .map_err(|e| format!("Failed to update security settings: {}", e))?;
+    session.set_idle_timeout(settings.idle_timeout_minutes);
+
+    Ok(settings)
+}
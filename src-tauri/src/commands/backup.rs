@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+use tauri::{command, AppHandle, Manager};
+use crate::auth::{require_role, SessionState};
+use crate::backup;
+use crate::database::{
+    audit::{record_audit_entry as db_record_audit_entry, AuditAction, NewAuditEntry},
+    settings::{
+        get_backup_settings as db_get_backup_settings,
+        update_backup_settings as db_update_backup_settings,
+    },
+    schema::{BackupInfo, BackupSettings, UpdateBackupSettings, UserRole},
+};
+use crate::utils::get_app_data_dir;
+
+#[command]
+pub async fn get_backup_settings(app_handle: AppHandle) -> Result<BackupSettings, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    // Anyone who can take a backup can see where they go
+    require_role(&app_handle, UserRole::Clerk)?;
+
+    db_get_backup_settings(&app_data_dir)
+        .map_err(|e| format!("Failed to get backup settings: {}", e))
+}
+
+#[command]
+pub async fn update_backup_settings(
+    app_handle: AppHandle,
+    data: UpdateBackupSettings,
+) -> Result<BackupSettings, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Admin)?;
+
+    if data.interval_hours == 0 {
+        return Err("Backup interval must be at least 1 hour".to_string());
+    }
+    let folder = data.folder.map(|folder| folder.trim().to_string()).filter(|folder| !folder.is_empty());
+    if let Some(folder) = &folder {
+        if !PathBuf::from(folder).is_absolute() {
+            return Err("Backup folder must be a full path".to_string());
+        }
+    }
+
+    db_update_backup_settings(&app_data_dir, UpdateBackupSettings { folder, ..data })
+        .map_err(|e| format!("Failed to update backup settings: {}", e))
+}
+
+/// Backups in the configured folder, newest first.
+#[command]
+pub async fn list_backups(app_handle: AppHandle) -> Result<Vec<BackupInfo>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Clerk)?;
+
+    let settings = db_get_backup_settings(&app_data_dir)
+        .map_err(|e| format!("Failed to get backup settings: {}", e))?;
+
+    backup::list_backups(&backup::backup_folder(&app_data_dir, &settings))
+}
+
+#[command]
+pub async fn create_backup_now(app_handle: AppHandle) -> Result<BackupInfo, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Clerk)?;
+
+    backup::create_backup(&app_data_dir)
+}
+
+/// Replace the database with a backup file, after checking it and backing up
+/// the current database. Returns that safety backup so the restore can be undone.
+///
+/// Ends the session: the restored accounts may not include the logged in
+/// user, or may give them a different role, so everyone logs in again.
+#[command]
+pub async fn restore_backup(app_handle: AppHandle, path: String) -> Result<BackupInfo, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Admin)?;
+
+    let safety_backup = backup::restore_backup(&app_data_dir, Path::new(&path))?;
+
+    // Recorded in the restored database, so the log shows where its contents came from
+    let entry = NewAuditEntry::new(&user, AuditAction::BackupRestore)
+        .with_description(format!("{} (previous database saved as {})", path, safety_backup.file_name));
+    let recorded = db_record_audit_entry(&app_data_dir, &entry)
+        .map_err(|e| format!("Restored, but failed to record audit entry: {}", e));
+    app_handle.state::<SessionState>().end();
+    recorded?;
+
+    Ok(safety_backup)
+}
@@ -0,0 +1,90 @@
+use tauri::{command, AppHandle};
+use crate::auth::require_role;
+use crate::database::{
+    encryption::{
+        get_encryption_status as db_get_encryption_status,
+        unlock_database as db_unlock_database,
+        encrypt_database as db_encrypt_database,
+        change_passphrase as db_change_passphrase,
+        is_encrypted,
+    },
+    operations::initialize_database,
+    schema::{EncryptionStatus, UserRole},
+};
+use crate::utils::get_app_data_dir;
+
+const MIN_PASSPHRASE_LENGTH: usize = 8;
+
+fn validate_passphrase(passphrase: &str) -> Result<(), String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LENGTH {
+        return Err(format!("Passphrase must be at least {} characters", MIN_PASSPHRASE_LENGTH));
+    }
+    Ok(())
+}
+
+#[command]
+pub async fn get_encryption_status(app_handle: AppHandle) -> Result<EncryptionStatus, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+
+    db_get_encryption_status(&app_data_dir)
+        .map_err(|e| format!("Failed to check database encryption: {}", e))
+}
+
+/// Open an encrypted database with its passphrase. Migrations that were
+/// skipped at startup because the database was locked run now.
+#[command]
+pub async fn unlock_database(app_handle: AppHandle, passphrase: String) -> Result<EncryptionStatus, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+
+    let unlocked = db_unlock_database(&app_data_dir, &passphrase)
+        .map_err(|e| format!("Failed to unlock database: {}", e))?;
+    if !unlocked {
+        return Err("Incorrect passphrase".to_string());
+    }
+
+    initialize_database(&app_data_dir)
+        .map_err(|e| format!("Failed to initialize database: {}", e))?;
+
+    db_get_encryption_status(&app_data_dir)
+        .map_err(|e| format!("Failed to check database encryption: {}", e))
+}
+
+/// Encrypt the current plain database in place with a new passphrase.
+#[command]
+pub async fn encrypt_database(app_handle: AppHandle, passphrase: String) -> Result<EncryptionStatus, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Admin)?;
+
+    if is_encrypted(&app_data_dir).map_err(|e| format!("Failed to check database encryption: {}", e))? {
+        return Err("The database is already encrypted".to_string());
+    }
+    validate_passphrase(&passphrase)?;
+
+    db_encrypt_database(&app_data_dir, &passphrase)?;
+
+    db_get_encryption_status(&app_data_dir)
+        .map_err(|e| format!("Failed to check database encryption: {}", e))
+}
+
+#[command]
+pub async fn change_database_passphrase(
+    app_handle: AppHandle,
+    current_passphrase: String,
+    new_passphrase: String,
+) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Admin)?;
+
+    if !is_encrypted(&app_data_dir).map_err(|e| format!("Failed to check database encryption: {}", e))? {
+        return Err("The database is not encrypted".to_string());
+    }
+    validate_passphrase(&new_passphrase)?;
+
+    let changed = db_change_passphrase(&app_data_dir, &current_passphrase, &new_passphrase)
+        .map_err(|e| format!("Failed to change passphrase: {}", e))?;
+    if !changed {
+        return Err("Current passphrase is incorrect".to_string());
+    }
+
+    Ok(())
+}
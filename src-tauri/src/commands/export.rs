@@ -0,0 +1,71 @@
+use tauri::{command, AppHandle};
+use crate::auth::{masking::mask_for, require_role};
+use crate::commands::print::load_bulk_staff;
+use crate::database::{
+    schema::{ExportStaffCsvParams, ExportStaffXlsxParams, UserRole},
+    transfer::export_all as db_export_all,
+};
+use crate::export;
+use crate::utils::{get_app_data_dir, write_output_file};
+
+#[command]
+pub async fn export_staff_csv(
+    app_handle: AppHandle,
+    params: ExportStaffCsvParams,
+    output_path: String,
+) -> Result<String, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Viewer)?;
+    let selection = mask_for(&user, params.selection);
+
+    let staff_list = load_bulk_staff(&app_data_dir, &user, &selection)?;
+
+    if staff_list.is_empty() {
+        return Err("No staff data to export".to_string());
+    }
+
+    let columns = export::columns_or_default(&params.columns);
+    let csv = export::csv::write(&staff_list, columns, params.format_values, params.include_bom)?;
+    let file_path = write_output_file(&output_path, "csv", &csv)?;
+
+    Ok(format!("CSV saved: {} ({} records)", file_path.display(), staff_list.len()))
+}
+
+#[command]
+pub async fn export_staff_xlsx(
+    app_handle: AppHandle,
+    params: ExportStaffXlsxParams,
+    output_path: String,
+) -> Result<String, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Viewer)?;
+    let selection = mask_for(&user, params.selection);
+
+    let staff_list = load_bulk_staff(&app_data_dir, &user, &selection)?;
+
+    if staff_list.is_empty() {
+        return Err("No staff data to export".to_string());
+    }
+
+    let columns = export::columns_or_default(&params.columns);
+    let xlsx = export::xlsx::write(&staff_list, columns, params.sheet_per_designation, params.include_summary)?;
+    let file_path = write_output_file(&output_path, "xlsx", &xlsx)?;
+
+    Ok(format!("Excel file saved: {} ({} records)", file_path.display(), staff_list.len()))
+}
+
+/// Write the whole database, photos and settings included, to a JSON file
+/// that `import_all` can load on another machine.
+#[command]
+pub async fn export_all(app_handle: AppHandle, output_path: String) -> Result<String, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Admin)?;
+
+    let data = db_export_all(&app_data_dir)
+        .map_err(|e| format!("Failed to export database: {}", e))?;
+    let json = serde_json::to_vec_pretty(&data)
+        .map_err(|e| format!("Failed to encode database export: {}", e))?;
+    let file_path = write_output_file(&output_path, "json", &json)?;
+
+    Ok(format!("Database exported: {} ({} staff records)", file_path.display(), data.staff.len()))
+}
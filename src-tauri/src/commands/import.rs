@@ -0,0 +1,172 @@
+use std::path::Path;
+use tauri::{command, AppHandle};
+use crate::auth::require_role;
+use crate::database::{
+    audit::{record_audit_entry as db_record_audit_entry, AuditAction, NewAuditEntry},
+    operations::{
+        create_staff_batch as db_create_staff_batch,
+        get_staff_identities as db_get_staff_identities,
+    },
+    import_mappings::{
+        create_import_mapping as db_create_import_mapping,
+        list_import_mappings as db_list_import_mappings,
+        get_import_mapping as db_get_import_mapping,
+        update_import_mapping as db_update_import_mapping,
+        delete_import_mapping as db_delete_import_mapping,
+    },
+    schema::{
+        ImportMapping, CreateImportMapping, UpdateImportMapping, ImportPreview, ImportReport, ImportStaffParams,
+        ImportAllParams, TransferReport, UserRole,
+    },
+    transfer::{import_all as db_import_all, read_export as db_read_export},
+};
+use crate::import::{self, reader};
+use crate::utils::get_app_data_dir;
+
+const PREVIEW_ROWS: usize = 10;
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Name is required".to_string());
+    }
+    Ok(())
+}
+
+/// Headers, a few sample rows and a suggested column mapping for a file.
+#[command]
+pub async fn preview_staff_import(
+    app_handle: AppHandle,
+    path: String,
+    sheet: Option<String>,
+) -> Result<ImportPreview, String> {
+    require_role(&app_handle, UserRole::Clerk)?;
+
+    let table = reader::read(Path::new(&path), sheet.as_deref())?;
+
+    Ok(ImportPreview {
+        suggested_columns: import::suggest_columns(&table.headers),
+        sample_rows: table.rows.iter().take(PREVIEW_ROWS).map(|(_, row)| row.clone()).collect(),
+        total_rows: table.rows.len(),
+        sheets: table.sheets,
+        sheet: table.sheet,
+        headers: table.headers,
+    })
+}
+
+/// Validate every row of a file and, unless it is a dry run, save the valid
+/// rows in a single transaction. Rows with errors or conflicts are skipped
+/// and listed in the report.
+#[command]
+pub async fn import_staff(
+    app_handle: AppHandle,
+    params: ImportStaffParams,
+) -> Result<ImportReport, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Clerk)?;
+
+    let columns = match &params.mapping_id {
+        Some(mapping_id) => db_get_import_mapping(&app_data_dir, mapping_id)
+            .map_err(|e| format!("Failed to get import mapping: {}", e))?
+            .columns,
+        None => params.columns,
+    };
+    if import::maps_salary(&columns) {
+        require_role(&app_handle, UserRole::Admin)
+            .map_err(|_| "Only an administrator can import salary details; unmap the salary columns".to_string())?;
+    }
+
+    let table = reader::read(Path::new(&params.path), params.sheet.as_deref())?;
+    if table.rows.is_empty() {
+        return Err("The file has no rows to import".to_string());
+    }
+
+    let existing = db_get_staff_identities(&app_data_dir)
+        .map_err(|e| format!("Failed to check existing staff: {}", e))?;
+
+    let prepared = import::prepare(&table, &columns, &existing, params.dry_run)?;
+    let mut report = prepared.report;
+
+    if !params.dry_run && !prepared.staff.is_empty() {
+        report.imported_ids = db_create_staff_batch(&app_data_dir, &prepared.staff)
+            .map_err(|e| format!("Failed to import staff: {}", e))?;
+        report.imported = report.imported_ids.len();
+
+        let entry = NewAuditEntry::new(&user, AuditAction::StaffImport)
+            .with_description(format!("{} staff from {}", report.imported, params.path));
+        db_record_audit_entry(&app_data_dir, &entry)
+            .map_err(|e| format!("Imported, but failed to record audit entry: {}", e))?;
+    }
+
+    Ok(report)
+}
+
+#[command]
+pub async fn create_import_mapping(
+    app_handle: AppHandle,
+    data: CreateImportMapping,
+) -> Result<ImportMapping, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Clerk)?;
+    validate_name(&data.name)?;
+
+    db_create_import_mapping(&app_data_dir, data)
+        .map_err(|e| format!("Failed to create import mapping: {}", e))
+}
+
+#[command]
+pub async fn list_import_mappings(app_handle: AppHandle) -> Result<Vec<ImportMapping>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Clerk)?;
+
+    db_list_import_mappings(&app_data_dir)
+        .map_err(|e| format!("Failed to get import mappings: {}", e))
+}
+
+#[command]
+pub async fn update_import_mapping(
+    app_handle: AppHandle,
+    data: UpdateImportMapping,
+) -> Result<ImportMapping, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Clerk)?;
+    validate_name(&data.name)?;
+
+    db_update_import_mapping(&app_data_dir, data)
+        .map_err(|e| format!("Failed to update import mapping: {}", e))
+}
+
+#[command]
+pub async fn delete_import_mapping(app_handle: AppHandle, id: String) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Clerk)?;
+
+    db_delete_import_mapping(&app_data_dir, &id)
+        .map_err(|e| format!("Failed to delete import mapping: {}", e))
+}
+
+/// Load a file written by `export_all`, keeping record ids. Clashes with
+/// different local records are handled per `on_conflict` and listed in the report.
+#[command]
+pub async fn import_all(app_handle: AppHandle, params: ImportAllParams) -> Result<TransferReport, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Admin)?;
+
+    let json = std::fs::read(&params.path)
+        .map_err(|e| format!("Failed to read {}: {}", params.path, e))?;
+
+    let data = db_read_export(&json)?;
+
+    let report = db_import_all(&app_data_dir, &data, params.on_conflict, params.include_office_settings, params.dry_run)
+        .map_err(|e| format!("Failed to import database: {}", e))?;
+
+    if !params.dry_run {
+        let entry = NewAuditEntry::new(&user, AuditAction::DatabaseImport).with_description(format!(
+            "{} ({} staff added, {} updated, {} skipped)",
+            params.path, report.staff.inserted, report.staff.updated, report.staff.skipped
+        ));
+        db_record_audit_entry(&app_data_dir, &entry)
+            .map_err(|e| format!("Imported, but failed to record audit entry: {}", e))?;
+    }
+
+    Ok(report)
+}
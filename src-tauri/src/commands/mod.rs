@@ -1,2 +1,10 @@
 pub mod staff;
-pub mod print;
\ No newline at end of file
+pub mod print;
+pub mod saved_searches;
+pub mod settings;
+pub mod export;
+pub mod import;
+pub mod backup;
+pub mod encryption;
+pub mod auth;
+pub mod audit;
@@ -0,0 +1,241 @@
+use tauri::{command, AppHandle};
+use crate::auth::{masking::mask_for, require_role};
+use crate::commands::print::{generate_bulk_staff_pdf, generate_bulk_staff_pdf_with_params};
+use crate::database::{
+    operations::{
+        search_staff_ids as db_search_staff_ids,
+        search_staff_summaries as db_search_staff_summaries,
+        get_staff_summaries_by_ids as db_get_staff_summaries_by_ids,
+    },
+    saved_searches::{
+        create_saved_search as db_create_saved_search,
+        list_saved_searches as db_list_saved_searches,
+        get_saved_search as db_get_saved_search,
+        update_saved_search as db_update_saved_search,
+        delete_saved_search as db_delete_saved_search,
+        create_staff_group as db_create_staff_group,
+        list_staff_groups as db_list_staff_groups,
+        get_staff_group as db_get_staff_group,
+        update_staff_group as db_update_staff_group,
+        add_staff_to_group as db_add_staff_to_group,
+        remove_staff_from_group as db_remove_staff_from_group,
+        delete_staff_group as db_delete_staff_group,
+    },
+    schema::{
+        SavedSearch, CreateSavedSearch, UpdateSavedSearch, StaffGroup, CreateStaffGroup, UpdateStaffGroup,
+        StaffSummary, StaffPage, StaffPageParams, PrintStaffBulkParams, UserRole,
+    },
+};
+use crate::utils::get_app_data_dir;
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Name is required".to_string());
+    }
+    Ok(())
+}
+
+// Saved searches
+
+#[command]
+pub async fn create_saved_search(
+    app_handle: AppHandle,
+    data: CreateSavedSearch,
+) -> Result<SavedSearch, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+    validate_name(&data.name)?;
+
+    db_create_saved_search(&app_data_dir, data)
+        .map_err(|e| format!("Failed to create saved search: {}", e))
+}
+
+#[command]
+pub async fn list_saved_searches(app_handle: AppHandle) -> Result<Vec<SavedSearch>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+
+    db_list_saved_searches(&app_data_dir)
+        .map_err(|e| format!("Failed to get saved searches: {}", e))
+}
+
+#[command]
+pub async fn update_saved_search(
+    app_handle: AppHandle,
+    data: UpdateSavedSearch,
+) -> Result<SavedSearch, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+    validate_name(&data.name)?;
+
+    db_update_saved_search(&app_data_dir, data)
+        .map_err(|e| format!("Failed to update saved search: {}", e))
+}
+
+#[command]
+pub async fn delete_saved_search(app_handle: AppHandle, id: String) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+
+    db_delete_saved_search(&app_data_dir, &id)
+        .map_err(|e| format!("Failed to delete saved search: {}", e))
+}
+
+/// Run a saved search and return a page of matching staff.
+#[command]
+pub async fn run_saved_search(
+    app_handle: AppHandle,
+    id: String,
+    page: Option<StaffPageParams>,
+) -> Result<StaffPage<StaffSummary>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Viewer)?;
+
+    let saved_search = db_get_saved_search(&app_data_dir, &id)
+        .map_err(|e| format!("Failed to get saved search: {}", e))?;
+    let params = mask_for(&user, saved_search.params);
+    let page = mask_for(&user, page.unwrap_or_default());
+
+    db_search_staff_summaries(&app_data_dir, &params, &page)
+        .map(|staff| mask_for(&user, staff))
+        .map_err(|e| format!("Failed to run saved search: {}", e))
+}
+
+/// Ids of every staff member currently matching a saved search, ready for bulk printing.
+#[command]
+pub async fn get_saved_search_staff_ids(app_handle: AppHandle, id: String) -> Result<Vec<String>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Viewer)?;
+
+    let saved_search = db_get_saved_search(&app_data_dir, &id)
+        .map_err(|e| format!("Failed to get saved search: {}", e))?;
+
+    db_search_staff_ids(&app_data_dir, &mask_for(&user, saved_search.params))
+        .map_err(|e| format!("Failed to run saved search: {}", e))
+}
+
+#[command]
+pub async fn print_saved_search(
+    app_handle: AppHandle,
+    id: String,
+    output_path: String,
+) -> Result<String, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+
+    let saved_search = db_get_saved_search(&app_data_dir, &id)
+        .map_err(|e| format!("Failed to get saved search: {}", e))?;
+
+    let params = PrintStaffBulkParams {
+        filters: Some(saved_search.params),
+        ..Default::default()
+    };
+
+    generate_bulk_staff_pdf_with_params(app_handle, params, output_path).await
+}
+
+// Staff groups
+
+#[command]
+pub async fn create_staff_group(
+    app_handle: AppHandle,
+    data: CreateStaffGroup,
+) -> Result<StaffGroup, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+    validate_name(&data.name)?;
+
+    db_create_staff_group(&app_data_dir, data)
+        .map_err(|e| format!("Failed to create staff group: {}", e))
+}
+
+#[command]
+pub async fn list_staff_groups(app_handle: AppHandle) -> Result<Vec<StaffGroup>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+
+    db_list_staff_groups(&app_data_dir)
+        .map_err(|e| format!("Failed to get staff groups: {}", e))
+}
+
+#[command]
+pub async fn update_staff_group(
+    app_handle: AppHandle,
+    data: UpdateStaffGroup,
+) -> Result<StaffGroup, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+    validate_name(&data.name)?;
+
+    db_update_staff_group(&app_data_dir, data)
+        .map_err(|e| format!("Failed to update staff group: {}", e))
+}
+
+#[command]
+pub async fn add_staff_to_group(
+    app_handle: AppHandle,
+    group_id: String,
+    staff_ids: Vec<String>,
+) -> Result<StaffGroup, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+
+    db_add_staff_to_group(&app_data_dir, &group_id, &staff_ids)
+        .map_err(|e| format!("Failed to add staff to group: {}", e))
+}
+
+#[command]
+pub async fn remove_staff_from_group(
+    app_handle: AppHandle,
+    group_id: String,
+    staff_ids: Vec<String>,
+) -> Result<StaffGroup, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+
+    db_remove_staff_from_group(&app_data_dir, &group_id, &staff_ids)
+        .map_err(|e| format!("Failed to remove staff from group: {}", e))
+}
+
+#[command]
+pub async fn delete_staff_group(app_handle: AppHandle, id: String) -> Result<(), String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+
+    db_delete_staff_group(&app_data_dir, &id)
+        .map_err(|e| format!("Failed to delete staff group: {}", e))
+}
+
+/// Members of a group, in the order they were added.
+#[command]
+pub async fn run_staff_group(app_handle: AppHandle, id: String) -> Result<Vec<StaffSummary>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Viewer)?;
+
+    let group = db_get_staff_group(&app_data_dir, &id)
+        .map_err(|e| format!("Failed to get staff group: {}", e))?;
+
+    db_get_staff_summaries_by_ids(&app_data_dir, &group.staff_ids)
+        .map(|staff| mask_for(&user, staff))
+        .map_err(|e| format!("Failed to get staff group members: {}", e))
+}
+
+#[command]
+pub async fn print_staff_group(
+    app_handle: AppHandle,
+    id: String,
+    output_path: String,
+) -> Result<String, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+
+    let group = db_get_staff_group(&app_data_dir, &id)
+        .map_err(|e| format!("Failed to get staff group: {}", e))?;
+
+    // An empty id list means "everyone" to generate_bulk_staff_pdf
+    if group.staff_ids.is_empty() {
+        return Err("This staff group has no members".to_string());
+    }
+
+    generate_bulk_staff_pdf(app_handle, group.staff_ids, output_path).await
+}
@@ -0,0 +1,74 @@
+use tauri::{command, AppHandle};
+use crate::auth::require_role;
+use crate::database::{
+    settings::{
+        get_office_settings as db_get_office_settings,
+        update_office_settings as db_update_office_settings,
+        set_office_logo as db_set_office_logo,
+    },
+    schema::{OfficeSettings, UpdateOfficeSettings, UserRole},
+};
+use crate::utils::{get_app_data_dir, photo::process_logo};
+
+fn required(value: String, field: &str) -> Result<String, String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(format!("{} is required", field));
+    }
+    Ok(value)
+}
+
+fn optional(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+#[command]
+pub async fn get_office_settings(app_handle: AppHandle) -> Result<OfficeSettings, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+
+    db_get_office_settings(&app_data_dir)
+        .map_err(|e| format!("Failed to get office settings: {}", e))
+}
+
+#[command]
+pub async fn update_office_settings(
+    app_handle: AppHandle,
+    data: UpdateOfficeSettings,
+) -> Result<OfficeSettings, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Admin)?;
+
+    let data = UpdateOfficeSettings {
+        office_name: required(data.office_name, "Office name")?,
+        division: required(data.division, "Division")?,
+        address: required(data.address, "Address")?,
+        phone: optional(data.phone),
+        certifying_officer_name: optional(data.certifying_officer_name),
+        certifying_officer_designation: required(data.certifying_officer_designation, "Certifying officer designation")?,
+    };
+
+    db_update_office_settings(&app_data_dir, data)
+        .map_err(|e| format!("Failed to update office settings: {}", e))
+}
+
+#[command]
+pub async fn set_office_logo(
+    app_handle: AppHandle,
+    image_data: String,
+) -> Result<OfficeSettings, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Admin)?;
+    let logo = process_logo(&image_data)?;
+
+    db_set_office_logo(&app_data_dir, Some(&logo))
+        .map_err(|e| format!("Failed to save office logo: {}", e))
+}
+
+#[command]
+pub async fn remove_office_logo(app_handle: AppHandle) -> Result<OfficeSettings, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Admin)?;
+
+    db_set_office_logo(&app_data_dir, None)
+        .map_err(|e| format!("Failed to remove office logo: {}", e))
+}
@@ -1,72 +1,542 @@
 use tauri::{command, AppHandle};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use uuid::Uuid;
+use crate::auth::{masking::mask_for, require_role};
 use crate::database::{
+  audit::{
+    record_audit_entry as db_record_audit_entry,
+    fields_changed_since as db_fields_changed_since,
+    patch_changes, staff_changes, AuditAction, NewAuditEntry,
+  },
   operations::{
     create_staff as db_create_staff,
-    get_all_staff as db_get_all_staff,
     get_staff_by_id as db_get_staff_by_id,
+    get_staff_by_ids as db_get_staff_by_ids,
     update_staff as db_update_staff,
-    delete_staff as db_delete_staff,
+    patch_staff as db_patch_staff,
+    patch_staff_batch as db_patch_staff_batch,
     search_staff as db_search_staff,
+    get_staff_summaries_by_ids as db_get_staff_summaries_by_ids,
+    delete_staff as db_delete_staff,
+    search_staff_summaries as db_search_staff_summaries,
     get_staff_by_nic as db_get_staff_by_nic,
+    get_staff_photo as db_get_staff_photo,
+    patch_fields, UpdateError,
+  },
+  schema::{
+    Staff, CreateStaff, UpdateStaff, StaffPatch, StaffSearchParams, StaffSummary, StaffPage, StaffPageParams, UserRole,
+    FieldChange, StaffBulkUpdateParams, StaffBulkPreview, StaffBulkPreviewRow, StaffBulkUpdateResult,
   },
-  schema::{Staff, CreateStaff, UpdateStaff, StaffSearchParams},
 };
-use crate::utils::get_app_data_dir;
+use crate::import::{is_valid_email, is_valid_phone, parse_increment_date, MARITAL_STATUSES};
+use crate::utils::{get_app_data_dir, nic::normalize_nic};
+use crate::utils::photo::process_photo;
+use chrono::NaiveDate;
+
+/// The change is already saved by now, so the message says so rather than
+/// suggesting it failed.
+fn record_audit(app_data_dir: &PathBuf, entry: NewAuditEntry) -> Result<(), String> {
+    db_record_audit_entry(app_data_dir, &entry)
+        .map_err(|e| format!("Saved, but failed to record audit entry: {}", e))
+}
+
+/// Refusal for an edit started from an older version of the record.
+fn conflict_error(app_data_dir: &PathBuf, id: &str, version: i64) -> String {
+    let fields = db_fields_changed_since(app_data_dir, "staff", id, version).unwrap_or_default();
+
+    if fields.is_empty() {
+        "This record was changed by someone else after you opened it; reload it and make your changes again"
+            .to_string()
+    } else {
+        let fields: Vec<String> = fields.iter().map(|field| field.replace('_', " ")).collect();
+        format!(
+            "This record was changed by someone else after you opened it ({}); reload it and make your changes again",
+            fields.join(", ")
+        )
+    }
+}
 
 #[command]
 pub async fn create_staff(
     app_handle: AppHandle,
-    staff_data: CreateStaff,
+    mut staff_data: CreateStaff,
 ) -> Result<Staff, String> {
     let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Clerk)?;
+
+    // Same rule as editing: a clerk adds the record and an administrator fills in the salary
+    let has_salary = !staff_data.salary_code.trim().is_empty()
+        || staff_data.basic_salary != 0.0
+        || staff_data.increment_amount != 0.0;
+    if has_salary {
+        require_role(&app_handle, UserRole::Admin)
+            .map_err(|_| "Only an administrator can enter salary details".to_string())?;
+    }
+
+    staff_data.image_data = match staff_data.image_data.filter(|data| !data.trim().is_empty()) {
+        Some(image_data) => Some(process_photo(&image_data)?),
+        None => None,
+    };
+
+    let staff = db_create_staff(&app_data_dir, staff_data)
+        .map_err(|e| format!("Failed to create staff: {}", e))?;
 
-    db_create_staff(&app_data_dir, staff_data)
-        .map_err(|e| format!("Failed to create staff: {}", e))
+    record_audit(
+        &app_data_dir,
+        NewAuditEntry::new(&user, AuditAction::StaffCreate)
+            .on("staff", &staff.id)
+            .with_description(format!("{} ({})", staff.full_name, staff.appointment_number)),
+    )?;
+
+    Ok(mask_for(&user, staff))
 }
 
 #[command]
-pub async fn get_all_staff(app_handle: AppHandle) -> Result<Vec<Staff>, String> {
+pub async fn get_all_staff(
+    app_handle: AppHandle,
+    page: Option<StaffPageParams>,
+) -> Result<StaffPage<StaffSummary>, String> {
     let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Viewer)?;
+    let page = mask_for(&user, page.unwrap_or_default());
 
-    db_get_all_staff(&app_data_dir)
+    db_search_staff_summaries(&app_data_dir, &StaffSearchParams::default(), &page)
+        .map(|staff| mask_for(&user, staff))
         .map_err(|e| format!("Failed to get staff: {}", e))
 }
 
 #[command]
 pub async fn get_staff_by_id(app_handle: AppHandle, id: String) -> Result<Staff, String> {
     let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Viewer)?;
 
     db_get_staff_by_id(&app_data_dir, &id)
+        .map(|staff| mask_for(&user, staff))
         .map_err(|e| format!("Failed to get staff by ID: {}", e))
 }
 
+#[command]
+pub async fn get_staff_photo(
+    app_handle: AppHandle,
+    staff_id: String,
+) -> Result<Option<String>, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    require_role(&app_handle, UserRole::Viewer)?;
+
+    db_get_staff_photo(&app_data_dir, &staff_id)
+        .map_err(|e| format!("Failed to get staff photo: {}", e))
+}
+
 #[command]
 pub async fn update_staff(
     app_handle: AppHandle,
-    staff_data: UpdateStaff,
+    mut staff_data: UpdateStaff,
 ) -> Result<Staff, String> {
     let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Clerk)?;
+
+    let before = db_get_staff_by_id(&app_data_dir, &staff_data.id)
+        .map_err(|e| format!("Failed to get staff by ID: {}", e))?;
+    if before.version != staff_data.version {
+        return Err(conflict_error(&app_data_dir, &staff_data.id, staff_data.version));
+    }
+
+    let salary_changed = staff_data.salary_code != before.salary_code
+        || staff_data.basic_salary != before.basic_salary
+        || staff_data.increment_amount != before.increment_amount;
+    if salary_changed {
+        require_role(&app_handle, UserRole::Admin)
+            .map_err(|_| "Only an administrator can change salary details".to_string())?;
+    }
+
+    // Resending the stored photo isn't a change, so the JPEG isn't recompressed
+    // on every save; only new uploads are processed
+    if let Some(Some(image_data)) = &staff_data.image_data {
+        if !image_data.trim().is_empty() {
+            let existing = db_get_staff_photo(&app_data_dir, &staff_data.id)
+                .map_err(|e| format!("Failed to get staff photo: {}", e))?;
+            staff_data.image_data = if existing.as_deref() == Some(image_data.as_str()) {
+                None
+            } else {
+                Some(Some(process_photo(image_data)?))
+            };
+        }
+    }
+
+    let staff = db_update_staff(&app_data_dir, staff_data).map_err(|e| match e {
+        // Saved by someone else between the check above and now
+        UpdateError::Conflict { id, version } => conflict_error(&app_data_dir, &id, version),
+        e => format!("Failed to update staff: {}", e),
+    })?;
+
+    let changes = staff_changes(&before, &staff);
+    if !changes.is_empty() {
+        record_audit(
+            &app_data_dir,
+            NewAuditEntry::new(&user, AuditAction::StaffUpdate)
+                .on("staff", &staff.id)
+                .at_version(staff.version)
+                .with_description(format!("{} ({})", staff.full_name, staff.appointment_number))
+                .with_changes(changes),
+        )?;
+    }
+
+    Ok(mask_for(&user, staff))
+}
+
+/// An empty optional value clears the field, the same as null.
+fn clear_if_blank(field: &mut Option<Option<String>>) {
+    if let Some(value) = field {
+        if value.as_deref().is_some_and(|value| value.trim().is_empty()) {
+            *field = Some(None);
+        }
+    }
+}
+
+/// A required field in a patch: `None` when the patch leaves it alone.
+fn required_value<'a>(field: &'a Option<Option<String>>, label: &str, errors: &mut Vec<String>) -> Option<&'a str> {
+    match field {
+        None => None,
+        Some(Some(value)) if !value.trim().is_empty() => Some(value.as_str()),
+        Some(_) => {
+            errors.push(format!("{} is required", label));
+            None
+        }
+    }
+}
+
+fn required_date(field: &Option<Option<String>>, label: &str, errors: &mut Vec<String>) -> Option<NaiveDate> {
+    let value = required_value(field, label, errors)?;
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+    if date.is_none() {
+        errors.push(format!("{} \"{}\" is not a valid date", label, value));
+    }
+    date
+}
+
+/// Tidy and check only the fields the patch sets. Checks that involve a
+/// second field use its stored value when the patch leaves it alone.
+fn validate_patch(patch: &mut StaffPatch, before: &Staff) -> Result<(), String> {
+    let mut errors = Vec::new();
+
+    for field in [
+        &mut patch.nic_number_old, &mut patch.address_line1, &mut patch.address_line2, &mut patch.address_line3,
+        &mut patch.contact_number, &mut patch.email, &mut patch.increment_date,
+    ] {
+        clear_if_blank(field);
+    }
+
+    required_value(&patch.appointment_number, "Appointment number", &mut errors);
+    required_value(&patch.full_name, "Full name", &mut errors);
+    required_value(&patch.designation, "Designation", &mut errors);
+    required_value(&patch.salary_code, "Salary code", &mut errors);
+
+    if let Some(nic) = required_value(&patch.nic_number, "NIC number", &mut errors) {
+        if let Err(e) = normalize_nic(nic) {
+            errors.push(e);
+        }
+    }
+    if let Some(gender) = required_value(&patch.gender, "Gender", &mut errors) {
+        if gender != "Male" && gender != "Female" {
+            errors.push(format!("Gender must be Male or Female, not \"{}\"", gender));
+        }
+    }
+    if let Some(status) = required_value(&patch.marital_status, "Marital status", &mut errors) {
+        if !MARITAL_STATUSES.contains(&status) {
+            errors.push(format!("Marital status must be one of {}, not \"{}\"", MARITAL_STATUSES.join(", "), status));
+        }
+    }
+
+    let date_of_birth = required_date(&patch.date_of_birth, "Date of birth", &mut errors);
+    let first_appointment = required_date(&patch.date_of_first_appointment, "Date of first appointment", &mut errors);
+    required_date(&patch.date_of_retirement, "Date of retirement", &mut errors);
+
+    if date_of_birth.is_some_and(|date| date > chrono::Local::now().date_naive()) {
+        errors.push("Date of birth cannot be in the future".to_string());
+    }
+    if date_of_birth.is_some() || first_appointment.is_some() {
+        let stored = |value: &str| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+        let date_of_birth = date_of_birth.or_else(|| stored(&before.date_of_birth));
+        let first_appointment = first_appointment.or_else(|| stored(&before.date_of_first_appointment));
+        if let (Some(date_of_birth), Some(first_appointment)) = (date_of_birth, first_appointment) {
+            if first_appointment <= date_of_birth {
+                errors.push("Date of first appointment must be after the date of birth".to_string());
+            }
+        }
+    }
+
+    match patch.age {
+        Some(Some(age)) if age < 0 => errors.push("Age cannot be negative".to_string()),
+        Some(None) => errors.push("Age is required".to_string()),
+        _ => {}
+    }
+    match patch.basic_salary {
+        Some(Some(amount)) if amount.is_finite() && amount > 0.0 => {}
+        Some(_) => errors.push("Basic salary must be greater than 0".to_string()),
+        None => {}
+    }
+    match patch.increment_amount {
+        Some(Some(amount)) if amount.is_finite() && amount >= 0.0 => {}
+        Some(Some(_)) => errors.push("Increment amount cannot be negative".to_string()),
+        // No increment is stored as zero
+        Some(None) => patch.increment_amount = Some(Some(0.0)),
+        None => {}
+    }
+
+    if let Some(Some(email)) = &patch.email {
+        if !is_valid_email(email) {
+            errors.push(format!("Invalid email format \"{}\"", email));
+        }
+    }
+    if let Some(Some(contact_number)) = &patch.contact_number {
+        if !is_valid_phone(contact_number) {
+            errors.push(format!("Invalid contact number format \"{}\"", contact_number));
+        }
+    }
+    if let Some(Some(increment_date)) = &mut patch.increment_date {
+        match parse_increment_date(increment_date) {
+            Some(normalized) => *increment_date = normalized,
+            None => errors.push(format!("Increment date \"{}\" must be in DD-MM format", increment_date)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+fn require_salary_access(app_handle: &AppHandle, changes: &[FieldChange]) -> Result<(), String> {
+    if changes.iter().any(|change| matches!(change.field.as_str(), "salary_code" | "basic_salary" | "increment_amount")) {
+        require_role(app_handle, UserRole::Admin)
+            .map_err(|_| "Only an administrator can change salary details".to_string())?;
+    }
+    Ok(())
+}
+
+/// Change just the fields given, rather than sending the whole record back.
+/// Only those fields are validated and recorded in the audit log.
+#[command]
+pub async fn patch_staff(app_handle: AppHandle, mut patch: StaffPatch) -> Result<Staff, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Clerk)?;
 
-    db_update_staff(&app_data_dir, staff_data)
-        .map_err(|e| format!("Failed to update staff: {}", e))
+    let before = db_get_staff_by_id(&app_data_dir, &patch.id)
+        .map_err(|e| format!("Failed to get staff by ID: {}", e))?;
+    if before.version != patch.version {
+        return Err(conflict_error(&app_data_dir, &patch.id, patch.version));
+    }
+
+    validate_patch(&mut patch, &before)?;
+
+    // Resending the stored photo isn't a change
+    if let Some(Some(image_data)) = &patch.image_data {
+        let existing = db_get_staff_photo(&app_data_dir, &patch.id)
+            .map_err(|e| format!("Failed to get staff photo: {}", e))?;
+        patch.image_data = if existing.as_deref() == Some(image_data.as_str()) {
+            None
+        } else {
+            Some(Some(process_photo(image_data)?))
+        };
+    }
+
+    let changes = patch_changes(&before, &patch);
+    if changes.is_empty() {
+        return Ok(mask_for(&user, before));
+    }
+    require_salary_access(&app_handle, &changes)?;
+
+    let staff = db_patch_staff(&app_data_dir, &patch).map_err(|e| match e {
+        UpdateError::Conflict { id, version } => conflict_error(&app_data_dir, &id, version),
+        e => format!("Failed to update staff: {}", e),
+    })?;
+
+    record_audit(
+        &app_data_dir,
+        NewAuditEntry::new(&user, AuditAction::StaffUpdate)
+            .on("staff", &staff.id)
+            .at_version(staff.version)
+            .with_description(format!("{} ({})", staff.full_name, staff.appointment_number))
+            .with_changes(changes),
+    )?;
+
+    Ok(mask_for(&user, staff))
+}
+
+/// What a bulk edit would do: each record it changes, with its patch and
+/// the changes that makes, and how many already have the new values.
+struct BulkEdit {
+    rows: Vec<(Staff, StaffPatch, Vec<FieldChange>)>,
+    unchanged: usize,
+}
+
+fn plan_bulk_update(
+    app_handle: &AppHandle,
+    app_data_dir: &PathBuf,
+    params: &StaffBulkUpdateParams,
+) -> Result<BulkEdit, String> {
+    let changes = &params.changes;
+    let template = StaffPatch {
+        designation: changes.designation.clone(),
+        salary_code: changes.salary_code.clone(),
+        increment_date: changes.increment_date.clone(),
+        address_line1: changes.address_line1.clone(),
+        address_line2: changes.address_line2.clone(),
+        address_line3: changes.address_line3.clone(),
+        ..Default::default()
+    };
+    if patch_fields(&template).is_empty() {
+        return Err("Choose at least one field to change".to_string());
+    }
+    // Guard against an empty selection quietly meaning everyone
+    if params.staff_ids.is_empty() && params.filters.is_none() {
+        return Err("Choose the staff to update".to_string());
+    }
+
+    // Chosen records are loaded directly; the filters only pick them when none are chosen
+    let staff_list = if params.staff_ids.is_empty() {
+        let filters = params.filters.clone().unwrap_or_default();
+        db_search_staff(app_data_dir, &filters, Default::default(), Default::default())
+            .map_err(|e| format!("Failed to search staff: {}", e))?
+    } else {
+        db_get_staff_by_ids(app_data_dir, &params.staff_ids)
+            .map_err(|e| format!("Failed to get staff: {}", e))?
+    };
+
+    let mut edit = BulkEdit { rows: Vec::new(), unchanged: 0 };
+    for staff in staff_list {
+        let mut patch = StaffPatch { id: staff.id.clone(), version: staff.version, ..template.clone() };
+        validate_patch(&mut patch, &staff)
+            .map_err(|e| format!("{} ({}): {}", staff.full_name, staff.appointment_number, e))?;
+        let changes = patch_changes(&staff, &patch);
+        if changes.is_empty() {
+            edit.unchanged += 1;
+        } else {
+            edit.rows.push((staff, patch, changes));
+        }
+    }
+
+    for (_, _, changes) in &edit.rows {
+        require_salary_access(app_handle, changes)?;
+    }
+
+    Ok(edit)
+}
+
+/// The records a bulk edit would change and how, without changing anything.
+#[command]
+pub async fn preview_bulk_staff_update(
+    app_handle: AppHandle,
+    params: StaffBulkUpdateParams,
+) -> Result<StaffBulkPreview, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Clerk)?;
+
+    let edit = plan_bulk_update(&app_handle, &app_data_dir, &params)?;
+
+    let ids: Vec<String> = edit.rows.iter().map(|(staff, _, _)| staff.id.clone()).collect();
+    let mut summaries: HashMap<String, StaffSummary> = db_get_staff_summaries_by_ids(&app_data_dir, &ids)
+        .map_err(|e| format!("Failed to get staff summaries: {}", e))?
+        .into_iter()
+        .map(|summary| (summary.id.clone(), summary))
+        .collect();
+
+    let rows = edit
+        .rows
+        .into_iter()
+        .filter_map(|(staff, _, changes)| {
+            summaries.remove(&staff.id).map(|summary| StaffBulkPreviewRow { staff: summary, changes })
+        })
+        .collect();
+
+    Ok(mask_for(&user, StaffBulkPreview { rows, unchanged: edit.unchanged }))
+}
+
+/// Apply the same changes to many records in one go. Either every record is
+/// updated or none are. Pass the previewed ids as `staff_ids` so the edit
+/// covers exactly the records that were shown.
+#[command]
+pub async fn bulk_update_staff(
+    app_handle: AppHandle,
+    params: StaffBulkUpdateParams,
+) -> Result<StaffBulkUpdateResult, String> {
+    let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Clerk)?;
+
+    let edit = plan_bulk_update(&app_handle, &app_data_dir, &params)?;
+    if edit.rows.is_empty() {
+        return Err("None of the chosen records need changing".to_string());
+    }
+
+    let batch_id = Uuid::new_v4().to_string();
+    let mut fields: Vec<&str> = Vec::new();
+    for change in edit.rows.iter().flat_map(|(_, _, changes)| changes) {
+        if !fields.contains(&change.field.as_str()) {
+            fields.push(&change.field);
+        }
+    }
+
+    let mut entries = vec![
+        NewAuditEntry::new(&user, AuditAction::StaffBulkUpdate)
+            .in_batch(&batch_id)
+            .with_description(format!("Updated {} staff records: {}", edit.rows.len(), fields.join(", "))),
+    ];
+    for (staff, _, changes) in &edit.rows {
+        entries.push(
+            NewAuditEntry::new(&user, AuditAction::StaffUpdate)
+                .on("staff", &staff.id)
+                .at_version(staff.version + 1)
+                .in_batch(&batch_id)
+                .with_description(format!("{} ({})", staff.full_name, staff.appointment_number))
+                .with_changes(changes.clone()),
+        );
+    }
+
+    let patches: Vec<StaffPatch> = edit.rows.into_iter().map(|(_, patch, _)| patch).collect();
+    db_patch_staff_batch(&app_data_dir, &patches, &entries).map_err(|e| match e {
+        UpdateError::Conflict { .. } => {
+            "Some of these records were changed by someone else in the meantime; preview the update again".to_string()
+        }
+        e => format!("Failed to update staff: {}", e),
+    })?;
+
+    Ok(StaffBulkUpdateResult { batch_id, updated: patches.len(), unchanged: edit.unchanged })
 }
 
 #[command]
 pub async fn delete_staff(app_handle: AppHandle, id: String) -> Result<(), String> {
     let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Admin)?;
+
+    let staff = db_get_staff_by_id(&app_data_dir, &id)
+        .map_err(|e| format!("Failed to get staff by ID: {}", e))?;
 
     db_delete_staff(&app_data_dir, &id)
-        .map_err(|e| format!("Failed to delete staff: {}", e))
+        .map_err(|e| format!("Failed to delete staff: {}", e))?;
+
+    record_audit(
+        &app_data_dir,
+        NewAuditEntry::new(&user, AuditAction::StaffDelete)
+            .on("staff", &id)
+            .with_description(format!("{} ({})", staff.full_name, staff.appointment_number)),
+    )
 }
 
 #[command]
 pub async fn search_staff(
     app_handle: AppHandle,
     params: StaffSearchParams,
-) -> Result<Vec<Staff>, String> {
+    page: Option<StaffPageParams>,
+) -> Result<StaffPage<StaffSummary>, String> {
     let app_data_dir = get_app_data_dir(&app_handle)?;
+    let user = require_role(&app_handle, UserRole::Viewer)?;
+    let params = mask_for(&user, params);
+    let page = mask_for(&user, page.unwrap_or_default());
 
-    db_search_staff(&app_data_dir, params)
+    db_search_staff_summaries(&app_data_dir, &params, &page)
+        .map(|staff| mask_for(&user, staff))
         .map_err(|e| format!("Failed to search staff: {}", e))
 }
 
@@ -76,7 +546,80 @@ pub async fn get_staff_by_nic(
     nic: String,
 ) -> Result<Option<Staff>, String> {
     let app_data_dir = get_app_data_dir(&app_handle)?;
+    // Looking a record up by NIC would tell a viewer whose number it is
+    require_role(&app_handle, UserRole::Clerk)?;
 
     db_get_staff_by_nic(&app_data_dir, &nic)
         .map_err(|e| format!("Failed to get staff by NIC: {}", e))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{insert_staff, test_database, user};
+
+    /// A patch for `staff` as the app sends it.
+    fn patch(staff: &Staff, mut fields: serde_json::Value) -> StaffPatch {
+        fields["id"] = staff.id.clone().into();
+        fields["version"] = staff.version.into();
+        serde_json::from_value(fields).unwrap()
+    }
+
+    #[test]
+    fn patch_validation_tidies_only_the_fields_given() {
+        let dir = test_database();
+        let before = insert_staff(&dir, 1);
+
+        let mut staff_patch = patch(&before, serde_json::json!({ "email": "  ", "increment_date": "5/3/2024" }));
+        validate_patch(&mut staff_patch, &before).unwrap();
+
+        assert_eq!(staff_patch.email, Some(None));
+        assert_eq!(staff_patch.increment_date, Some(Some("05-03".to_string())));
+        assert_eq!(staff_patch.full_name, None);
+        assert_eq!(patch_fields(&staff_patch).len(), 2);
+    }
+
+    #[test]
+    fn patch_validation_checks_dates_against_the_stored_record() {
+        let dir = test_database();
+        let before = insert_staff(&dir, 1);
+
+        // Stored date of birth is 1980-05-01
+        let mut staff_patch = patch(&before, serde_json::json!({ "date_of_first_appointment": "1979-01-01" }));
+        let error = validate_patch(&mut staff_patch, &before).unwrap_err();
+        assert!(error.contains("must be after the date of birth"), "{}", error);
+
+        let mut staff_patch = patch(&before, serde_json::json!({ "full_name": "", "basic_salary": 0 }));
+        let error = validate_patch(&mut staff_patch, &before).unwrap_err();
+        assert!(error.contains("Full name is required"), "{}", error);
+        assert!(error.contains("Basic salary must be greater than 0"), "{}", error);
+    }
+
+    #[test]
+    fn a_conflict_names_the_fields_changed_since_the_version_read() {
+        let dir = test_database();
+        let app_data_dir = dir.path().to_path_buf();
+        let staff = insert_staff(&dir, 1);
+
+        let change = |field: &str| FieldChange {
+            field: field.to_string(),
+            old_value: serde_json::Value::Null,
+            new_value: "changed".into(),
+        };
+        let clerk = user(UserRole::Clerk);
+        for (version, fields) in [(2, vec!["designation"]), (3, vec!["contact_number", "designation"])] {
+            let entry = NewAuditEntry::new(&clerk, AuditAction::StaffUpdate)
+                .on("staff", &staff.id)
+                .at_version(version)
+                .with_changes(fields.into_iter().map(change).collect());
+            db_record_audit_entry(&app_data_dir, &entry).unwrap();
+        }
+
+        let error = conflict_error(&app_data_dir, &staff.id, 1);
+        assert!(error.contains("(designation, contact number)"), "{}", error);
+        let error = conflict_error(&app_data_dir, &staff.id, 2);
+        assert!(error.contains("(contact number, designation)"), "{}", error);
+        let error = conflict_error(&app_data_dir, &staff.id, 3);
+        assert!(!error.contains('('), "{}", error);
+    }
+}
@@ -0,0 +1,279 @@
+// src/database/audit.rs
+//
+// The audit log: who changed what, and when.
+use rusqlite::{Connection, Result, Row, params};
+use rusqlite::types::Type;
+use serde_json::Value;
+use std::path::PathBuf;
+use crate::database::operations::{get_connection, parse_datetime_from_row, patch_fields};
+use crate::database::schema::{AuditEntry, AuditLogParams, FieldChange, Staff, StaffPatch, User};
+
+const DEFAULT_LIMIT: u32 = 100;
+const MAX_LIMIT: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Login,
+    LoginFailed,
+    Logout,
+    SessionUnlock,
+    StaffCreate,
+    StaffUpdate,
+    StaffBulkUpdate,
+    StaffDelete,
+    StaffImport,
+    DatabaseImport,
+    BackupRestore,
+    UserCreate,
+    UserUpdate,
+    PasswordChange,
+}
+
+impl AuditAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditAction::Login => "login",
+            AuditAction::LoginFailed => "login_failed",
+            AuditAction::Logout => "logout",
+            AuditAction::SessionUnlock => "session_unlock",
+            AuditAction::StaffCreate => "staff_create",
+            AuditAction::StaffUpdate => "staff_update",
+            AuditAction::StaffBulkUpdate => "staff_bulk_update",
+            AuditAction::StaffDelete => "staff_delete",
+            AuditAction::StaffImport => "staff_import",
+            AuditAction::DatabaseImport => "database_import",
+            AuditAction::BackupRestore => "backup_restore",
+            AuditAction::UserCreate => "user_create",
+            AuditAction::UserUpdate => "user_update",
+            AuditAction::PasswordChange => "password_change",
+        }
+    }
+}
+
+/// An entry about to be written; the id and time are filled in on insert.
+#[derive(Debug, Clone)]
+pub struct NewAuditEntry {
+    pub user_id: Option<String>,
+    pub username: String,
+    pub action: AuditAction,
+    pub entity_type: Option<&'static str>,
+    pub entity_id: Option<String>,
+    pub entity_version: Option<i64>,
+    pub description: Option<String>,
+    pub changes: Vec<FieldChange>,
+    pub batch_id: Option<String>,
+}
+
+impl NewAuditEntry {
+    pub fn new(user: &User, action: AuditAction) -> Self {
+        NewAuditEntry {
+            user_id: Some(user.id.clone()),
+            username: user.username.clone(),
+            action,
+            entity_type: None,
+            entity_id: None,
+            entity_version: None,
+            description: None,
+            changes: Vec::new(),
+            batch_id: None,
+        }
+    }
+
+    pub fn on(mut self, entity_type: &'static str, entity_id: &str) -> Self {
+        self.entity_type = Some(entity_type);
+        self.entity_id = Some(entity_id.to_string());
+        self
+    }
+
+    /// Record the version of the entity after this change.
+    pub fn at_version(mut self, version: i64) -> Self {
+        self.entity_version = Some(version);
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_changes(mut self, changes: Vec<FieldChange>) -> Self {
+        self.changes = changes;
+        self
+    }
+
+    /// Mark the entry as part of a bulk edit.
+    pub fn in_batch(mut self, batch_id: &str) -> Self {
+        self.batch_id = Some(batch_id.to_string());
+        self
+    }
+}
+
+/// Write an entry on an open connection, so it can share a transaction with the change it records.
+pub fn insert_audit_entry(conn: &Connection, entry: &NewAuditEntry) -> Result<()> {
+    let changes = serde_json::to_string(&entry.changes)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+    conn.execute(
+        r#"
+        INSERT INTO audit_log (
+            user_id, username, action, entity_type, entity_id, entity_version, description, changes, batch_id,
+            created_at
+        )
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+        "#,
+        params![
+            entry.user_id, entry.username, entry.action.as_str(), entry.entity_type, entry.entity_id,
+            entry.entity_version, entry.description, changes, entry.batch_id, chrono::Utc::now().to_rfc3339()
+        ],
+    )?;
+
+    Ok(())
+}
+
+pub fn record_audit_entry(app_data_dir: &PathBuf, entry: &NewAuditEntry) -> Result<()> {
+    let conn = get_connection(app_data_dir)?;
+    insert_audit_entry(&conn, entry)
+}
+
+fn parse_changes(col_index: usize, changes_json: &str) -> Result<Vec<FieldChange>> {
+    serde_json::from_str(changes_json)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(col_index, Type::Text, Box::new(e)))
+}
+
+fn map_audit_row(row: &Row) -> Result<AuditEntry> {
+    let changes = parse_changes(8, &row.get::<_, String>(8)?)?;
+
+    Ok(AuditEntry {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        username: row.get(2)?,
+        action: row.get(3)?,
+        entity_type: row.get(4)?,
+        entity_id: row.get(5)?,
+        entity_version: row.get(6)?,
+        description: row.get(7)?,
+        changes,
+        batch_id: row.get(10)?,
+        created_at: parse_datetime_from_row(9, row.get(9)?)?,
+    })
+}
+
+/// Entries matching every given filter, newest first.
+pub fn list_audit_log(app_data_dir: &PathBuf, filters: &AuditLogParams) -> Result<Vec<AuditEntry>> {
+    let conn = get_connection(app_data_dir)?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, user_id, username, action, entity_type, entity_id, entity_version, description, changes, created_at,
+            batch_id
+        FROM audit_log
+        WHERE (?1 IS NULL OR entity_type = ?1)
+          AND (?2 IS NULL OR entity_id = ?2)
+          AND (?3 IS NULL OR user_id = ?3)
+          AND (?6 IS NULL OR batch_id = ?6)
+        ORDER BY id DESC
+        LIMIT ?4 OFFSET ?5
+        "#,
+    )?;
+
+    let entries = stmt
+        .query_map(
+            params![
+                filters.entity_type, filters.entity_id, filters.user_id,
+                filters.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT), filters.offset.unwrap_or(0),
+                filters.batch_id
+            ],
+            map_audit_row,
+        )?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(entries)
+}
+
+/// Names of the fields recorded as changed on an entity after `version`,
+/// in the order they were first changed. Changes that didn't record a
+/// version, such as imports, aren't included.
+pub fn fields_changed_since(
+    app_data_dir: &PathBuf,
+    entity_type: &str,
+    entity_id: &str,
+    version: i64,
+) -> Result<Vec<String>> {
+    let conn = get_connection(app_data_dir)?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT changes FROM audit_log
+        WHERE entity_type = ?1 AND entity_id = ?2 AND entity_version > ?3
+        ORDER BY id
+        "#,
+    )?;
+
+    let mut fields: Vec<String> = Vec::new();
+    let mut rows = stmt.query(params![entity_type, entity_id, version])?;
+    while let Some(row) = rows.next()? {
+        for change in parse_changes(0, &row.get::<_, String>(0)?)? {
+            if !fields.contains(&change.field) {
+                fields.push(change.field);
+            }
+        }
+    }
+
+    Ok(fields)
+}
+
+/// Fields that differ between two versions of a staff record. A new or
+/// removed photo is recorded as a change to `photo`, without the image.
+pub fn staff_changes(before: &Staff, after: &Staff) -> Vec<FieldChange> {
+    const SKIPPED: &[&str] = &["id", "has_photo", "photo_thumbnail", "created_at", "updated_at", "version", "masked"];
+
+    let (Ok(Value::Object(before_fields)), Ok(Value::Object(after_fields))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return Vec::new();
+    };
+
+    let mut changes: Vec<FieldChange> = before_fields
+        .into_iter()
+        .filter(|(field, _)| !SKIPPED.contains(&field.as_str()))
+        .filter_map(|(field, old_value)| {
+            let new_value = after_fields.get(&field).cloned().unwrap_or(Value::Null);
+            (old_value != new_value).then_some(FieldChange { field, old_value, new_value })
+        })
+        .collect();
+
+    if before.photo_thumbnail != after.photo_thumbnail {
+        changes.push(FieldChange {
+            field: "photo".to_string(),
+            old_value: Value::Bool(before.has_photo),
+            new_value: Value::Bool(after.has_photo),
+        });
+    }
+
+    changes
+}
+
+/// The changes a patch makes to `before`, covering only the fields it sets.
+pub fn patch_changes(before: &Staff, patch: &StaffPatch) -> Vec<FieldChange> {
+    let Ok(Value::Object(before_fields)) = serde_json::to_value(before) else {
+        return Vec::new();
+    };
+
+    let mut changes: Vec<FieldChange> = patch_fields(patch)
+        .into_iter()
+        .filter_map(|(field, new_value)| {
+            let old_value = before_fields.get(&field).cloned().unwrap_or(Value::Null);
+            (old_value != new_value).then_some(FieldChange { field, old_value, new_value })
+        })
+        .collect();
+
+    if let Some(image_data) = &patch.image_data {
+        changes.push(FieldChange {
+            field: "photo".to_string(),
+            old_value: Value::Bool(before.has_photo),
+            new_value: Value::Bool(image_data.is_some()),
+        });
+    }
+
+    changes
+}
@@ -0,0 +1,134 @@
+//! Optional SQLCipher encryption of the staff database.
+//!
+//! An encrypted database is unlocked with a passphrase entered at startup.
+//! The passphrase is kept in memory only, and every connection opened through
+//! `open_database_file` is keyed with it, so the rest of the database code
+//! works the same either way. A plain database is opened without a key.
+use rusqlite::{params, Connection, DatabaseName, ErrorCode, OpenFlags, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use crate::database::operations::{get_connection, get_database_path};
+use crate::database::schema::EncryptionStatus;
+
+static PASSPHRASE: RwLock<Option<String>> = RwLock::new(None);
+
+fn passphrase() -> Option<String> {
+    PASSPHRASE.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+fn set_passphrase(passphrase: Option<String>) {
+    *PASSPHRASE.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = passphrase;
+}
+
+/// Open a database file keyed with the passphrase, if the database is encrypted.
+/// Backups are copied through this too, so they are encrypted like the original.
+pub fn open_database_file(path: &Path, flags: OpenFlags) -> Result<Connection> {
+    let conn = Connection::open_with_flags(path, flags)?;
+    if let Some(passphrase) = passphrase() {
+        conn.pragma_update(None, "key", passphrase)?;
+    }
+    Ok(conn)
+}
+
+/// Whether the connection can read the file, i.e. it isn't encrypted or the key is right.
+fn can_read(conn: &Connection) -> Result<bool> {
+    match conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0)) {
+        Ok(_) => Ok(true),
+        Err(rusqlite::Error::SqliteFailure(e, _)) if e.code == ErrorCode::NotADatabase => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn is_encrypted(app_data_dir: &PathBuf) -> Result<bool> {
+    let db_path = get_database_path(app_data_dir);
+    if !db_path.exists() {
+        return Ok(false);
+    }
+
+    let conn = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    Ok(!can_read(&conn)?)
+}
+
+/// Whether a passphrase is in use, so backups taken before encryption can't be read.
+pub fn is_keyed() -> bool {
+    passphrase().is_some()
+}
+
+pub fn get_encryption_status(app_data_dir: &PathBuf) -> Result<EncryptionStatus> {
+    let encrypted = is_encrypted(app_data_dir)?;
+    Ok(EncryptionStatus { encrypted, unlocked: !encrypted || is_keyed() })
+}
+
+/// Try a passphrase against the database; it is kept for later connections
+/// only if it opens it. Returns whether it did.
+pub fn unlock_database(app_data_dir: &PathBuf, passphrase: &str) -> Result<bool> {
+    let conn = Connection::open_with_flags(get_database_path(app_data_dir), OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    conn.pragma_update(None, "key", passphrase)?;
+    if !can_read(&conn)? {
+        return Ok(false);
+    }
+
+    set_passphrase(Some(passphrase.to_string()));
+    Ok(true)
+}
+
+/// Encrypt a plain database in place. The data is exported into an encrypted
+/// copy, which then replaces the original file.
+pub fn encrypt_database(app_data_dir: &PathBuf, passphrase: &str) -> std::result::Result<(), String> {
+    if !cfg!(feature = "sqlcipher") {
+        return Err("This copy of the application was built without encryption support".to_string());
+    }
+
+    let db_path = get_database_path(app_data_dir);
+    let encrypted_path = app_data_dir.join("staff_database.db.encrypting");
+    let _ = fs::remove_file(&encrypted_path);
+
+    let result = export_encrypted(app_data_dir, &encrypted_path, passphrase)
+        .map_err(|e| format!("Failed to encrypt database: {}", e))
+        .and_then(|_| {
+            fs::rename(&encrypted_path, &db_path)
+                .map_err(|e| format!("Failed to replace database with the encrypted copy: {}", e))
+        });
+
+    match result {
+        Ok(()) => {
+            set_passphrase(Some(passphrase.to_string()));
+            Ok(())
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&encrypted_path);
+            Err(e)
+        }
+    }
+}
+
+fn export_encrypted(app_data_dir: &PathBuf, encrypted_path: &Path, passphrase: &str) -> Result<()> {
+    let conn = get_connection(app_data_dir)?;
+    // sqlcipher_export copies the schema and data but not the migration counter
+    let version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    conn.execute(
+        "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+        params![encrypted_path.to_string_lossy(), passphrase],
+    )?;
+    conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))?;
+    conn.pragma_update(Some(DatabaseName::Attached("encrypted")), "user_version", version)?;
+    conn.execute("DETACH DATABASE encrypted", [])?;
+
+    Ok(())
+}
+
+/// Re-encrypt the database under a new passphrase. Returns false, changing
+/// nothing, if `current` isn't the passphrase in use.
+pub fn change_passphrase(app_data_dir: &PathBuf, current: &str, new: &str) -> Result<bool> {
+    if passphrase().as_deref() != Some(current) {
+        return Ok(false);
+    }
+
+    let conn = get_connection(app_data_dir)?;
+    conn.pragma_update(None, "rekey", new)?;
+    set_passphrase(Some(new.to_string()));
+
+    Ok(true)
+}
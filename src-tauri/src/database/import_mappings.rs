@@ -0,0 +1,86 @@
+// src/database/import_mappings.rs
+//
+// Saved column mappings for staff imports.
+use rusqlite::{Result, Row, params};
+use rusqlite::types::Type;
+use std::path::PathBuf;
+use uuid::Uuid;
+use crate::database::operations::{get_connection, parse_datetime_from_row};
+use crate::database::schema::{ImportMapping, CreateImportMapping, UpdateImportMapping, ImportColumnMapping};
+
+fn columns_to_json(columns: &[ImportColumnMapping]) -> Result<String> {
+    serde_json::to_string(columns).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+fn map_import_mapping_row(row: &Row) -> Result<ImportMapping> {
+    let columns_json: String = row.get(2)?;
+    let columns = serde_json::from_str(&columns_json)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, Type::Text, Box::new(e)))?;
+
+    Ok(ImportMapping {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        columns,
+        created_at: parse_datetime_from_row(3, row.get(3)?)?,
+        updated_at: parse_datetime_from_row(4, row.get(4)?)?,
+    })
+}
+
+pub fn create_import_mapping(app_data_dir: &PathBuf, data: CreateImportMapping) -> Result<ImportMapping> {
+    let conn = get_connection(app_data_dir)?;
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO import_mappings (id, name, columns, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![id, data.name.trim(), columns_to_json(&data.columns)?, now, now],
+    )?;
+
+    get_import_mapping(app_data_dir, &id)
+}
+
+pub fn get_import_mapping(app_data_dir: &PathBuf, id: &str) -> Result<ImportMapping> {
+    let conn = get_connection(app_data_dir)?;
+
+    conn.query_row(
+        "SELECT id, name, columns, created_at, updated_at FROM import_mappings WHERE id = ?1",
+        [id],
+        map_import_mapping_row,
+    )
+}
+
+pub fn list_import_mappings(app_data_dir: &PathBuf) -> Result<Vec<ImportMapping>> {
+    let conn = get_connection(app_data_dir)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, name, columns, created_at, updated_at FROM import_mappings ORDER BY name COLLATE NOCASE",
+    )?;
+
+    let mappings = stmt
+        .query_map([], map_import_mapping_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(mappings)
+}
+
+pub fn update_import_mapping(app_data_dir: &PathBuf, data: UpdateImportMapping) -> Result<ImportMapping> {
+    let conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let updated = conn.execute(
+        "UPDATE import_mappings SET name = ?2, columns = ?3, updated_at = ?4 WHERE id = ?1",
+        params![data.id, data.name.trim(), columns_to_json(&data.columns)?, now],
+    )?;
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+
+    get_import_mapping(app_data_dir, &data.id)
+}
+
+pub fn delete_import_mapping(app_data_dir: &PathBuf, id: &str) -> Result<()> {
+    let conn = get_connection(app_data_dir)?;
+
+    conn.execute("DELETE FROM import_mappings WHERE id = ?1", params![id])?;
+
+    Ok(())
+}
@@ -1,2 +1,13 @@
 pub mod schema;
-pub mod operations;
\ No newline at end of file
+pub mod operations;
+pub mod saved_searches;
+pub mod settings;
+pub mod import_mappings;
+pub mod transfer;
+pub mod encryption;
+pub mod users;
+pub mod audit;
+pub mod timestamp_checks;
+
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -1,46 +1,183 @@
 // src/database/operations.rs
-use rusqlite::{Connection, Result, params};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, Result, Row, params};
 use rusqlite::types::Type;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
-use crate::database::schema::{Staff, CreateStaff, UpdateStaff, StaffSearchParams};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use crate::database::schema::{
+    Staff, CreateStaff, UpdateStaff, StaffSearchParams, StaffSummary, StaffPage, StaffPageParams,
+    StaffSortColumn, SortDirection, SnippetSegment, StaffIdentity, StaffPatch,
+};
+use crate::database::audit::{insert_audit_entry, NewAuditEntry};
+use crate::database::encryption::open_database_file;
+use crate::database::timestamp_checks::timestamp_check_sql;
+use crate::utils::photo::make_thumbnail;
+
+/// A schema migration: a SQL file, or SQL built in Rust where a file would
+/// repeat the same statement for many tables.
+enum Migration {
+    Sql(&'static str),
+    Generated(fn() -> String),
+}
+
+/// Schema migrations in order. `PRAGMA user_version` records how many have been applied.
+const MIGRATIONS: &[Migration] = &[
+    Migration::Sql(include_str!("../../migrations/001_initial.sql")),
+    Migration::Sql(include_str!("../../migrations/002_staff_photos.sql")),
+    Migration::Sql(include_str!("../../migrations/003_staff_fts.sql")),
+    Migration::Sql(include_str!("../../migrations/004_saved_searches.sql")),
+    Migration::Sql(include_str!("../../migrations/005_office_settings.sql")),
+    Migration::Sql(include_str!("../../migrations/006_import_mappings.sql")),
+    Migration::Sql(include_str!("../../migrations/007_backup_settings.sql")),
+    Migration::Sql(include_str!("../../migrations/008_users_and_audit.sql")),
+    Migration::Sql(include_str!("../../migrations/009_security_settings.sql")),
+    Migration::Sql(include_str!("../../migrations/010_staff_version.sql")),
+    Migration::Sql(include_str!("../../migrations/011_audit_batches.sql")),
+    Migration::Sql(include_str!("../../migrations/012_timestamp_format.sql")),
+    Migration::Sql(include_str!("../../migrations/013_staff_fts_keys.sql")),
+    // 014: RFC3339 checks on every timestamp column
+    Migration::Generated(timestamp_check_sql),
+];
+
+/// The `user_version` of a fully migrated database.
+pub fn schema_version() -> u32 {
+    MIGRATIONS.len() as u32
+}
+
+/// Column list shared by every query that returns a full `Staff` row (see `map_staff_row`).
+const STAFF_SELECT: &str = r#"
+        SELECT s.id, s.appointment_number, s.full_name, s.gender, s.date_of_birth, s.age,
+               s.nic_number, s.nic_number_old, s.marital_status, s.address_line1, s.address_line2, s.address_line3,
+               s.contact_number, s.email, s.designation, s.date_of_first_appointment, s.date_of_retirement,
+               s.increment_date, s.salary_code, s.basic_salary, s.increment_amount,
+               p.thumbnail_data, p.staff_id IS NOT NULL,
+               s.created_at, s.updated_at, s.version
+        FROM staff s
+        LEFT JOIN staff_photos p ON p.staff_id = s.id
+"#;
+
+/// Column list for `StaffSummary` rows (see `map_staff_summary_row`).
+const STAFF_SUMMARY_COLUMNS: &str = r#"
+               s.id, s.appointment_number, s.full_name, s.gender, s.age, s.nic_number, s.nic_number_old,
+               s.designation, s.contact_number, s.email, s.salary_code, s.basic_salary,
+               p.staff_id IS NOT NULL, p.thumbnail_data
+"#;
+
+const STAFF_FROM: &str = "FROM staff s LEFT JOIN staff_photos p ON p.staff_id = s.id";
+
+/// bm25 weights for the staff_fts columns (staff_id, full_name, appointment_number,
+/// nic_number, nic_number_old, designation, address, email).
+const FTS_RANK: &str = "bm25(staff_fts, 0.0, 10.0, 5.0, 5.0, 5.0, 2.0, 1.0, 1.0)";
+
+/// staff_fts columns a search may match when the searcher can't see NIC
+/// numbers or addresses; matching those would let them be guessed a prefix
+/// at a time.
+const FTS_PUBLIC_COLUMNS: &str = "full_name appointment_number designation email";
+
+/// Markers passed to snippet(); control characters so they can't clash with real data.
+const SNIPPET_START: char = '\u{2}';
+const SNIPPET_END: char = '\u{3}';
+
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+pub const MAX_PAGE_SIZE: u32 = 500;
 
 pub fn get_database_path(app_data_dir: &PathBuf) -> PathBuf {
     app_data_dir.join("staff_database.db")
 }
 
 pub fn initialize_database(app_data_dir: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    let db_path = get_database_path(app_data_dir);
-    let conn = Connection::open(&db_path)?;
+    let mut conn = get_connection(app_data_dir)?;
+
+    run_migrations(&mut conn)?;
+    backfill_photo_thumbnails(&conn)?;
+
+    Ok(())
+}
 
-    // Read and execute migration
-    let migration_sql = include_str!("../../migrations/001_initial.sql");
-    conn.execute_batch(migration_sql)?;
+pub(crate) fn run_migrations(conn: &mut Connection) -> Result<()> {
+    let applied: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+        let tx = conn.transaction()?;
+        match migration {
+            Migration::Sql(sql) => tx.execute_batch(sql)?,
+            Migration::Generated(build) => tx.execute_batch(&build())?,
+        }
+        tx.pragma_update(None, "user_version", index + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+/// Generate thumbnails for photos that don't have one yet (e.g. ones moved by migration 002).
+fn backfill_photo_thumbnails(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("SELECT staff_id, image_data FROM staff_photos WHERE thumbnail_data IS NULL")?;
+    let pending = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>>>()?;
+
+    for (staff_id, image_data) in pending {
+        // Undecodable images keep a NULL thumbnail; the full photo is still served
+        if let Ok(thumbnail) = make_thumbnail(&image_data) {
+            conn.execute(
+                "UPDATE staff_photos SET thumbnail_data = ?1 WHERE staff_id = ?2",
+                params![thumbnail, staff_id],
+            )?;
+        }
+    }
 
     Ok(())
 }
 
 pub fn get_connection(app_data_dir: &PathBuf) -> Result<Connection> {
     let db_path = get_database_path(app_data_dir);
-    Connection::open(db_path)
+    let conn = open_database_file(&db_path, OpenFlags::default())?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    Ok(conn)
 }
 
 pub fn create_staff(app_data_dir: &PathBuf, staff_data: CreateStaff) -> Result<Staff> {
-    let conn = get_connection(app_data_dir)?;
-    let id = Uuid::new_v4().to_string();
+    let mut conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.transaction()?;
+    let id = insert_staff(&tx, &staff_data, &now)?;
+    tx.commit()?;
+
+    get_staff_by_id(app_data_dir, &id)
+}
+
+/// Insert several staff members in one transaction; nothing is saved if any insert fails.
+pub fn create_staff_batch(app_data_dir: &PathBuf, staff: &[CreateStaff]) -> Result<Vec<String>> {
+    let mut conn = get_connection(app_data_dir)?;
     let now = chrono::Utc::now().to_rfc3339();
 
+    let tx = conn.transaction()?;
+    let ids = staff
+        .iter()
+        .map(|staff_data| insert_staff(&tx, staff_data, &now))
+        .collect::<Result<Vec<_>>>()?;
+    tx.commit()?;
+
+    Ok(ids)
+}
+
+/// Insert one staff row and its photo; returns the new id.
+fn insert_staff(conn: &Connection, staff_data: &CreateStaff, now: &str) -> Result<String> {
+    let id = Uuid::new_v4().to_string();
+
     conn.execute(
         r#"
         INSERT INTO staff (
             id, appointment_number, full_name, gender, date_of_birth, age,
             nic_number, nic_number_old, marital_status, address_line1, address_line2, address_line3,
             contact_number, email, designation, date_of_first_appointment, date_of_retirement,
-            increment_date, salary_code, basic_salary, increment_amount, image_data,
+            increment_date, salary_code, basic_salary, increment_amount,
             created_at, updated_at
         ) VALUES (
-            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24
+            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23
         )
         "#,
         params![
@@ -49,18 +186,56 @@ pub fn create_staff(app_data_dir: &PathBuf, staff_data: CreateStaff) -> Result<S
             staff_data.marital_status, staff_data.address_line1, staff_data.address_line2, staff_data.address_line3,
             staff_data.contact_number, staff_data.email, staff_data.designation, staff_data.date_of_first_appointment,
             staff_data.date_of_retirement, staff_data.increment_date, staff_data.salary_code, staff_data.basic_salary,
-            staff_data.increment_amount, staff_data.image_data, now, now
+            staff_data.increment_amount, now, now
         ],
     )?;
+    save_staff_photo(conn, &id, staff_data.image_data.as_deref(), now)?;
 
-    get_staff_by_id(app_data_dir, &id)
+    Ok(id)
 }
 
-/// Parse an RFC3339 timestamp (stored as TEXT) into DateTime<Utc>.
+/// Store (or remove, when `image_data` is empty) the photo for a staff member.
+pub(crate) fn save_staff_photo(conn: &Connection, staff_id: &str, image_data: Option<&str>, now: &str) -> Result<()> {
+    match image_data.filter(|data| !data.trim().is_empty()) {
+        Some(image_data) => {
+            let thumbnail = make_thumbnail(image_data).ok();
+            conn.execute(
+                r#"
+                INSERT INTO staff_photos (staff_id, image_data, thumbnail_data, updated_at)
+                VALUES (?1, ?2, ?3, ?4)
+                ON CONFLICT(staff_id) DO UPDATE SET
+                    image_data = excluded.image_data,
+                    thumbnail_data = excluded.thumbnail_data,
+                    updated_at = excluded.updated_at
+                "#,
+                params![staff_id, image_data, thumbnail, now],
+            )?;
+        }
+        None => {
+            conn.execute("DELETE FROM staff_photos WHERE staff_id = ?1", params![staff_id])?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Timestamps written before every one was stored as RFC3339: SQLite's
+/// `CURRENT_TIMESTAMP` and the same with a `T`, both UTC.
+const LEGACY_DATETIME_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"];
+
+/// Parse a timestamp (stored as TEXT) into DateTime<Utc>. Expects RFC3339,
+/// but also reads the legacy formats in case a row predates migration 012.
 /// `col_index` should be the column index (usize) used for nicer error messages.
-fn parse_datetime_from_row(col_index: usize, value: String) -> Result<DateTime<Utc>> {
+pub(crate) fn parse_datetime_from_row(col_index: usize, value: String) -> Result<DateTime<Utc>> {
     DateTime::parse_from_rfc3339(&value)
         .map(|dt| dt.with_timezone(&Utc))
+        .or_else(|e| {
+            LEGACY_DATETIME_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(&value, format).ok())
+                .map(|dt| dt.and_utc())
+                .ok_or(e)
+        })
         .map_err(|e| rusqlite::Error::FromSqlConversionFailure(
             col_index,
             Type::Text,
@@ -68,129 +243,126 @@ fn parse_datetime_from_row(col_index: usize, value: String) -> Result<DateTime<U
         ))
 }
 
-pub fn get_all_staff(app_data_dir: &PathBuf) -> Result<Vec<Staff>> {
-    let conn = get_connection(app_data_dir)?;
-    let mut stmt = conn.prepare(
-        r#"
-        SELECT id, appointment_number, full_name, gender, date_of_birth, age,
-               nic_number, nic_number_old, marital_status, address_line1, address_line2, address_line3,
-               contact_number, email, designation, date_of_first_appointment, date_of_retirement,
-               increment_date, salary_code, basic_salary, increment_amount, image_data,
-               created_at, updated_at
-        FROM staff
-        ORDER BY full_name
-        "#,
-    )?;
+/// Map a row selected with `STAFF_SELECT` into a `Staff`.
+fn map_staff_row(row: &Row) -> Result<Staff> {
+    // read created/updated as String and parse
+    let created_at_str: String = row.get(23)?;
+    let created_at = parse_datetime_from_row(23, created_at_str)?;
 
-    let staff_iter = stmt.query_map([], |row| {
-        // read created/updated as String and parse
-        let created_at_str: String = row.get(22)?;
-        let created_at = parse_datetime_from_row(22, created_at_str)?;
+    let updated_at_str: String = row.get(24)?;
+    let updated_at = parse_datetime_from_row(24, updated_at_str)?;
 
-        let updated_at_str: String = row.get(23)?;
-        let updated_at = parse_datetime_from_row(23, updated_at_str)?;
+    Ok(Staff {
+        id: row.get(0)?,
+        appointment_number: row.get(1)?,
+        full_name: row.get(2)?,
+        gender: row.get(3)?,
+        date_of_birth: row.get(4)?,
+        age: row.get(5)?,
+        nic_number: row.get(6)?,
+        nic_number_old: row.get(7)?,
+        marital_status: row.get(8)?,
+        address_line1: row.get(9)?,
+        address_line2: row.get(10)?,
+        address_line3: row.get(11)?,
+        contact_number: row.get(12)?,
+        email: row.get(13)?,
+        designation: row.get(14)?,
+        date_of_first_appointment: row.get(15)?,
+        date_of_retirement: row.get(16)?,
+        increment_date: row.get(17)?,
+        salary_code: row.get(18)?,
+        basic_salary: row.get(19)?,
+        increment_amount: row.get(20)?,
+        photo_thumbnail: row.get(21)?,
+        has_photo: row.get(22)?,
+        created_at,
+        updated_at,
+        version: row.get(25)?,
+        masked: false,
+    })
+}
 
-        Ok(Staff {
-            id: row.get(0)?,
-            appointment_number: row.get(1)?,
-            full_name: row.get(2)?,
-            gender: row.get(3)?,
-            date_of_birth: row.get(4)?,
-            age: row.get(5)?,
-            nic_number: row.get(6)?,
-            nic_number_old: row.get(7)?,
-            marital_status: row.get(8)?,
-            address_line1: row.get(9)?,
-            address_line2: row.get(10)?,
-            address_line3: row.get(11)?,
-            contact_number: row.get(12)?,
-            email: row.get(13)?,
-            designation: row.get(14)?,
-            date_of_first_appointment: row.get(15)?,
-            date_of_retirement: row.get(16)?,
-            increment_date: row.get(17)?,
-            salary_code: row.get(18)?,
-            basic_salary: row.get(19)?,
-            increment_amount: row.get(20)?,
-            image_data: row.get(21)?,
-            created_at,
-            updated_at,
-        })
-    })?;
+pub fn get_staff_by_id(app_data_dir: &PathBuf, id: &str) -> Result<Staff> {
+    let conn = get_connection(app_data_dir)?;
+    let mut stmt = conn.prepare(&format!("{} WHERE s.id = ?1", STAFF_SELECT))?;
 
-    let mut staff_list = Vec::new();
-    for staff in staff_iter {
-        staff_list.push(staff?);
+    let staff = stmt.query_row([id], map_staff_row)?;
+
+    Ok(staff)
+}
+
+/// Full records for the given ids, in the order given; ids that don't exist are left out.
+pub fn get_staff_by_ids(app_data_dir: &PathBuf, ids: &[String]) -> Result<Vec<Staff>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
     }
 
-    Ok(staff_list)
+    let conn = get_connection(app_data_dir)?;
+
+    let placeholders: Vec<String> = (1..=ids.len()).map(|i| format!("?{}", i)).collect();
+    let query = format!("{} WHERE s.id IN ({})", STAFF_SELECT, placeholders.join(", "));
+
+    let mut stmt = conn.prepare(&query)?;
+    let mut found: HashMap<String, Staff> = stmt
+        .query_map(rusqlite::params_from_iter(ids.iter()), map_staff_row)?
+        .map(|staff| staff.map(|staff| (staff.id.clone(), staff)))
+        .collect::<Result<_>>()?;
+
+    Ok(ids.iter().filter_map(|id| found.remove(id)).collect())
 }
 
-pub fn get_staff_by_id(app_data_dir: &PathBuf, id: &str) -> Result<Staff> {
+/// Full size base64 photo for a staff member, if one has been uploaded.
+pub fn get_staff_photo(app_data_dir: &PathBuf, staff_id: &str) -> Result<Option<String>> {
     let conn = get_connection(app_data_dir)?;
-    let mut stmt = conn.prepare(
-        r#"
-        SELECT id, appointment_number, full_name, gender, date_of_birth, age,
-               nic_number, nic_number_old, marital_status, address_line1, address_line2, address_line3,
-               contact_number, email, designation, date_of_first_appointment, date_of_retirement,
-               increment_date, salary_code, basic_salary, increment_amount, image_data,
-               created_at, updated_at
-        FROM staff
-        WHERE id = ?1
-        "#,
-    )?;
 
-    let staff = stmt.query_row([id], |row| {
-        let created_at_str: String = row.get(22)?;
-        let created_at = parse_datetime_from_row(22, created_at_str)?;
+    conn.query_row(
+        "SELECT image_data FROM staff_photos WHERE staff_id = ?1",
+        [staff_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
 
-        let updated_at_str: String = row.get(23)?;
-        let updated_at = parse_datetime_from_row(23, updated_at_str)?;
+/// Why a versioned staff update was not saved.
+#[derive(Debug)]
+pub enum UpdateError {
+    /// Someone else saved the record after `version` was read, or deleted it
+    Conflict { id: String, version: i64 },
+    Database(rusqlite::Error),
+}
 
-        Ok(Staff {
-            id: row.get(0)?,
-            appointment_number: row.get(1)?,
-            full_name: row.get(2)?,
-            gender: row.get(3)?,
-            date_of_birth: row.get(4)?,
-            age: row.get(5)?,
-            nic_number: row.get(6)?,
-            nic_number_old: row.get(7)?,
-            marital_status: row.get(8)?,
-            address_line1: row.get(9)?,
-            address_line2: row.get(10)?,
-            address_line3: row.get(11)?,
-            contact_number: row.get(12)?,
-            email: row.get(13)?,
-            designation: row.get(14)?,
-            date_of_first_appointment: row.get(15)?,
-            date_of_retirement: row.get(16)?,
-            increment_date: row.get(17)?,
-            salary_code: row.get(18)?,
-            basic_salary: row.get(19)?,
-            increment_amount: row.get(20)?,
-            image_data: row.get(21)?,
-            created_at,
-            updated_at,
-        })
-    })?;
+impl From<rusqlite::Error> for UpdateError {
+    fn from(e: rusqlite::Error) -> Self {
+        UpdateError::Database(e)
+    }
+}
 
-    Ok(staff)
+impl std::fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateError::Conflict { id, version } => write!(f, "staff {} has changed since version {}", id, version),
+            UpdateError::Database(e) => e.fmt(f),
+        }
+    }
 }
 
-pub fn update_staff(app_data_dir: &PathBuf, staff_data: UpdateStaff) -> Result<Staff> {
-    let conn = get_connection(app_data_dir)?;
+impl std::error::Error for UpdateError {}
+
+pub fn update_staff(app_data_dir: &PathBuf, staff_data: UpdateStaff) -> std::result::Result<Staff, UpdateError> {
+    let mut conn = get_connection(app_data_dir)?;
     let now = chrono::Utc::now().to_rfc3339();
 
-    conn.execute(
+    let tx = conn.transaction()?;
+    let updated = tx.execute(
         r#"
         UPDATE staff SET
             appointment_number = ?2, full_name = ?3, gender = ?4, date_of_birth = ?5, age = ?6,
             nic_number = ?7, nic_number_old = ?8, marital_status = ?9, address_line1 = ?10, address_line2 = ?11, address_line3 = ?12,
             contact_number = ?13, email = ?14, designation = ?15, date_of_first_appointment = ?16, date_of_retirement = ?17,
-            increment_date = ?18, salary_code = ?19, basic_salary = ?20, increment_amount = ?21, image_data = ?22,
-            updated_at = ?23
-        WHERE id = ?1
+            increment_date = ?18, salary_code = ?19, basic_salary = ?20, increment_amount = ?21,
+            updated_at = ?22, version = version + 1
+        WHERE id = ?1 AND version = ?23
         "#,
         params![
             staff_data.id, staff_data.appointment_number, staff_data.full_name, staff_data.gender,
@@ -198,65 +370,182 @@ pub fn update_staff(app_data_dir: &PathBuf, staff_data: UpdateStaff) -> Result<S
             staff_data.marital_status, staff_data.address_line1, staff_data.address_line2, staff_data.address_line3,
             staff_data.contact_number, staff_data.email, staff_data.designation, staff_data.date_of_first_appointment,
             staff_data.date_of_retirement, staff_data.increment_date, staff_data.salary_code, staff_data.basic_salary,
-            staff_data.increment_amount, staff_data.image_data, now
+            staff_data.increment_amount, now, staff_data.version
         ],
     )?;
+    if updated == 0 {
+        return Err(UpdateError::Conflict { id: staff_data.id, version: staff_data.version });
+    }
+    if let Some(image_data) = &staff_data.image_data {
+        save_staff_photo(&tx, &staff_data.id, image_data.as_deref(), &now)?;
+    }
+    tx.commit()?;
 
-    get_staff_by_id(app_data_dir, &staff_data.id)
+    Ok(get_staff_by_id(app_data_dir, &staff_data.id)?)
+}
+
+/// The fields a patch sets, by column name, with `null` for the ones it clears.
+pub fn patch_fields(patch: &StaffPatch) -> serde_json::Map<String, serde_json::Value> {
+    match serde_json::to_value(patch) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    }
+}
+
+fn to_sql_value(value: &serde_json::Value) -> rusqlite::types::Value {
+    use rusqlite::types::Value;
+
+    match value {
+        serde_json::Value::String(text) => Value::Text(text.clone()),
+        serde_json::Value::Number(number) => match number.as_i64() {
+            Some(integer) => Value::Integer(integer),
+            None => Value::Real(number.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::Bool(flag) => Value::Integer(*flag as i64),
+        _ => Value::Null,
+    }
+}
+
+/// Run the UPDATE for one patch on an open connection, returning
+/// `UpdateError::Conflict` when `patch.version` is out of date.
+fn apply_patch(conn: &Connection, patch: &StaffPatch, now: &str) -> std::result::Result<(), UpdateError> {
+    // Column names come from StaffPatch's own field names, never from the caller
+    let fields = patch_fields(patch);
+    let mut assignments: Vec<String> = Vec::with_capacity(fields.len() + 2);
+    let mut values: Vec<rusqlite::types::Value> = Vec::with_capacity(fields.len() + 4);
+    for (column, value) in &fields {
+        values.push(to_sql_value(value));
+        assignments.push(format!("{} = ?{}", column, values.len()));
+    }
+    values.push(now.to_string().into());
+    assignments.push(format!("updated_at = ?{}", values.len()));
+    assignments.push("version = version + 1".to_string());
+
+    values.push(patch.id.clone().into());
+    values.push(patch.version.into());
+    let sql = format!(
+        "UPDATE staff SET {} WHERE id = ?{} AND version = ?{}",
+        assignments.join(", "),
+        values.len() - 1,
+        values.len()
+    );
+
+    if conn.execute(&sql, rusqlite::params_from_iter(values))? == 0 {
+        return Err(UpdateError::Conflict { id: patch.id.clone(), version: patch.version });
+    }
+    if let Some(image_data) = &patch.image_data {
+        save_staff_photo(conn, &patch.id, image_data.as_deref(), now)?;
+    }
+
+    Ok(())
+}
+
+/// Update only the fields present in `patch`. Fails like `update_staff` when
+/// `patch.version` is out of date.
+pub fn patch_staff(app_data_dir: &PathBuf, patch: &StaffPatch) -> std::result::Result<Staff, UpdateError> {
+    let mut conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.transaction()?;
+    apply_patch(&tx, patch, &now)?;
+    tx.commit()?;
+
+    Ok(get_staff_by_id(app_data_dir, &patch.id)?)
+}
+
+/// Apply every patch and write the audit entries in one transaction, so a
+/// bulk edit either lands completely or not at all. Fails like `patch_staff`
+/// if any patch is out of date.
+pub fn patch_staff_batch(
+    app_data_dir: &PathBuf,
+    patches: &[StaffPatch],
+    entries: &[NewAuditEntry],
+) -> std::result::Result<(), UpdateError> {
+    let mut conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.transaction()?;
+    for patch in patches {
+        apply_patch(&tx, patch, &now)?;
+    }
+    for entry in entries {
+        insert_audit_entry(&tx, entry)?;
+    }
+    Ok(tx.commit()?)
 }
 
 pub fn delete_staff(app_data_dir: &PathBuf, id: &str) -> Result<()> {
     let conn = get_connection(app_data_dir)?;
 
+    // staff_photos rows go with it via ON DELETE CASCADE
     conn.execute("DELETE FROM staff WHERE id = ?1", params![id])?;
 
     Ok(())
 }
 
-pub fn search_staff(app_data_dir: &PathBuf, params: StaffSearchParams) -> Result<Vec<Staff>> {
-    let conn = get_connection(app_data_dir)?;
+/// Pieces of a staff search query. `joins` goes straight after the FROM clause and
+/// `conditions` after `WHERE 1=1`; parameters are numbered from ?1.
+struct SearchFilter {
+    joins: String,
+    conditions: String,
+    params: Vec<Box<dyn rusqlite::ToSql>>,
+    full_text: bool,
+}
 
-    let mut query = String::from(
-        r#"
-        SELECT id, appointment_number, full_name, gender, date_of_birth, age,
-               nic_number, nic_number_old, marital_status, address_line1, address_line2, address_line3,
-               contact_number, email, designation, date_of_first_appointment, date_of_retirement,
-               increment_date, salary_code, basic_salary, increment_amount, image_data,
-               created_at, updated_at
-        FROM staff
-        WHERE 1=1
-        "#,
-    );
+/// Turn free text into an FTS5 query: every word must match, each as a prefix.
+/// Words are quoted so FTS operators typed by the user are treated as text.
+/// With `public_columns_only` the query is limited to `FTS_PUBLIC_COLUMNS`.
+fn fts_match_query(search_term: &str, public_columns_only: bool) -> Option<String> {
+    let terms: Vec<String> = search_term
+        .split_whitespace()
+        .filter(|word| word.chars().any(|c| c.is_alphanumeric()))
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
 
+    if terms.is_empty() {
+        None
+    } else if public_columns_only {
+        Some(format!("{{{}}}: ({})", FTS_PUBLIC_COLUMNS, terms.join(" ")))
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+fn build_search_filter(params: &StaffSearchParams) -> SearchFilter {
+    let mut joins = String::new();
+    let mut query = String::new();
     let mut query_params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
-    if let Some(search_term) = &params.search_term {
-        if !search_term.is_empty() {
-            query.push_str(" AND (full_name LIKE ?1 OR appointment_number LIKE ?1 OR nic_number LIKE ?1 OR nic_number_old LIKE ?1)");
-            query_params.push(Box::new(format!("%{}%", search_term)));
-        }
+    let match_query = params
+        .search_term
+        .as_deref()
+        .and_then(|term| fts_match_query(term, params.public_columns_only));
+    if let Some(match_query) = match_query {
+        joins.push_str(" JOIN staff_fts ON staff_fts.staff_id = s.id");
+        query.push_str(" AND staff_fts MATCH ?1");
+        query_params.push(Box::new(match_query));
     }
 
     if let Some(designation) = &params.designation {
         if !designation.is_empty() {
-            query.push_str(&format!(" AND designation = ?{}", query_params.len() + 1));
+            query.push_str(&format!(" AND s.designation = ?{}", query_params.len() + 1));
             query_params.push(Box::new(designation.clone()));
         }
     }
 
     if let Some(age_min) = params.age_min {
-        query.push_str(&format!(" AND age >= ?{}", query_params.len() + 1));
+        query.push_str(&format!(" AND s.age >= ?{}", query_params.len() + 1));
         query_params.push(Box::new(age_min));
     }
 
     if let Some(age_max) = params.age_max {
-        query.push_str(&format!(" AND age <= ?{}", query_params.len() + 1));
+        query.push_str(&format!(" AND s.age <= ?{}", query_params.len() + 1));
         query_params.push(Box::new(age_max));
     }
 
     if let Some(nic_number) = &params.nic_number {
         if !nic_number.is_empty() {
-            query.push_str(&format!(" AND (nic_number = ?{} OR nic_number_old = ?{})", query_params.len() + 1, query_params.len() + 2));
+            query.push_str(&format!(" AND (s.nic_number = ?{} OR s.nic_number_old = ?{})", query_params.len() + 1, query_params.len() + 2));
             query_params.push(Box::new(nic_number.clone()));
             query_params.push(Box::new(nic_number.clone()));
         }
@@ -264,48 +553,128 @@ pub fn search_staff(app_data_dir: &PathBuf, params: StaffSearchParams) -> Result
 
     if let Some(salary_code) = &params.salary_code {
         if !salary_code.is_empty() {
-            query.push_str(&format!(" AND salary_code = ?{}", query_params.len() + 1));
+            query.push_str(&format!(" AND s.salary_code = ?{}", query_params.len() + 1));
             query_params.push(Box::new(salary_code.clone()));
         }
     }
 
-    query.push_str(" ORDER BY full_name");
+    if let Some(gender) = &params.gender {
+        if !gender.is_empty() {
+            query.push_str(&format!(" AND s.gender = ?{}", query_params.len() + 1));
+            query_params.push(Box::new(gender.clone()));
+        }
+    }
 
-    let mut stmt = conn.prepare(&query)?;
-    let staff_iter = stmt.query_map(rusqlite::params_from_iter(query_params.iter()), |row| {
-        let created_at_str: String = row.get(22)?;
-        let created_at = parse_datetime_from_row(22, created_at_str)?;
+    if let Some(marital_status) = &params.marital_status {
+        if !marital_status.is_empty() {
+            query.push_str(&format!(" AND s.marital_status = ?{}", query_params.len() + 1));
+            query_params.push(Box::new(marital_status.clone()));
+        }
+    }
 
-        let updated_at_str: String = row.get(23)?;
-        let updated_at = parse_datetime_from_row(23, updated_at_str)?;
+    if let Some(designations) = &params.designations {
+        push_in_filter(&mut query, &mut query_params, "s.designation", designations);
+    }
 
-        Ok(Staff {
-            id: row.get(0)?,
-            appointment_number: row.get(1)?,
-            full_name: row.get(2)?,
-            gender: row.get(3)?,
-            date_of_birth: row.get(4)?,
-            age: row.get(5)?,
-            nic_number: row.get(6)?,
-            nic_number_old: row.get(7)?,
-            marital_status: row.get(8)?,
-            address_line1: row.get(9)?,
-            address_line2: row.get(10)?,
-            address_line3: row.get(11)?,
-            contact_number: row.get(12)?,
-            email: row.get(13)?,
-            designation: row.get(14)?,
-            date_of_first_appointment: row.get(15)?,
-            date_of_retirement: row.get(16)?,
-            increment_date: row.get(17)?,
-            salary_code: row.get(18)?,
-            basic_salary: row.get(19)?,
-            increment_amount: row.get(20)?,
-            image_data: row.get(21)?,
-            created_at,
-            updated_at,
-        })
-    })?;
+    if let Some(salary_codes) = &params.salary_codes {
+        push_in_filter(&mut query, &mut query_params, "s.salary_code", salary_codes);
+    }
+
+    if let Some(basic_salary_min) = params.basic_salary_min {
+        query.push_str(&format!(" AND s.basic_salary >= ?{}", query_params.len() + 1));
+        query_params.push(Box::new(basic_salary_min));
+    }
+
+    if let Some(basic_salary_max) = params.basic_salary_max {
+        query.push_str(&format!(" AND s.basic_salary <= ?{}", query_params.len() + 1));
+        query_params.push(Box::new(basic_salary_max));
+    }
+
+    let date_ranges = [
+        ("s.date_of_first_appointment", &params.appointment_date_from, ">="),
+        ("s.date_of_first_appointment", &params.appointment_date_to, "<="),
+        ("s.date_of_retirement", &params.retirement_date_from, ">="),
+        ("s.date_of_retirement", &params.retirement_date_to, "<="),
+    ];
+    for (column, bound, operator) in date_ranges {
+        if let Some(date) = bound {
+            if !date.is_empty() {
+                // Dates are stored as YYYY-MM-DD (older rows may carry a time part), so
+                // comparing the date prefix as text orders correctly
+                query.push_str(&format!(" AND substr({}, 1, 10) {} ?{}", column, operator, query_params.len() + 1));
+                query_params.push(Box::new(date.clone()));
+            }
+        }
+    }
+
+    if let Some(increment_month) = params.increment_month {
+        // Increment dates are entered as DD-MM; fall back to YYYY-MM-DD for older rows
+        query.push_str(&format!(
+            " AND CAST(CASE WHEN length(s.increment_date) = 5 THEN substr(s.increment_date, 4, 2) ELSE substr(s.increment_date, 6, 2) END AS INTEGER) = ?{}",
+            query_params.len() + 1
+        ));
+        query_params.push(Box::new(increment_month));
+    }
+
+    if let Some(has_photo) = params.has_photo {
+        query.push_str(if has_photo { " AND p.staff_id IS NOT NULL" } else { " AND p.staff_id IS NULL" });
+    }
+
+    if let Some(has_email) = params.has_email {
+        query.push_str(if has_email {
+            " AND s.email IS NOT NULL AND trim(s.email) <> ''"
+        } else {
+            " AND (s.email IS NULL OR trim(s.email) = '')"
+        });
+    }
+
+    SearchFilter {
+        full_text: !joins.is_empty(),
+        joins,
+        conditions: query,
+        params: query_params,
+    }
+}
+
+/// Append `AND column IN (...)` for the non-empty values; does nothing for an empty list.
+fn push_in_filter(
+    query: &mut String,
+    query_params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+    column: &str,
+    values: &[String],
+) {
+    let values: Vec<&String> = values.iter().filter(|value| !value.is_empty()).collect();
+    if values.is_empty() {
+        return;
+    }
+
+    let placeholders: Vec<String> = (0..values.len())
+        .map(|i| format!("?{}", query_params.len() + i + 1))
+        .collect();
+    query.push_str(&format!(" AND {} IN ({})", column, placeholders.join(", ")));
+
+    for value in values {
+        query_params.push(Box::new(value.clone()));
+    }
+}
+
+/// Full staff records matching `params`, for reports.
+pub fn search_staff(
+    app_data_dir: &PathBuf,
+    params: &StaffSearchParams,
+    sort_by: StaffSortColumn,
+    sort_direction: SortDirection,
+) -> Result<Vec<Staff>> {
+    let conn = get_connection(app_data_dir)?;
+
+    let filter = build_search_filter(params);
+    let query = format!(
+        "{}{} WHERE 1=1{} ORDER BY {} {}, s.full_name, s.id",
+        STAFF_SELECT, filter.joins, filter.conditions, sort_column_sql(sort_by), sort_direction_sql(sort_direction)
+    );
+
+    let mut stmt = conn.prepare(&query)?;
+    let staff_iter = stmt.query_map(rusqlite::params_from_iter(filter.params.iter()), map_staff_row)?;
 
     let mut staff_list = Vec::new();
     for staff in staff_iter {
@@ -315,56 +684,412 @@ pub fn search_staff(app_data_dir: &PathBuf, params: StaffSearchParams) -> Result
     Ok(staff_list)
 }
 
-pub fn get_staff_by_nic(app_data_dir: &PathBuf, nic: &str) -> Result<Option<Staff>> {
+/// Ids of every staff member matching `params`, ordered by name.
+pub fn search_staff_ids(app_data_dir: &PathBuf, params: &StaffSearchParams) -> Result<Vec<String>> {
     let conn = get_connection(app_data_dir)?;
-    let mut stmt = conn.prepare(
-        r#"
-        SELECT id, appointment_number, full_name, gender, date_of_birth, age,
-               nic_number, nic_number_old, marital_status, address_line1, address_line2, address_line3,
-               contact_number, email, designation, date_of_first_appointment, date_of_retirement,
-               increment_date, salary_code, basic_salary, increment_amount, image_data,
-               created_at, updated_at
-        FROM staff
-        WHERE nic_number = ?1 OR nic_number_old = ?1
-        "#,
+
+    let filter = build_search_filter(params);
+    let query = format!(
+        "SELECT s.id {}{} WHERE 1=1{} ORDER BY s.full_name, s.id",
+        STAFF_FROM, filter.joins, filter.conditions
+    );
+
+    let mut stmt = conn.prepare(&query)?;
+    let ids = stmt
+        .query_map(rusqlite::params_from_iter(filter.params.iter()), |row| row.get(0))?
+        .collect::<Result<Vec<String>>>()?;
+
+    Ok(ids)
+}
+
+/// `StaffSummary` rows for the given ids, in the same order. Unknown ids are skipped.
+pub fn get_staff_summaries_by_ids(app_data_dir: &PathBuf, ids: &[String]) -> Result<Vec<StaffSummary>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let conn = get_connection(app_data_dir)?;
+
+    let placeholders: Vec<String> = (1..=ids.len()).map(|i| format!("?{}", i)).collect();
+    let query = format!(
+        "SELECT {}, NULL {} WHERE s.id IN ({})",
+        STAFF_SUMMARY_COLUMNS,
+        STAFF_FROM,
+        placeholders.join(", ")
+    );
+
+    let mut stmt = conn.prepare(&query)?;
+    let mut found = stmt
+        .query_map(rusqlite::params_from_iter(ids.iter()), map_staff_summary_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut ordered = Vec::with_capacity(found.len());
+    for id in ids {
+        if let Some(index) = found.iter().position(|staff| &staff.id == id) {
+            ordered.push(found.swap_remove(index));
+        }
+    }
+
+    Ok(ordered)
+}
+
+fn sort_column_sql(column: StaffSortColumn) -> &'static str {
+    match column {
+        StaffSortColumn::FullName => "s.full_name",
+        StaffSortColumn::AppointmentNumber => "s.appointment_number",
+        StaffSortColumn::Designation => "s.designation",
+        StaffSortColumn::Age => "s.age",
+        StaffSortColumn::NicNumber => "s.nic_number",
+        StaffSortColumn::SalaryCode => "s.salary_code",
+        StaffSortColumn::BasicSalary => "s.basic_salary",
+        StaffSortColumn::DateOfFirstAppointment => "s.date_of_first_appointment",
+        StaffSortColumn::DateOfRetirement => "s.date_of_retirement",
+        StaffSortColumn::CreatedAt => "s.created_at",
+        StaffSortColumn::UpdatedAt => "s.updated_at",
+    }
+}
+
+fn sort_direction_sql(direction: SortDirection) -> &'static str {
+    match direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    }
+}
+
+/// Split a snippet() result into plain and highlighted segments.
+fn parse_snippet(snippet: &str) -> Vec<SnippetSegment> {
+    let mut segments = Vec::new();
+    let mut highlighted = false;
+
+    for part in snippet.split([SNIPPET_START, SNIPPET_END]) {
+        if !part.is_empty() {
+            segments.push(SnippetSegment { text: part.to_string(), highlighted });
+        }
+        highlighted = !highlighted;
+    }
+
+    segments
+}
+
+fn map_staff_summary_row(row: &Row) -> Result<StaffSummary> {
+    let snippet: Option<String> = row.get(14)?;
+
+    Ok(StaffSummary {
+        id: row.get(0)?,
+        appointment_number: row.get(1)?,
+        full_name: row.get(2)?,
+        gender: row.get(3)?,
+        age: row.get(4)?,
+        nic_number: row.get(5)?,
+        nic_number_old: row.get(6)?,
+        designation: row.get(7)?,
+        contact_number: row.get(8)?,
+        email: row.get(9)?,
+        salary_code: row.get(10)?,
+        basic_salary: row.get(11)?,
+        has_photo: row.get(12)?,
+        photo_thumbnail: row.get(13)?,
+        snippet: snippet.as_deref().map(parse_snippet),
+        masked: false,
+    })
+}
+
+/// Paged, sorted `StaffSummary` rows matching `params`, with the total match count.
+///
+/// With a search term and no explicit `sort_by`, results come back in relevance
+/// order and each row carries a highlighted snippet of the best matching field.
+pub fn search_staff_summaries(
+    app_data_dir: &PathBuf,
+    params: &StaffSearchParams,
+    page: &StaffPageParams,
+) -> Result<StaffPage<StaffSummary>> {
+    let conn = get_connection(app_data_dir)?;
+
+    let mut filter = build_search_filter(params);
+
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) {}{} WHERE 1=1{}", STAFF_FROM, filter.joins, filter.conditions),
+        rusqlite::params_from_iter(filter.params.iter()),
+        |row| row.get(0),
     )?;
 
-    match stmt.query_row([nic], |row| {
-        let created_at_str: String = row.get(22)?;
-        let created_at = parse_datetime_from_row(22, created_at_str)?;
+    let limit = page.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = page.offset.unwrap_or(0);
+    let order_by = match page.sort_by {
+        None if filter.full_text => format!("{}, s.full_name", FTS_RANK),
+        sort_by => format!(
+            "{} {}",
+            sort_column_sql(sort_by.unwrap_or_default()),
+            sort_direction_sql(page.sort_direction.unwrap_or_default())
+        ),
+    };
+    let snippet = if filter.full_text {
+        format!("snippet(staff_fts, -1, char({}), char({}), '…', 12)", SNIPPET_START as u32, SNIPPET_END as u32)
+    } else {
+        "NULL".to_string()
+    };
 
-        let updated_at_str: String = row.get(23)?;
-        let updated_at = parse_datetime_from_row(23, updated_at_str)?;
+    // s.id breaks ties so rows don't shift between pages
+    let query = format!(
+        "SELECT {}, {} {}{} WHERE 1=1{} ORDER BY {}, s.id LIMIT ?{} OFFSET ?{}",
+        STAFF_SUMMARY_COLUMNS,
+        snippet,
+        STAFF_FROM,
+        filter.joins,
+        filter.conditions,
+        order_by,
+        filter.params.len() + 1,
+        filter.params.len() + 2
+    );
+    filter.params.push(Box::new(limit));
+    filter.params.push(Box::new(offset));
+
+    let mut stmt = conn.prepare(&query)?;
+    let items = stmt
+        .query_map(rusqlite::params_from_iter(filter.params.iter()), map_staff_summary_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(StaffPage { items, total, limit, offset })
+}
+
+/// Identifying numbers of every staff member, for duplicate checks before bulk changes.
+pub fn get_staff_identities(app_data_dir: &PathBuf) -> Result<Vec<StaffIdentity>> {
+    let conn = get_connection(app_data_dir)?;
+    let mut stmt = conn.prepare("SELECT id, appointment_number, nic_number, nic_number_old FROM staff")?;
 
-        Ok(Staff {
+    let rows = stmt.query_map([], |row| {
+        Ok(StaffIdentity {
             id: row.get(0)?,
             appointment_number: row.get(1)?,
-            full_name: row.get(2)?,
-            gender: row.get(3)?,
-            date_of_birth: row.get(4)?,
-            age: row.get(5)?,
-            nic_number: row.get(6)?,
-            nic_number_old: row.get(7)?,
-            marital_status: row.get(8)?,
-            address_line1: row.get(9)?,
-            address_line2: row.get(10)?,
-            address_line3: row.get(11)?,
-            contact_number: row.get(12)?,
-            email: row.get(13)?,
-            designation: row.get(14)?,
-            date_of_first_appointment: row.get(15)?,
-            date_of_retirement: row.get(16)?,
-            increment_date: row.get(17)?,
-            salary_code: row.get(18)?,
-            basic_salary: row.get(19)?,
-            increment_amount: row.get(20)?,
-            image_data: row.get(21)?,
-            created_at,
-            updated_at,
+            nic_number: row.get(2)?,
+            nic_number_old: row.get(3)?,
         })
-    }) {
+    })?;
+
+    rows.collect()
+}
+
+pub fn get_staff_by_nic(app_data_dir: &PathBuf, nic: &str) -> Result<Option<Staff>> {
+    let conn = get_connection(app_data_dir)?;
+    let mut stmt = conn.prepare(&format!("{} WHERE s.nic_number = ?1 OR s.nic_number_old = ?1", STAFF_SELECT))?;
+
+    match stmt.query_row([nic], map_staff_row) {
         Ok(staff) => Ok(Some(staff)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
         Err(e) => Err(e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{insert_staff, new_staff, test_database};
+
+    fn search(app_data_dir: &PathBuf, term: &str) -> Vec<String> {
+        let params = StaffSearchParams { search_term: Some(term.to_string()), ..Default::default() };
+        search_staff(app_data_dir, &params, StaffSortColumn::default(), SortDirection::default())
+            .unwrap()
+            .into_iter()
+            .map(|staff| staff.id)
+            .collect()
+    }
+
+    #[test]
+    fn full_text_index_follows_updates_and_deletes() {
+        let dir = test_database();
+        let app_data_dir = dir.path().to_path_buf();
+        let first = insert_staff(&dir, 1);
+        let second = insert_staff(&dir, 2);
+
+        let conn = get_connection(&app_data_dir).unwrap();
+        conn.execute("UPDATE staff SET full_name = 'Nimal Perera' WHERE id = ?1", params![first.id]).unwrap();
+        assert_eq!(search(&app_data_dir, "Nimal"), vec![first.id.clone()]);
+        assert_eq!(search(&app_data_dir, "Kumar"), vec![second.id.clone()]);
+
+        delete_staff(&app_data_dir, &first.id).unwrap();
+        assert!(search(&app_data_dir, "Nimal").is_empty());
+        assert_eq!(search(&app_data_dir, "Kumar"), vec![second.id.clone()]);
+
+        let indexed: i64 = conn.query_row("SELECT COUNT(*) FROM staff_fts", [], |row| row.get(0)).unwrap();
+        let keys: i64 = conn.query_row("SELECT COUNT(*) FROM staff_fts_keys", [], |row| row.get(0)).unwrap();
+        assert_eq!((indexed, keys), (1, 1));
+    }
+
+    #[test]
+    fn staff_by_ids_keeps_the_order_given_and_skips_missing_ids() {
+        let dir = test_database();
+        let first = insert_staff(&dir, 1);
+        let second = insert_staff(&dir, 2);
+        insert_staff(&dir, 3);
+
+        let ids = vec![second.id.clone(), "missing".to_string(), first.id.clone()];
+        let found: Vec<String> = get_staff_by_ids(&dir.path().to_path_buf(), &ids)
+            .unwrap()
+            .into_iter()
+            .map(|staff| staff.id)
+            .collect();
+        assert_eq!(found, vec![second.id, first.id]);
+    }
+
+    #[test]
+    fn timestamps_that_are_not_rfc3339_are_refused() {
+        let dir = test_database();
+        let staff = insert_staff(&dir, 1);
+        let conn = get_connection(&dir.path().to_path_buf()).unwrap();
+
+        for bad in ["2024-05-01 09:30:00", "2024-05-01T09:30:00", "2024-13-01T09:30:00+00:00", "yesterday"] {
+            let result = conn.execute("UPDATE staff SET updated_at = ?1 WHERE id = ?2", params![bad, staff.id]);
+            assert!(result.is_err(), "{} was accepted", bad);
+        }
+        for good in ["2024-05-01T09:30:00+00:00", "2024-05-01T09:30:00.123456789Z", "2024-05-01T15:00:00+05:30"] {
+            conn.execute("UPDATE staff SET updated_at = ?1 WHERE id = ?2", params![good, staff.id]).unwrap();
+        }
+
+        let result = conn.execute(
+            "INSERT INTO audit_log (username, action, created_at) VALUES ('clerk', 'login', '2024-05-01 09:30:00')",
+            [],
+        );
+        assert!(result.unwrap_err().to_string().contains("RFC3339"));
+    }
+
+    #[test]
+    fn updating_without_a_photo_keeps_the_stored_one() {
+        let dir = test_database();
+        let app_data_dir = dir.path().to_path_buf();
+        let mut data = new_staff(1);
+        data.image_data = Some("stored-photo".to_string());
+        let staff = create_staff(&app_data_dir, data).unwrap();
+
+        // The edit as sent from the app, with `image_data` left out when `None`
+        let update = |staff: &Staff, image_data: Option<serde_json::Value>| -> UpdateStaff {
+            let mut value = serde_json::to_value(staff).unwrap();
+            if let Some(image_data) = image_data {
+                value["image_data"] = image_data;
+            }
+            serde_json::from_value(value).unwrap()
+        };
+
+        let staff = update_staff(&app_data_dir, update(&staff, None)).unwrap();
+        assert_eq!(get_staff_photo(&app_data_dir, &staff.id).unwrap().as_deref(), Some("stored-photo"));
+
+        update_staff(&app_data_dir, update(&staff, Some(serde_json::Value::Null))).unwrap();
+        assert_eq!(get_staff_photo(&app_data_dir, &staff.id).unwrap(), None);
+    }
+
+    #[test]
+    fn a_patch_changes_only_its_fields() {
+        let dir = test_database();
+        let app_data_dir = dir.path().to_path_buf();
+        let before = insert_staff(&dir, 1);
+
+        let patch: StaffPatch = serde_json::from_value(serde_json::json!({
+            "id": before.id,
+            "version": before.version,
+            "full_name": "Nimal Perera",
+            "email": null,
+        }))
+        .unwrap();
+        let after = patch_staff(&app_data_dir, &patch).unwrap();
+
+        assert_eq!(after.full_name, "Nimal Perera");
+        assert_eq!(after.email, None);
+        assert_eq!(after.version, before.version + 1);
+        assert_eq!(after.nic_number, before.nic_number);
+        assert_eq!(after.address_line1, before.address_line1);
+        assert_eq!(after.basic_salary, before.basic_salary);
+    }
+
+    #[test]
+    fn a_patch_from_an_old_version_changes_nothing() {
+        let dir = test_database();
+        let app_data_dir = dir.path().to_path_buf();
+        let before = insert_staff(&dir, 1);
+
+        let patch = StaffPatch {
+            id: before.id.clone(),
+            version: before.version - 1,
+            full_name: Some(Some("Nimal Perera".to_string())),
+            ..Default::default()
+        };
+        assert!(matches!(
+            patch_staff(&app_data_dir, &patch),
+            Err(UpdateError::Conflict { version, .. }) if version == before.version - 1
+        ));
+        let after = get_staff_by_id(&app_data_dir, &before.id).unwrap();
+        assert_eq!((after.full_name, after.version), (before.full_name, before.version));
+    }
+
+    #[test]
+    fn an_update_from_an_old_version_changes_nothing() {
+        let dir = test_database();
+        let app_data_dir = dir.path().to_path_buf();
+        let before = insert_staff(&dir, 1);
+
+        let mut value = serde_json::to_value(&before).unwrap();
+        value["full_name"] = "Nimal Perera".into();
+        value["version"] = (before.version - 1).into();
+        let update: UpdateStaff = serde_json::from_value(value).unwrap();
+
+        assert!(matches!(
+            update_staff(&app_data_dir, update),
+            Err(UpdateError::Conflict { id, .. }) if id == before.id
+        ));
+        let after = get_staff_by_id(&app_data_dir, &before.id).unwrap();
+        assert_eq!((after.full_name, after.version), (before.full_name, before.version));
+    }
+
+    #[test]
+    fn migration_012_rewrites_old_timestamps_as_rfc3339() {
+        let dir = test_database();
+        let staff = insert_staff(&dir, 1);
+        let mut conn = get_connection(&dir.path().to_path_buf()).unwrap();
+
+        // A database from before 012, written the old way
+        conn.execute_batch(
+            "DROP TRIGGER staff_timestamp_check_update; DROP TRIGGER users_timestamp_check_insert; PRAGMA user_version = 11;",
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE staff SET created_at = '2020-02-03 04:05:06', updated_at = '2020-02-03T04:05:07' WHERE id = ?1",
+            params![staff.id],
+        )
+        .unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO users (id, username, full_name, password_hash, role, created_at, updated_at)
+            VALUES ('old', 'old', 'Old', 'hash', 'admin', '2021-01-01 00:00:00', '2021-01-01 00:00:00')
+            "#,
+            [],
+        )
+        .unwrap();
+
+        run_migrations(&mut conn).unwrap();
+
+        let timestamps = |sql: &str| -> (String, String) {
+            conn.query_row(sql, [], |row| Ok((row.get(0)?, row.get(1)?))).unwrap()
+        };
+        assert_eq!(
+            timestamps(&format!("SELECT created_at, updated_at FROM staff WHERE id = '{}'", staff.id)),
+            ("2020-02-03T04:05:06+00:00".to_string(), "2020-02-03T04:05:07+00:00".to_string())
+        );
+        assert_eq!(
+            timestamps("SELECT created_at, updated_at FROM users WHERE id = 'old'"),
+            ("2021-01-01T00:00:00+00:00".to_string(), "2021-01-01T00:00:00+00:00".to_string())
+        );
+
+        // New rows relying on the CURRENT_TIMESTAMP default are converted on insert
+        conn.execute(
+            r#"
+            INSERT INTO staff (id, appointment_number, full_name, gender, date_of_birth, age, nic_number, marital_status,
+                               designation, date_of_first_appointment, date_of_retirement, salary_code, basic_salary, increment_amount)
+            VALUES ('defaults', 'APP/2', 'Defaults', 'Male', '1980-01-01', 44, '198000000002', 'Single',
+                    'Development Officer', '2005-01-01', '2040-01-01', '', 0, 0)
+            "#,
+            [],
+        )
+        .unwrap();
+        let (created_at, updated_at) = timestamps("SELECT created_at, updated_at FROM staff WHERE id = 'defaults'");
+        assert!(DateTime::parse_from_rfc3339(&created_at).is_ok(), "{}", created_at);
+        assert!(DateTime::parse_from_rfc3339(&updated_at).is_ok(), "{}", updated_at);
+    }
+}
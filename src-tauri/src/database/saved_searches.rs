@@ -0,0 +1,277 @@
+// src/database/saved_searches.rs
+//
+// Saved searches (a named StaffSearchParams) and static staff groups
+// (a named, hand-picked list of staff ids).
+use rusqlite::{Connection, OptionalExtension, Result, Row, params};
+use rusqlite::types::Type;
+use std::path::PathBuf;
+use uuid::Uuid;
+use crate::database::operations::{get_connection, parse_datetime_from_row};
+use crate::database::schema::{
+    SavedSearch, CreateSavedSearch, UpdateSavedSearch, StaffGroup, CreateStaffGroup, UpdateStaffGroup,
+    StaffSearchParams,
+};
+
+fn params_to_json(params: &StaffSearchParams) -> Result<String> {
+    serde_json::to_string(params).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+fn map_saved_search_row(row: &Row) -> Result<SavedSearch> {
+    let params_json: String = row.get(2)?;
+    let params = serde_json::from_str(&params_json)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, Type::Text, Box::new(e)))?;
+
+    Ok(SavedSearch {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        params,
+        created_at: parse_datetime_from_row(3, row.get(3)?)?,
+        updated_at: parse_datetime_from_row(4, row.get(4)?)?,
+    })
+}
+
+pub fn create_saved_search(app_data_dir: &PathBuf, data: CreateSavedSearch) -> Result<SavedSearch> {
+    let conn = get_connection(app_data_dir)?;
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO saved_searches (id, name, params, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![id, data.name.trim(), params_to_json(&data.params)?, now, now],
+    )?;
+
+    get_saved_search(app_data_dir, &id)
+}
+
+pub fn get_saved_search(app_data_dir: &PathBuf, id: &str) -> Result<SavedSearch> {
+    let conn = get_connection(app_data_dir)?;
+
+    conn.query_row(
+        "SELECT id, name, params, created_at, updated_at FROM saved_searches WHERE id = ?1",
+        [id],
+        map_saved_search_row,
+    )
+}
+
+pub fn list_saved_searches(app_data_dir: &PathBuf) -> Result<Vec<SavedSearch>> {
+    let conn = get_connection(app_data_dir)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, name, params, created_at, updated_at FROM saved_searches ORDER BY name COLLATE NOCASE",
+    )?;
+
+    let saved_searches = stmt
+        .query_map([], map_saved_search_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(saved_searches)
+}
+
+pub fn update_saved_search(app_data_dir: &PathBuf, data: UpdateSavedSearch) -> Result<SavedSearch> {
+    let conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let updated = conn.execute(
+        "UPDATE saved_searches SET name = ?2, params = ?3, updated_at = ?4 WHERE id = ?1",
+        params![data.id, data.name.trim(), params_to_json(&data.params)?, now],
+    )?;
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+
+    get_saved_search(app_data_dir, &data.id)
+}
+
+pub fn delete_saved_search(app_data_dir: &PathBuf, id: &str) -> Result<()> {
+    let conn = get_connection(app_data_dir)?;
+
+    conn.execute("DELETE FROM saved_searches WHERE id = ?1", params![id])?;
+
+    Ok(())
+}
+
+fn get_group_member_ids(conn: &Connection, group_id: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT staff_id FROM staff_group_members WHERE group_id = ?1 ORDER BY position",
+    )?;
+
+    let ids = stmt
+        .query_map([group_id], |row| row.get(0))?
+        .collect::<Result<Vec<String>>>()?;
+
+    Ok(ids)
+}
+
+/// Append staff to the end of a group, skipping ones already in it. Returns
+/// the ids that aren't staff records (deleted since they were chosen, say),
+/// which are left out.
+fn add_group_members(conn: &Connection, group_id: &str, staff_ids: &[String]) -> Result<Vec<String>> {
+    let mut next_position: i64 = conn.query_row(
+        "SELECT COALESCE(MAX(position), -1) + 1 FROM staff_group_members WHERE group_id = ?1",
+        [group_id],
+        |row| row.get(0),
+    )?;
+
+    let mut insert = conn.prepare(
+        r#"
+        INSERT OR IGNORE INTO staff_group_members (group_id, staff_id, position)
+        SELECT ?1, id, ?3 FROM staff WHERE id = ?2
+        "#,
+    )?;
+    let mut staff_exists = conn.prepare("SELECT 1 FROM staff WHERE id = ?1")?;
+
+    let mut skipped = Vec::new();
+    for staff_id in staff_ids {
+        if insert.execute(params![group_id, staff_id, next_position])? > 0 {
+            next_position += 1;
+        } else if !staff_exists.exists([staff_id])? {
+            skipped.push(staff_id.clone());
+        }
+    }
+
+    Ok(skipped)
+}
+
+pub fn get_staff_group(app_data_dir: &PathBuf, id: &str) -> Result<StaffGroup> {
+    let conn = get_connection(app_data_dir)?;
+
+    let (name, description, created_at, updated_at): (String, Option<String>, String, String) = conn.query_row(
+        "SELECT name, description, created_at, updated_at FROM staff_groups WHERE id = ?1",
+        [id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+    )?;
+
+    Ok(StaffGroup {
+        id: id.to_string(),
+        name,
+        description,
+        staff_ids: get_group_member_ids(&conn, id)?,
+        created_at: parse_datetime_from_row(2, created_at)?,
+        updated_at: parse_datetime_from_row(3, updated_at)?,
+        skipped_staff_ids: Vec::new(),
+    })
+}
+
+pub fn list_staff_groups(app_data_dir: &PathBuf) -> Result<Vec<StaffGroup>> {
+    let conn = get_connection(app_data_dir)?;
+    let mut stmt = conn.prepare("SELECT id FROM staff_groups ORDER BY name COLLATE NOCASE")?;
+
+    let ids = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>>>()?;
+
+    ids.iter().map(|id| get_staff_group(app_data_dir, id)).collect()
+}
+
+pub fn create_staff_group(app_data_dir: &PathBuf, data: CreateStaffGroup) -> Result<StaffGroup> {
+    let mut conn = get_connection(app_data_dir)?;
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT INTO staff_groups (id, name, description, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![id, data.name.trim(), data.description, now, now],
+    )?;
+    let skipped = add_group_members(&tx, &id, &data.staff_ids)?;
+    tx.commit()?;
+
+    let mut group = get_staff_group(app_data_dir, &id)?;
+    group.skipped_staff_ids = skipped;
+    Ok(group)
+}
+
+/// Rename a group and replace its member list.
+pub fn update_staff_group(app_data_dir: &PathBuf, data: UpdateStaffGroup) -> Result<StaffGroup> {
+    let mut conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.transaction()?;
+    let updated = tx.execute(
+        "UPDATE staff_groups SET name = ?2, description = ?3, updated_at = ?4 WHERE id = ?1",
+        params![data.id, data.name.trim(), data.description, now],
+    )?;
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+    tx.execute("DELETE FROM staff_group_members WHERE group_id = ?1", params![data.id])?;
+    let skipped = add_group_members(&tx, &data.id, &data.staff_ids)?;
+    tx.commit()?;
+
+    let mut group = get_staff_group(app_data_dir, &data.id)?;
+    group.skipped_staff_ids = skipped;
+    Ok(group)
+}
+
+pub fn add_staff_to_group(app_data_dir: &PathBuf, group_id: &str, staff_ids: &[String]) -> Result<StaffGroup> {
+    let mut conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.transaction()?;
+    let exists = tx
+        .query_row("SELECT 1 FROM staff_groups WHERE id = ?1", [group_id], |_| Ok(()))
+        .optional()?;
+    if exists.is_none() {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+    let skipped = add_group_members(&tx, group_id, staff_ids)?;
+    tx.execute("UPDATE staff_groups SET updated_at = ?2 WHERE id = ?1", params![group_id, now])?;
+    tx.commit()?;
+
+    let mut group = get_staff_group(app_data_dir, group_id)?;
+    group.skipped_staff_ids = skipped;
+    Ok(group)
+}
+
+pub fn remove_staff_from_group(app_data_dir: &PathBuf, group_id: &str, staff_ids: &[String]) -> Result<StaffGroup> {
+    let mut conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let tx = conn.transaction()?;
+    for staff_id in staff_ids {
+        tx.execute(
+            "DELETE FROM staff_group_members WHERE group_id = ?1 AND staff_id = ?2",
+            params![group_id, staff_id],
+        )?;
+    }
+    tx.execute("UPDATE staff_groups SET updated_at = ?2 WHERE id = ?1", params![group_id, now])?;
+    tx.commit()?;
+
+    get_staff_group(app_data_dir, group_id)
+}
+
+pub fn delete_staff_group(app_data_dir: &PathBuf, id: &str) -> Result<()> {
+    let conn = get_connection(app_data_dir)?;
+
+    // Members go with it via ON DELETE CASCADE
+    conn.execute("DELETE FROM staff_groups WHERE id = ?1", params![id])?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{insert_staff, test_database};
+
+    #[test]
+    fn missing_staff_are_left_out_of_a_group_and_reported() {
+        let dir = test_database();
+        let app_data_dir = dir.path().to_path_buf();
+        let first = insert_staff(&dir, 1);
+        let second = insert_staff(&dir, 2);
+
+        let group = create_staff_group(&app_data_dir, CreateStaffGroup {
+            name: "Field staff".to_string(),
+            description: None,
+            staff_ids: vec![first.id.clone(), "deleted".to_string()],
+        })
+        .unwrap();
+        assert_eq!(group.staff_ids, vec![first.id.clone()]);
+        assert_eq!(group.skipped_staff_ids, vec!["deleted".to_string()]);
+
+        // Already a member isn't reported as skipped
+        let group = add_staff_to_group(&app_data_dir, &group.id, &[first.id.clone(), second.id.clone()]).unwrap();
+        assert_eq!(group.staff_ids, vec![first.id, second.id]);
+        assert!(group.skipped_staff_ids.is_empty());
+    }
+}
@@ -33,13 +33,22 @@ pub struct Staff {
     pub basic_salary: f64,
     pub increment_amount: f64,
 
-    // Image (base64)
-    pub image_data: Option<String>,
+    // Photo (full image lives in staff_photos, see get_staff_photo)
+    pub has_photo: bool,
+    pub photo_thumbnail: Option<String>,
 
     // Timestamps
     // chrono::DateTime<Utc> serializes/deserializes (with chrono's serde feature) as RFC3339 strings by default.
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+
+    // Bumped on every save; send it back in UpdateStaff
+    #[serde(default)]
+    pub version: i64,
+
+    // Sensitive fields were hidden for the current user, see auth::masking
+    #[serde(default)]
+    pub masked: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,11 +113,137 @@ pub struct UpdateStaff {
     pub basic_salary: f64,
     pub increment_amount: f64,
 
-    // Image (optional base64)
-    pub image_data: Option<String>,
+    // Photo as base64, read like a `StaffPatch` field: absent leaves the
+    // stored photo alone and null (or an empty string) removes it
+    #[serde(default, deserialize_with = "patch_field")]
+    pub image_data: Option<Option<String>>,
+
+    // The `Staff::version` this edit started from; the update is refused if
+    // someone else has saved the record since
+    pub version: i64,
+}
+
+/// A field in a patch: absent (`None`) leaves it alone, `null` (`Some(None)`)
+/// clears it and a value (`Some(Some(_))`) sets it.
+fn patch_field<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Only the staff fields to change. Serializes to just the fields present,
+/// named after their columns.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StaffPatch {
+    #[serde(skip_serializing)]
+    pub id: String,
+    // As in UpdateStaff
+    #[serde(skip_serializing)]
+    pub version: i64,
+
+    // Identification & Personal Details
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub appointment_number: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub gender: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub date_of_birth: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub age: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub nic_number: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub nic_number_old: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub marital_status: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub address_line1: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub address_line2: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub address_line3: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub contact_number: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub email: Option<Option<String>>,
+
+    // Employment Details
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub designation: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub date_of_first_appointment: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub date_of_retirement: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub increment_date: Option<Option<String>>,
+
+    // Salary Information
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub salary_code: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub basic_salary: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "patch_field", skip_serializing_if = "Option::is_none")]
+    pub increment_amount: Option<Option<f64>>,
+
+    // New photo as base64, or null to remove it
+    #[serde(default, deserialize_with = "patch_field", skip_serializing)]
+    pub image_data: Option<Option<String>>,
+}
+
+/// Changes a bulk edit makes to every record it covers, read like `StaffPatch`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StaffBulkChanges {
+    #[serde(default, deserialize_with = "patch_field")]
+    pub designation: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field")]
+    pub salary_code: Option<Option<String>>,
+    // DD-MM
+    #[serde(default, deserialize_with = "patch_field")]
+    pub increment_date: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field")]
+    pub address_line1: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field")]
+    pub address_line2: Option<Option<String>>,
+    #[serde(default, deserialize_with = "patch_field")]
+    pub address_line3: Option<Option<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StaffBulkUpdateParams {
+    // When given, exactly these records; empty means "everyone matching the filters"
+    #[serde(default)]
+    pub staff_ids: Vec<String>,
+    pub filters: Option<StaffSearchParams>,
+    pub changes: StaffBulkChanges,
+}
+
+/// A record a bulk edit would change, and how.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffBulkPreviewRow {
+    pub staff: StaffSummary,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffBulkPreview {
+    pub rows: Vec<StaffBulkPreviewRow>,
+    // Covered, but already have the new values
+    pub unchanged: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffBulkUpdateResult {
+    // Shared by the audit entries for every record changed
+    pub batch_id: String,
+    pub updated: usize,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StaffSearchParams {
     pub search_term: Option<String>,
     pub designation: Option<String>,
@@ -116,10 +251,628 @@ pub struct StaffSearchParams {
     pub age_max: Option<i32>,
     pub nic_number: Option<String>,
     pub salary_code: Option<String>,
+
+    // Structured filters; `None` (or an empty list) leaves the filter off
+    pub gender: Option<String>,
+    pub marital_status: Option<String>,
+    pub designations: Option<Vec<String>>,
+    pub salary_codes: Option<Vec<String>>,
+    pub basic_salary_min: Option<f64>,
+    pub basic_salary_max: Option<f64>,
+    // Dates are YYYY-MM-DD, bounds inclusive
+    pub appointment_date_from: Option<String>,
+    pub appointment_date_to: Option<String>,
+    pub retirement_date_from: Option<String>,
+    pub retirement_date_to: Option<String>,
+    // Month of the increment date, 1-12
+    pub increment_month: Option<u32>,
+    pub has_photo: Option<bool>,
+    pub has_email: Option<bool>,
+
+    // Set by masking, never sent: the search term only matches the columns
+    // in FTS_PUBLIC_COLUMNS
+    #[serde(skip)]
+    pub public_columns_only: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PrintStaffBulkParams {
+    // Empty means "everyone matching the filters"
+    #[serde(default)]
     pub staff_ids: Vec<String>,
     pub filters: Option<StaffSearchParams>,
+    pub sort_by: Option<StaffSortColumn>,
+    pub sort_direction: Option<SortDirection>,
+}
+
+/// A column that can be included in a staff export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffExportColumn {
+    AppointmentNumber,
+    FullName,
+    Gender,
+    DateOfBirth,
+    Age,
+    NicNumber,
+    NicNumberOld,
+    MaritalStatus,
+    /// Address lines joined with commas
+    Address,
+    AddressLine1,
+    AddressLine2,
+    AddressLine3,
+    ContactNumber,
+    Email,
+    Designation,
+    DateOfFirstAppointment,
+    DateOfRetirement,
+    IncrementDate,
+    SalaryCode,
+    BasicSalary,
+    IncrementAmount,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Staff to export to CSV, selected as for bulk printing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportStaffCsvParams {
+    #[serde(flatten)]
+    pub selection: PrintStaffBulkParams,
+    // In output order; empty means the default column set
+    #[serde(default)]
+    pub columns: Vec<StaffExportColumn>,
+    // Dates as dd-mm-yyyy and amounts as "Rs. 1,234.00" instead of ISO dates and plain numbers
+    #[serde(default)]
+    pub format_values: bool,
+    // Start with a UTF-8 byte order mark so Excel picks the right encoding
+    #[serde(default)]
+    pub include_bom: bool,
+}
+
+/// Staff to export to an Excel workbook, selected as for bulk printing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportStaffXlsxParams {
+    #[serde(flatten)]
+    pub selection: PrintStaffBulkParams,
+    // In output order; empty means the default column set
+    #[serde(default)]
+    pub columns: Vec<StaffExportColumn>,
+    // One sheet per designation instead of a single "Staff" sheet
+    #[serde(default)]
+    pub sheet_per_designation: bool,
+    // Add a sheet with staff counts and salary totals by designation and salary code
+    #[serde(default)]
+    pub include_summary: bool,
+}
+
+/// A `CreateStaff` field that an import column can be mapped to. Age is
+/// always computed from the date of birth and photos are not imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffImportField {
+    AppointmentNumber,
+    FullName,
+    Gender,
+    DateOfBirth,
+    NicNumber,
+    NicNumberOld,
+    MaritalStatus,
+    AddressLine1,
+    AddressLine2,
+    AddressLine3,
+    ContactNumber,
+    Email,
+    Designation,
+    DateOfFirstAppointment,
+    DateOfRetirement,
+    IncrementDate,
+    SalaryCode,
+    BasicSalary,
+    IncrementAmount,
+}
+
+/// Which spreadsheet column (by header text) fills which staff field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportColumnMapping {
+    pub column: String,
+    pub field: StaffImportField,
+}
+
+/// A named column mapping, kept for files that arrive in the same layout every time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportMapping {
+    pub id: String,
+    pub name: String,
+    pub columns: Vec<ImportColumnMapping>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateImportMapping {
+    pub name: String,
+    pub columns: Vec<ImportColumnMapping>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateImportMapping {
+    pub id: String,
+    pub name: String,
+    pub columns: Vec<ImportColumnMapping>,
+}
+
+/// Headers and the first few rows of a file about to be imported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPreview {
+    // Worksheet names; empty for CSV files
+    pub sheets: Vec<String>,
+    pub sheet: Option<String>,
+    pub headers: Vec<String>,
+    pub sample_rows: Vec<Vec<String>>,
+    pub total_rows: usize,
+    // Columns whose header matches a field name or an export column label
+    pub suggested_columns: Vec<ImportColumnMapping>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportStaffParams {
+    pub path: String,
+    // Worksheet to read from an Excel file; the first one when not given
+    pub sheet: Option<String>,
+    // A saved mapping, used instead of `columns` when given
+    pub mapping_id: Option<String>,
+    #[serde(default)]
+    pub columns: Vec<ImportColumnMapping>,
+    // Validate and report without saving anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A NIC or appointment number that is already taken.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportConflict {
+    pub field: StaffImportField,
+    pub value: String,
+    // Set when the number belongs to a staff member already in the database
+    pub existing_staff_id: Option<String>,
+    // Set when the number appears on an earlier row of the same file
+    pub existing_row: Option<usize>,
+}
+
+/// Problems found on one row. `row` is the line or sheet row number, counting the header as row 1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRowReport {
+    pub row: usize,
+    pub appointment_number: Option<String>,
+    pub full_name: Option<String>,
+    pub errors: Vec<String>,
+    pub conflicts: Vec<ImportConflict>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total_rows: usize,
+    pub valid_rows: usize,
+    // Rows saved; always 0 for a dry run
+    pub imported: usize,
+    pub imported_ids: Vec<String>,
+    // Only rows with errors or conflicts, which are never imported
+    pub rows: Vec<ImportRowReport>,
+}
+
+/// The unique numbers that identify a staff member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffIdentity {
+    pub id: String,
+    pub appointment_number: String,
+    pub nic_number: String,
+    pub nic_number_old: Option<String>,
+}
+
+/// Lightweight row for list and search views; no photo beyond the thumbnail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffSummary {
+    pub id: String,
+    pub appointment_number: String,
+    pub full_name: String,
+    pub gender: String,
+    pub age: i32,
+    pub nic_number: String,
+    pub nic_number_old: Option<String>,
+    pub designation: String,
+    pub contact_number: Option<String>,
+    pub email: Option<String>,
+    pub salary_code: String,
+    pub basic_salary: f64,
+    pub has_photo: bool,
+    pub photo_thumbnail: Option<String>,
+    // Only set for full-text searches
+    pub snippet: Option<Vec<SnippetSegment>>,
+    #[serde(default)]
+    pub masked: bool,
+}
+
+/// Piece of a search snippet; `highlighted` segments matched the search term.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnippetSegment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffSortColumn {
+    #[default]
+    FullName,
+    AppointmentNumber,
+    Designation,
+    Age,
+    NicNumber,
+    SalaryCode,
+    BasicSalary,
+    DateOfFirstAppointment,
+    DateOfRetirement,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StaffPageParams {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub sort_by: Option<StaffSortColumn>,
+    pub sort_direction: Option<SortDirection>,
+}
+
+/// One page of results plus the total number of matching rows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffPage<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: String,
+    pub name: String,
+    pub params: StaffSearchParams,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSavedSearch {
+    pub name: String,
+    pub params: StaffSearchParams,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateSavedSearch {
+    pub id: String,
+    pub name: String,
+    pub params: StaffSearchParams,
+}
+
+/// Static, hand-picked list of staff. `staff_ids` are in the order they were added.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffGroup {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub staff_ids: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    // Ids left out of a create, update or add because they aren't staff
+    // records; empty everywhere else
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_staff_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateStaffGroup {
+    pub name: String,
+    pub description: Option<String>,
+    pub staff_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateStaffGroup {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub staff_ids: Vec<String>,
+}
+
+/// Office details used in the letterhead, footer and signature block of every report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfficeSettings {
+    pub office_name: String,
+    pub division: String,
+    pub address: String,
+    pub phone: Option<String>,
+    /// Base64 JPEG, already normalised by `process_logo`
+    pub logo: Option<String>,
+    pub certifying_officer_name: Option<String>,
+    pub certifying_officer_designation: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Everything but the logo, which has its own commands since it is uploaded separately.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateOfficeSettings {
+    pub office_name: String,
+    pub division: String,
+    pub address: String,
+    pub phone: Option<String>,
+    pub certifying_officer_name: Option<String>,
+    pub certifying_officer_designation: String,
+}
+
+/// Automatic backup schedule and retention, plus the outcome of the last attempt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSettings {
+    pub enabled: bool,
+    // None means the backups folder in the app data directory
+    pub folder: Option<String>,
+    pub interval_hours: u32,
+    pub keep_daily: u32,
+    pub keep_weekly: u32,
+    pub keep_monthly: u32,
+    pub last_backup_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateBackupSettings {
+    pub enabled: bool,
+    pub folder: Option<String>,
+    pub interval_hours: u32,
+    pub keep_daily: u32,
+    pub keep_weekly: u32,
+    pub keep_monthly: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub file_name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptionStatus {
+    pub encrypted: bool,
+    /// False until the passphrase has been entered for an encrypted database
+    pub unlocked: bool,
+}
+
+/// Identifies files written by `export_all`.
+pub const TRANSFER_FORMAT: &str = "forest-office-staff";
+
+/// Version of the `export_all` file layout. Files from a newer version are refused.
+pub const TRANSFER_FORMAT_VERSION: u32 = 1;
+
+/// A staff row exactly as stored, photo included, for moving records between installations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffRecord {
+    pub id: String,
+    pub appointment_number: String,
+    pub full_name: String,
+    pub gender: String,
+    pub date_of_birth: String,
+    pub age: i32,
+    pub nic_number: String,
+    pub nic_number_old: Option<String>,
+    pub marital_status: String,
+    pub address_line1: Option<String>,
+    pub address_line2: Option<String>,
+    pub address_line3: Option<String>,
+    pub contact_number: Option<String>,
+    pub email: Option<String>,
+    pub designation: String,
+    pub date_of_first_appointment: String,
+    pub date_of_retirement: String,
+    pub increment_date: Option<String>,
+    pub salary_code: String,
+    pub basic_salary: f64,
+    pub increment_amount: f64,
+    // Base64 JPEG; thumbnails are regenerated on import
+    pub photo: Option<String>,
+    // Kept as stored rather than parsed, so every row can be exported
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// The whole database as written by `export_all`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseExport {
+    pub format: String,
+    pub format_version: u32,
+    // Migration level of the exporting database, for reference
+    pub schema_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub office_settings: Option<OfficeSettings>,
+    pub staff: Vec<StaffRecord>,
+    #[serde(default)]
+    pub saved_searches: Vec<SavedSearch>,
+    #[serde(default)]
+    pub staff_groups: Vec<StaffGroup>,
+    #[serde(default)]
+    pub import_mappings: Vec<ImportMapping>,
+}
+
+/// What to do with an incoming record whose NIC, appointment number or name
+/// belongs to a different record already in the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferConflictPolicy {
+    /// Keep the local record and leave the incoming one out
+    #[default]
+    Skip,
+    /// Delete the local record and import the incoming one with its own id
+    Replace,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportAllParams {
+    pub path: String,
+    #[serde(default)]
+    pub on_conflict: TransferConflictPolicy,
+    // Overwrite the letterhead and logo with the ones in the file
+    #[serde(default)]
+    pub include_office_settings: bool,
+    // Work out the report without saving anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TransferCounts {
+    pub inserted: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferConflict {
+    // "staff", "saved_search", "staff_group" or "import_mapping"
+    pub table: String,
+    pub id: String,
+    // Full name or record name, for display
+    pub name: String,
+    // The column that clashed: nic_number, appointment_number or name, or
+    // updated_at when the local copy of the same record is newer
+    pub field: String,
+    pub value: String,
+    pub existing_id: String,
+    pub resolution: TransferConflictPolicy,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferReport {
+    pub dry_run: bool,
+    pub staff: TransferCounts,
+    pub saved_searches: TransferCounts,
+    pub staff_groups: TransferCounts,
+    pub import_mappings: TransferCounts,
+    pub office_settings_updated: bool,
+    pub conflicts: Vec<TransferConflict>,
+}
+
+/// What a user may do. Each role can do everything the ones before it can.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserRole {
+    /// Look up staff and print reports
+    Viewer,
+    /// Also add and edit staff, apart from salary details
+    Clerk,
+    /// Also change salaries, delete staff and manage users
+    Admin,
+}
+
+/// A login account. The password hash never leaves the database layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    pub full_name: String,
+    pub role: UserRole,
+    pub active: bool,
+    pub last_login_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateUser {
+    pub username: String,
+    pub full_name: String,
+    pub password: String,
+    pub role: UserRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateUser {
+    pub id: String,
+    pub full_name: String,
+    pub role: UserRole,
+    pub active: bool,
+}
+
+/// Whether anyone has to log in yet, and who is logged in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthStatus {
+    // False on a fresh install, until the first administrator is created
+    pub has_users: bool,
+    pub user: Option<User>,
+    // The session has been idle too long; the password unlocks it again
+    pub locked: bool,
+}
+
+/// Payload of the `session-lock-changed` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionLockChanged {
+    pub locked: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecuritySettings {
+    // 0 means the session never locks
+    pub idle_timeout_minutes: u32,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateSecuritySettings {
+    pub idle_timeout_minutes: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: serde_json::Value,
+    pub new_value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub user_id: Option<String>,
+    pub username: String,
+    pub action: String,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    // The staff version the change produced
+    pub entity_version: Option<i64>,
+    pub description: Option<String>,
+    pub changes: Vec<FieldChange>,
+    // Set on every entry written by the same bulk edit
+    pub batch_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditLogParams {
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub user_id: Option<String>,
+    pub batch_id: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
 }
@@ -0,0 +1,174 @@
+// src/database/settings.rs
+//
+// Office letterhead and signatory settings, stored as the single row of
+// office_settings, the backup schedule in the single row of backup_settings
+// and the session idle timeout in the single row of security_settings.
+use rusqlite::{params, Result, Row};
+use std::path::PathBuf;
+use crate::database::operations::{get_connection, parse_datetime_from_row};
+use crate::database::schema::{
+    OfficeSettings, UpdateOfficeSettings, BackupSettings, UpdateBackupSettings, SecuritySettings, UpdateSecuritySettings,
+};
+
+fn map_office_settings_row(row: &Row) -> Result<OfficeSettings> {
+    Ok(OfficeSettings {
+        office_name: row.get(0)?,
+        division: row.get(1)?,
+        address: row.get(2)?,
+        phone: row.get(3)?,
+        logo: row.get(4)?,
+        certifying_officer_name: row.get(5)?,
+        certifying_officer_designation: row.get(6)?,
+        updated_at: parse_datetime_from_row(7, row.get(7)?)?,
+    })
+}
+
+pub fn get_office_settings(app_data_dir: &PathBuf) -> Result<OfficeSettings> {
+    let conn = get_connection(app_data_dir)?;
+
+    conn.query_row(
+        r#"
+        SELECT office_name, division, address, phone, logo,
+               certifying_officer_name, certifying_officer_designation, updated_at
+        FROM office_settings WHERE id = 1
+        "#,
+        [],
+        map_office_settings_row,
+    )
+}
+
+pub fn update_office_settings(app_data_dir: &PathBuf, data: UpdateOfficeSettings) -> Result<OfficeSettings> {
+    let conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        r#"
+        UPDATE office_settings SET
+            office_name = ?1, division = ?2, address = ?3, phone = ?4,
+            certifying_officer_name = ?5, certifying_officer_designation = ?6, updated_at = ?7
+        WHERE id = 1
+        "#,
+        params![
+            data.office_name,
+            data.division,
+            data.address,
+            data.phone,
+            data.certifying_officer_name,
+            data.certifying_officer_designation,
+            now,
+        ],
+    )?;
+
+    get_office_settings(app_data_dir)
+}
+
+/// Replace the logo; `None` removes it.
+pub fn set_office_logo(app_data_dir: &PathBuf, logo: Option<&str>) -> Result<OfficeSettings> {
+    let conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE office_settings SET logo = ?1, updated_at = ?2 WHERE id = 1",
+        params![logo, now],
+    )?;
+
+    get_office_settings(app_data_dir)
+}
+
+fn map_backup_settings_row(row: &Row) -> Result<BackupSettings> {
+    let last_backup_at: Option<String> = row.get(6)?;
+
+    Ok(BackupSettings {
+        enabled: row.get(0)?,
+        folder: row.get(1)?,
+        interval_hours: row.get(2)?,
+        keep_daily: row.get(3)?,
+        keep_weekly: row.get(4)?,
+        keep_monthly: row.get(5)?,
+        last_backup_at: last_backup_at.map(|value| parse_datetime_from_row(6, value)).transpose()?,
+        last_error: row.get(7)?,
+        updated_at: parse_datetime_from_row(8, row.get(8)?)?,
+    })
+}
+
+pub fn get_backup_settings(app_data_dir: &PathBuf) -> Result<BackupSettings> {
+    let conn = get_connection(app_data_dir)?;
+
+    conn.query_row(
+        r#"
+        SELECT enabled, folder, interval_hours, keep_daily, keep_weekly, keep_monthly,
+               last_backup_at, last_error, updated_at
+        FROM backup_settings WHERE id = 1
+        "#,
+        [],
+        map_backup_settings_row,
+    )
+}
+
+pub fn update_backup_settings(app_data_dir: &PathBuf, data: UpdateBackupSettings) -> Result<BackupSettings> {
+    let conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        r#"
+        UPDATE backup_settings SET
+            enabled = ?1, folder = ?2, interval_hours = ?3,
+            keep_daily = ?4, keep_weekly = ?5, keep_monthly = ?6, updated_at = ?7
+        WHERE id = 1
+        "#,
+        params![
+            data.enabled,
+            data.folder,
+            data.interval_hours,
+            data.keep_daily,
+            data.keep_weekly,
+            data.keep_monthly,
+            now,
+        ],
+    )?;
+
+    get_backup_settings(app_data_dir)
+}
+
+/// Note the outcome of a backup attempt. A failure keeps the time of the last
+/// good backup, so the scheduler keeps retrying until one succeeds.
+pub fn record_backup_result(app_data_dir: &PathBuf, error: Option<&str>) -> Result<()> {
+    let conn = get_connection(app_data_dir)?;
+
+    match error {
+        Some(error) => conn.execute("UPDATE backup_settings SET last_error = ?1 WHERE id = 1", params![error])?,
+        None => conn.execute(
+            "UPDATE backup_settings SET last_backup_at = ?1, last_error = NULL WHERE id = 1",
+            params![chrono::Utc::now().to_rfc3339()],
+        )?,
+    };
+
+    Ok(())
+}
+
+pub fn get_security_settings(app_data_dir: &PathBuf) -> Result<SecuritySettings> {
+    let conn = get_connection(app_data_dir)?;
+
+    conn.query_row(
+        "SELECT idle_timeout_minutes, updated_at FROM security_settings WHERE id = 1",
+        [],
+        |row| {
+            Ok(SecuritySettings {
+                idle_timeout_minutes: row.get(0)?,
+                updated_at: parse_datetime_from_row(1, row.get(1)?)?,
+            })
+        },
+    )
+}
+
+pub fn update_security_settings(app_data_dir: &PathBuf, data: UpdateSecuritySettings) -> Result<SecuritySettings> {
+    let conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE security_settings SET idle_timeout_minutes = ?1, updated_at = ?2 WHERE id = 1",
+        params![data.idle_timeout_minutes, now],
+    )?;
+
+    get_security_settings(app_data_dir)
+}
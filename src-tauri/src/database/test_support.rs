@@ -0,0 +1,56 @@
+//! Fixtures shared by the database tests.
+use tempfile::TempDir;
+use crate::database::operations::{create_staff, initialize_database};
+use crate::database::schema::{CreateStaff, Staff, User, UserRole};
+
+/// A migrated database in a directory removed when the `TempDir` is dropped.
+pub fn test_database() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    initialize_database(&dir.path().to_path_buf()).unwrap();
+    dir
+}
+
+pub fn new_staff(n: u32) -> CreateStaff {
+    serde_json::from_value(serde_json::json!({
+        "appointment_number": format!("APP/{}", n),
+        "full_name": format!("Person {} Kumar", n),
+        "gender": "Male",
+        "date_of_birth": "1980-05-01",
+        "age": 44,
+        "nic_number": format!("19801{:07}", n),
+        "nic_number_old": null,
+        "marital_status": "Married",
+        "address_line1": "12, Station Road",
+        "address_line2": "Vavuniya",
+        "address_line3": null,
+        "contact_number": "0771234567",
+        "email": format!("person{}@forest.lk", n),
+        "designation": "Development Officer",
+        "date_of_first_appointment": "2005-01-01",
+        "date_of_retirement": "2040-05-01",
+        "increment_date": "15-03",
+        "salary_code": "MN-1-2016",
+        "basic_salary": 50000.0,
+        "increment_amount": 1200.0,
+        "image_data": null
+    }))
+    .unwrap()
+}
+
+pub fn insert_staff(dir: &TempDir, n: u32) -> Staff {
+    create_staff(&dir.path().to_path_buf(), new_staff(n)).unwrap()
+}
+
+pub fn user(role: UserRole) -> User {
+    let now = chrono::Utc::now();
+    User {
+        id: format!("{:?}", role).to_lowercase(),
+        username: format!("{:?}", role).to_lowercase(),
+        full_name: format!("{:?}", role),
+        role,
+        active: true,
+        last_login_at: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
@@ -0,0 +1,131 @@
+//! Triggers that refuse timestamps that aren't RFC3339 (e.g.
+//! 2024-05-01T09:30:00+00:00), so nothing writing to the database directly can
+//! bring back the formats migration 012 converted.
+//!
+//! The triggers are built from [`TIMESTAMP_COLUMNS`] rather than written out
+//! by hand, so every column gets exactly the same check. NULL is left to the
+//! columns' NOT NULL constraints, and an update only has the values it
+//! changes checked.
+
+/// Every timestamp column, as (table, column).
+pub const TIMESTAMP_COLUMNS: &[(&str, &str)] = &[
+    ("staff", "created_at"),
+    ("staff", "updated_at"),
+    ("staff_photos", "updated_at"),
+    ("saved_searches", "created_at"),
+    ("saved_searches", "updated_at"),
+    ("staff_groups", "created_at"),
+    ("staff_groups", "updated_at"),
+    ("office_settings", "updated_at"),
+    ("import_mappings", "created_at"),
+    ("import_mappings", "updated_at"),
+    ("backup_settings", "last_backup_at"),
+    ("backup_settings", "updated_at"),
+    ("users", "last_login_at"),
+    ("users", "created_at"),
+    ("users", "updated_at"),
+    ("audit_log", "created_at"),
+    ("security_settings", "updated_at"),
+];
+
+/// Tables whose new rows may still take the CURRENT_TIMESTAMP default, which
+/// the staff_timestamp_format trigger from 012 converts straight after the insert.
+const CURRENT_TIMESTAMP_TABLES: &[&str] = &["staff"];
+
+const EXAMPLE: &str = "2024-05-01T09:30:00+00:00";
+
+/// SQL for the insert and update triggers on every table in [`TIMESTAMP_COLUMNS`].
+pub fn timestamp_check_sql() -> String {
+    let mut tables: Vec<(&str, Vec<&str>)> = Vec::new();
+    for (table, column) in TIMESTAMP_COLUMNS {
+        match tables.iter_mut().find(|(name, _)| name == table) {
+            Some((_, columns)) => columns.push(column),
+            None => tables.push((table, vec![column])),
+        }
+    }
+
+    let mut sql = String::new();
+    for (table, columns) in tables {
+        let allow_default = CURRENT_TIMESTAMP_TABLES.contains(&table);
+        let inserted: Vec<String> = columns.iter().map(|column| bad_value(column, false, allow_default)).collect();
+        let updated: Vec<String> = columns.iter().map(|column| bad_value(column, true, false)).collect();
+
+        sql.push_str(&trigger(table, "insert", &format!("INSERT ON {}", table), &inserted));
+        sql.push_str(&trigger(
+            table,
+            "update",
+            &format!("UPDATE OF {} ON {}", columns.join(", "), table),
+            &updated,
+        ));
+    }
+
+    sql
+}
+
+fn trigger(table: &str, suffix: &str, event: &str, conditions: &[String]) -> String {
+    format!(
+        "CREATE TRIGGER IF NOT EXISTS {table}_timestamp_check_{suffix}\n    \
+         BEFORE {event}\n    \
+         FOR EACH ROW\n    \
+         WHEN {conditions}\n    \
+         BEGIN\n        \
+         SELECT RAISE(ABORT, '{table}: timestamps must be RFC3339, e.g. {EXAMPLE}');\n    \
+         END;\n\n",
+        conditions = conditions.join(" OR "),
+    )
+}
+
+/// Condition that holds when the new value of `column` is not an RFC3339 timestamp.
+fn bad_value(column: &str, changed_only: bool, allow_default: bool) -> String {
+    let value = format!("NEW.{}", column);
+    let changed = if changed_only { format!(" AND {} IS NOT OLD.{}", value, column) } else { String::new() };
+    let default = if allow_default {
+        format!(" OR {} GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9] [0-9][0-9]:[0-9][0-9]:[0-9][0-9]'", value)
+    } else {
+        String::new()
+    };
+
+    format!(
+        "{value} IS NOT NULL{changed} AND NOT (\
+         {value} GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]T[0-9][0-9]:[0-9][0-9]:[0-9][0-9]*' \
+         AND ({value} GLOB '*Z' OR {value} GLOB '*[+-][0-9][0-9]:[0-9][0-9]') \
+         AND datetime({value}) IS NOT NULL{default})"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::operations::get_connection;
+    use crate::database::test_support::test_database;
+
+    #[test]
+    fn every_timestamp_column_is_checked() {
+        let dir = test_database();
+        let conn = get_connection(&dir.path().to_path_buf()).unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT m.name, p.name FROM sqlite_master m, pragma_table_info(m.name) p
+                 WHERE m.type = 'table' AND p.name LIKE '%\\_at' ESCAPE '\\' ORDER BY m.name, p.cid",
+            )
+            .unwrap();
+        let mut found: Vec<(String, String)> =
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap().collect::<Result<_, _>>().unwrap();
+        found.sort();
+
+        let mut expected: Vec<(String, String)> = TIMESTAMP_COLUMNS
+            .iter()
+            .map(|(table, column)| (table.to_string(), column.to_string()))
+            .collect();
+        expected.sort();
+        assert_eq!(found, expected);
+
+        // An insert and an update trigger per table
+        let mut tables: Vec<&str> = TIMESTAMP_COLUMNS.iter().map(|(table, _)| *table).collect();
+        tables.dedup();
+        let sql = "SELECT COUNT(*) FROM sqlite_master WHERE type = 'trigger' AND name LIKE '%timestamp_check%'";
+        let triggers: usize = conn.query_row(sql, [], |row| row.get(0)).unwrap();
+        assert_eq!(triggers, tables.len() * 2);
+    }
+}
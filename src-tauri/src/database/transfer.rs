@@ -0,0 +1,542 @@
+// src/database/transfer.rs
+//
+// Whole-database export and import for moving records between machines.
+// Imports keep every record's id: a record whose id already exists is
+// updated in place, anything else is inserted. A record that clashes with a
+// different local record (same NIC or appointment number for staff, same name
+// for saved searches, groups and mappings) is skipped or replaces the local
+// one, per `TransferConflictPolicy`. A staff record edited here since the
+// export was written is kept as it is and reported as a conflict.
+//
+// Users, the audit log, and the backup and security settings are left out
+// on purpose. Accounts and passwords belong to one machine, the audit log
+// must only ever describe what happened on it, and backup folders and lock
+// rules are local choices. Office settings are only applied when asked for.
+use chrono::DateTime;
+use rusqlite::{Connection, OptionalExtension, Result, params};
+use std::path::PathBuf;
+use crate::database::import_mappings::list_import_mappings;
+use crate::database::operations::{get_connection, save_staff_photo};
+use crate::database::saved_searches::{list_saved_searches, list_staff_groups};
+use crate::database::settings::get_office_settings;
+use crate::database::schema::{
+    DatabaseExport, StaffRecord, TransferConflict, TransferConflictPolicy, TransferCounts, TransferReport,
+    OfficeSettings, TRANSFER_FORMAT, TRANSFER_FORMAT_VERSION,
+};
+
+pub fn export_all(app_data_dir: &PathBuf) -> Result<DatabaseExport> {
+    let conn = get_connection(app_data_dir)?;
+    let schema_version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT s.id, s.appointment_number, s.full_name, s.gender, s.date_of_birth, s.age,
+               s.nic_number, s.nic_number_old, s.marital_status, s.address_line1, s.address_line2, s.address_line3,
+               s.contact_number, s.email, s.designation, s.date_of_first_appointment, s.date_of_retirement,
+               s.increment_date, s.salary_code, s.basic_salary, s.increment_amount,
+               p.image_data, s.created_at, s.updated_at
+        FROM staff s
+        LEFT JOIN staff_photos p ON p.staff_id = s.id
+        ORDER BY s.created_at, s.id
+        "#,
+    )?;
+    let staff = stmt
+        .query_map([], |row| {
+            Ok(StaffRecord {
+                id: row.get(0)?,
+                appointment_number: row.get(1)?,
+                full_name: row.get(2)?,
+                gender: row.get(3)?,
+                date_of_birth: row.get(4)?,
+                age: row.get(5)?,
+                nic_number: row.get(6)?,
+                nic_number_old: row.get(7)?,
+                marital_status: row.get(8)?,
+                address_line1: row.get(9)?,
+                address_line2: row.get(10)?,
+                address_line3: row.get(11)?,
+                contact_number: row.get(12)?,
+                email: row.get(13)?,
+                designation: row.get(14)?,
+                date_of_first_appointment: row.get(15)?,
+                date_of_retirement: row.get(16)?,
+                increment_date: row.get(17)?,
+                salary_code: row.get(18)?,
+                basic_salary: row.get(19)?,
+                increment_amount: row.get(20)?,
+                photo: row.get(21)?,
+                created_at: row.get(22)?,
+                updated_at: row.get(23)?,
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(DatabaseExport {
+        format: TRANSFER_FORMAT.to_string(),
+        format_version: TRANSFER_FORMAT_VERSION,
+        schema_version,
+        exported_at: chrono::Utc::now(),
+        office_settings: Some(get_office_settings(app_data_dir)?),
+        staff,
+        saved_searches: list_saved_searches(app_data_dir)?,
+        staff_groups: list_staff_groups(app_data_dir)?,
+        import_mappings: list_import_mappings(app_data_dir)?,
+    })
+}
+
+/// Parse a file written by `export_all`, refusing other files and newer formats.
+pub fn read_export(json: &[u8]) -> std::result::Result<DatabaseExport, String> {
+    // Check the header first so a newer file gets a clear message rather than a parse error
+    let value: serde_json::Value = serde_json::from_slice(json)
+        .map_err(|e| format!("The file is not a database export: {}", e))?;
+    if value.get("format").and_then(|format| format.as_str()) != Some(TRANSFER_FORMAT) {
+        return Err("The file is not a database export from this application".to_string());
+    }
+    let format_version = value.get("format_version").and_then(|version| version.as_u64()).unwrap_or(0);
+    if format_version == 0 || format_version > TRANSFER_FORMAT_VERSION as u64 {
+        return Err(format!(
+            "The export uses format version {}, but this version of the app reads up to version {}; update the app first",
+            format_version, TRANSFER_FORMAT_VERSION
+        ));
+    }
+
+    serde_json::from_value(value).map_err(|e| format!("The database export is damaged: {}", e))
+}
+
+/// Import everything in `data` in one transaction. A dry run does the same
+/// work and rolls it back, so its report is exactly what a real run would do.
+pub fn import_all(
+    app_data_dir: &PathBuf,
+    data: &DatabaseExport,
+    policy: TransferConflictPolicy,
+    include_office_settings: bool,
+    dry_run: bool,
+) -> Result<TransferReport> {
+    let mut conn = get_connection(app_data_dir)?;
+    let mut report = TransferReport { dry_run, ..Default::default() };
+
+    let tx = conn.transaction()?;
+    for record in &data.staff {
+        import_staff_record(&tx, record, policy, &mut report)?;
+    }
+    import_saved_searches(&tx, data, policy, &mut report)?;
+    import_staff_groups(&tx, data, policy, &mut report)?;
+    import_import_mappings(&tx, data, policy, &mut report)?;
+    if include_office_settings {
+        if let Some(settings) = &data.office_settings {
+            import_office_settings(&tx, settings)?;
+            report.office_settings_updated = true;
+        }
+    }
+
+    if dry_run {
+        tx.rollback()?;
+    } else {
+        tx.commit()?;
+    }
+
+    Ok(report)
+}
+
+fn exists(conn: &Connection, table: &str, id: &str) -> Result<bool> {
+    conn.query_row(&format!("SELECT 1 FROM {} WHERE id = ?1", table), [id], |_| Ok(()))
+        .optional()
+        .map(|row| row.is_some())
+}
+
+/// Id of a different record in `table` whose `column` holds `value`.
+fn clashing_id(conn: &Connection, table: &str, column: &str, value: &str, id: &str) -> Result<Option<String>> {
+    conn.query_row(
+        &format!("SELECT id FROM {} WHERE {} = ?1 AND id <> ?2", table, column),
+        params![value, id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Singular name of a table's records, as reported in conflicts.
+fn record_kind(table: &str) -> &str {
+    match table {
+        "saved_searches" => "saved_search",
+        "staff_groups" => "staff_group",
+        "import_mappings" => "import_mapping",
+        _ => table,
+    }
+}
+
+/// Record any clashes on `columns` and, under `Replace`, delete the local
+/// records involved. Returns true when the incoming record should be skipped.
+fn resolve_conflicts(
+    conn: &Connection,
+    table: &str,
+    id: &str,
+    name: &str,
+    columns: &[(&str, &str)],
+    policy: TransferConflictPolicy,
+    report: &mut TransferReport,
+) -> Result<bool> {
+    let mut clashes = Vec::new();
+    for (column, value) in columns {
+        if let Some(existing_id) = clashing_id(conn, table, column, value, id)? {
+            clashes.push(TransferConflict {
+                table: record_kind(table).to_string(),
+                id: id.to_string(),
+                name: name.to_string(),
+                field: column.to_string(),
+                value: value.to_string(),
+                existing_id,
+                resolution: policy,
+            });
+        }
+    }
+
+    if clashes.is_empty() {
+        return Ok(false);
+    }
+    if policy == TransferConflictPolicy::Replace {
+        for clash in &clashes {
+            conn.execute(&format!("DELETE FROM {} WHERE id = ?1", table), [&clash.existing_id])?;
+        }
+    }
+    report.conflicts.extend(clashes);
+
+    Ok(policy == TransferConflictPolicy::Skip)
+}
+
+fn count(counts: &mut TransferCounts, existed: bool) {
+    if existed {
+        counts.updated += 1;
+    } else {
+        counts.inserted += 1;
+    }
+}
+
+/// Whether the local copy of a record was saved after the incoming one.
+fn is_stale(conn: &Connection, table: &str, id: &str, updated_at: &str) -> Result<bool> {
+    let local: Option<String> = conn
+        .query_row(&format!("SELECT updated_at FROM {} WHERE id = ?1", table), [id], |row| row.get(0))
+        .optional()?;
+    let parse = |value: &str| DateTime::parse_from_rfc3339(value).ok();
+
+    Ok(match (local.as_deref().and_then(parse), parse(updated_at)) {
+        (Some(local), Some(incoming)) => local > incoming,
+        _ => false,
+    })
+}
+
+fn import_staff_record(
+    conn: &Connection,
+    record: &StaffRecord,
+    policy: TransferConflictPolicy,
+    report: &mut TransferReport,
+) -> Result<()> {
+    // An edit made here since the export is kept whatever the policy
+    if is_stale(conn, "staff", &record.id, &record.updated_at)? {
+        report.conflicts.push(TransferConflict {
+            table: "staff".to_string(),
+            id: record.id.clone(),
+            name: record.full_name.clone(),
+            field: "updated_at".to_string(),
+            value: record.updated_at.clone(),
+            existing_id: record.id.clone(),
+            resolution: TransferConflictPolicy::Skip,
+        });
+        report.staff.skipped += 1;
+        return Ok(());
+    }
+
+    let columns = [("nic_number", record.nic_number.as_str()), ("appointment_number", record.appointment_number.as_str())];
+    if resolve_conflicts(conn, "staff", &record.id, &record.full_name, &columns, policy, report)? {
+        report.staff.skipped += 1;
+        return Ok(());
+    }
+
+    let existed = exists(conn, "staff", &record.id)?;
+    conn.execute(
+        r#"
+        INSERT INTO staff (
+            id, appointment_number, full_name, gender, date_of_birth, age,
+            nic_number, nic_number_old, marital_status, address_line1, address_line2, address_line3,
+            contact_number, email, designation, date_of_first_appointment, date_of_retirement,
+            increment_date, salary_code, basic_salary, increment_amount, created_at, updated_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)
+        ON CONFLICT(id) DO UPDATE SET
+            appointment_number = excluded.appointment_number, full_name = excluded.full_name,
+            gender = excluded.gender, date_of_birth = excluded.date_of_birth, age = excluded.age,
+            nic_number = excluded.nic_number, nic_number_old = excluded.nic_number_old,
+            marital_status = excluded.marital_status, address_line1 = excluded.address_line1,
+            address_line2 = excluded.address_line2, address_line3 = excluded.address_line3,
+            contact_number = excluded.contact_number, email = excluded.email, designation = excluded.designation,
+            date_of_first_appointment = excluded.date_of_first_appointment,
+            date_of_retirement = excluded.date_of_retirement, increment_date = excluded.increment_date,
+            salary_code = excluded.salary_code, basic_salary = excluded.basic_salary,
+            increment_amount = excluded.increment_amount, created_at = excluded.created_at,
+            updated_at = excluded.updated_at, version = staff.version + 1
+        "#,
+        params![
+            record.id, record.appointment_number, record.full_name, record.gender, record.date_of_birth,
+            record.age, record.nic_number, record.nic_number_old, record.marital_status, record.address_line1,
+            record.address_line2, record.address_line3, record.contact_number, record.email, record.designation,
+            record.date_of_first_appointment, record.date_of_retirement, record.increment_date, record.salary_code,
+            record.basic_salary, record.increment_amount, record.created_at, record.updated_at
+        ],
+    )?;
+    save_staff_photo(conn, &record.id, record.photo.as_deref(), &record.updated_at)?;
+
+    count(&mut report.staff, existed);
+    Ok(())
+}
+
+fn import_saved_searches(
+    conn: &Connection,
+    data: &DatabaseExport,
+    policy: TransferConflictPolicy,
+    report: &mut TransferReport,
+) -> Result<()> {
+    for saved_search in &data.saved_searches {
+        let columns = [("name", saved_search.name.as_str())];
+        if resolve_conflicts(conn, "saved_searches", &saved_search.id, &saved_search.name, &columns, policy, report)? {
+            report.saved_searches.skipped += 1;
+            continue;
+        }
+
+        let existed = exists(conn, "saved_searches", &saved_search.id)?;
+        let params_json = serde_json::to_string(&saved_search.params)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            r#"
+            INSERT INTO saved_searches (id, name, params, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name, params = excluded.params,
+                created_at = excluded.created_at, updated_at = excluded.updated_at
+            "#,
+            params![
+                saved_search.id, saved_search.name, params_json,
+                saved_search.created_at.to_rfc3339(), saved_search.updated_at.to_rfc3339()
+            ],
+        )?;
+
+        count(&mut report.saved_searches, existed);
+    }
+
+    Ok(())
+}
+
+fn import_staff_groups(
+    conn: &Connection,
+    data: &DatabaseExport,
+    policy: TransferConflictPolicy,
+    report: &mut TransferReport,
+) -> Result<()> {
+    for group in &data.staff_groups {
+        let columns = [("name", group.name.as_str())];
+        if resolve_conflicts(conn, "staff_groups", &group.id, &group.name, &columns, policy, report)? {
+            report.staff_groups.skipped += 1;
+            continue;
+        }
+
+        let existed = exists(conn, "staff_groups", &group.id)?;
+        conn.execute(
+            r#"
+            INSERT INTO staff_groups (id, name, description, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name, description = excluded.description,
+                created_at = excluded.created_at, updated_at = excluded.updated_at
+            "#,
+            params![group.id, group.name, group.description, group.created_at.to_rfc3339(), group.updated_at.to_rfc3339()],
+        )?;
+
+        // Members that were skipped or never existed here are left out
+        conn.execute("DELETE FROM staff_group_members WHERE group_id = ?1", [&group.id])?;
+        let mut stmt = conn.prepare(
+            r#"
+            INSERT OR IGNORE INTO staff_group_members (group_id, staff_id, position)
+            SELECT ?1, ?2, ?3 WHERE EXISTS (SELECT 1 FROM staff WHERE id = ?2)
+            "#,
+        )?;
+        for (position, staff_id) in group.staff_ids.iter().enumerate() {
+            stmt.execute(params![group.id, staff_id, position as i64])?;
+        }
+
+        count(&mut report.staff_groups, existed);
+    }
+
+    Ok(())
+}
+
+fn import_import_mappings(
+    conn: &Connection,
+    data: &DatabaseExport,
+    policy: TransferConflictPolicy,
+    report: &mut TransferReport,
+) -> Result<()> {
+    for mapping in &data.import_mappings {
+        let columns = [("name", mapping.name.as_str())];
+        if resolve_conflicts(conn, "import_mappings", &mapping.id, &mapping.name, &columns, policy, report)? {
+            report.import_mappings.skipped += 1;
+            continue;
+        }
+
+        let existed = exists(conn, "import_mappings", &mapping.id)?;
+        let columns_json = serde_json::to_string(&mapping.columns)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        conn.execute(
+            r#"
+            INSERT INTO import_mappings (id, name, columns, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name, columns = excluded.columns,
+                created_at = excluded.created_at, updated_at = excluded.updated_at
+            "#,
+            params![
+                mapping.id, mapping.name, columns_json,
+                mapping.created_at.to_rfc3339(), mapping.updated_at.to_rfc3339()
+            ],
+        )?;
+
+        count(&mut report.import_mappings, existed);
+    }
+
+    Ok(())
+}
+
+fn import_office_settings(conn: &Connection, settings: &OfficeSettings) -> Result<()> {
+    conn.execute(
+        r#"
+        UPDATE office_settings SET
+            office_name = ?1, division = ?2, address = ?3, phone = ?4, logo = ?5,
+            certifying_officer_name = ?6, certifying_officer_designation = ?7, updated_at = ?8
+        WHERE id = 1
+        "#,
+        params![
+            settings.office_name,
+            settings.division,
+            settings.address,
+            settings.phone,
+            settings.logo,
+            settings.certifying_officer_name,
+            settings.certifying_officer_designation,
+            settings.updated_at.to_rfc3339(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::operations::get_staff_by_id;
+    use crate::database::test_support::{insert_staff, test_database};
+    use tempfile::TempDir;
+
+    fn path(dir: &TempDir) -> PathBuf {
+        dir.path().to_path_buf()
+    }
+
+    fn staff_names(dir: &TempDir) -> Vec<String> {
+        let conn = get_connection(&path(dir)).unwrap();
+        let mut stmt = conn.prepare("SELECT full_name FROM staff ORDER BY full_name").unwrap();
+        stmt.query_map([], |row| row.get(0)).unwrap().collect::<Result<_>>().unwrap()
+    }
+
+    /// An export holding one record numbered like `insert_staff(_, 1)`, under a new id and name.
+    fn incoming() -> DatabaseExport {
+        let source = test_database();
+        insert_staff(&source, 1);
+        let mut data = export_all(&path(&source)).unwrap();
+        data.staff[0].full_name = "Incoming Person".to_string();
+        data
+    }
+
+    #[test]
+    fn clashing_records_are_skipped_under_skip() {
+        let mut nic_clash = incoming();
+        nic_clash.staff[0].appointment_number = "APP/99".to_string();
+        let mut appointment_clash = incoming();
+        appointment_clash.staff[0].nic_number = "198010000099".to_string();
+
+        for (data, field) in [(nic_clash, "nic_number"), (appointment_clash, "appointment_number")] {
+            let target = test_database();
+            let local = insert_staff(&target, 1);
+
+            let report = import_all(&path(&target), &data, TransferConflictPolicy::Skip, false, false).unwrap();
+            assert_eq!((report.staff.inserted, report.staff.skipped), (0, 1));
+            assert_eq!(report.conflicts.len(), 1);
+            assert_eq!(report.conflicts[0].field, field);
+            assert_eq!(report.conflicts[0].existing_id, local.id);
+            assert_eq!(staff_names(&target), vec![local.full_name]);
+        }
+    }
+
+    #[test]
+    fn clashing_records_replace_the_local_ones_under_replace() {
+        let target = test_database();
+        let local = insert_staff(&target, 1);
+        insert_staff(&target, 2);
+        let data = incoming();
+
+        let report = import_all(&path(&target), &data, TransferConflictPolicy::Replace, false, false).unwrap();
+        assert_eq!((report.staff.inserted, report.staff.skipped), (1, 0));
+        // One clash on each of the NIC and the appointment number, both with the same record
+        assert_eq!(report.conflicts.len(), 2);
+        assert!(report.conflicts.iter().all(|conflict| conflict.existing_id == local.id));
+        assert_eq!(staff_names(&target), vec!["Incoming Person", "Person 2 Kumar"]);
+        assert!(get_staff_by_id(&path(&target), &local.id).is_err());
+    }
+
+    #[test]
+    fn a_dry_run_reports_without_saving() {
+        let target = test_database();
+        let data = incoming();
+
+        let report = import_all(&path(&target), &data, TransferConflictPolicy::Skip, false, true).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.staff.inserted, 1);
+        assert!(staff_names(&target).is_empty());
+    }
+
+    #[test]
+    fn a_record_edited_since_the_export_is_kept() {
+        let dir = test_database();
+        let staff = insert_staff(&dir, 1);
+        let data = export_all(&path(&dir)).unwrap();
+
+        let later = (chrono::Utc::now() + chrono::Duration::minutes(5)).to_rfc3339();
+        get_connection(&path(&dir))
+            .unwrap()
+            .execute("UPDATE staff SET full_name = 'Edited', updated_at = ?1 WHERE id = ?2", params![later, staff.id])
+            .unwrap();
+
+        let report = import_all(&path(&dir), &data, TransferConflictPolicy::Replace, false, false).unwrap();
+        assert_eq!((report.staff.updated, report.staff.skipped), (0, 1));
+        assert_eq!(report.conflicts[0].field, "updated_at");
+        assert_eq!(staff_names(&dir), vec!["Edited"]);
+    }
+
+    #[test]
+    fn other_files_and_newer_formats_are_refused() {
+        let data = incoming();
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(read_export(json.to_string().as_bytes()).unwrap().staff.len(), 1);
+
+        assert!(read_export(b"not json").unwrap_err().starts_with("The file is not a database export:"));
+
+        let mut other = json.clone();
+        other["format"] = "something-else".into();
+        assert_eq!(
+            read_export(other.to_string().as_bytes()).unwrap_err(),
+            "The file is not a database export from this application"
+        );
+
+        for version in [0, TRANSFER_FORMAT_VERSION + 1] {
+            let mut newer = json.clone();
+            newer["format_version"] = version.into();
+            let error = read_export(newer.to_string().as_bytes()).unwrap_err();
+            assert!(error.starts_with(&format!("The export uses format version {}", version)), "{}", error);
+        }
+
+        let mut damaged = json;
+        damaged["staff"] = "none".into();
+        assert!(read_export(damaged.to_string().as_bytes()).unwrap_err().starts_with("The database export is damaged"));
+    }
+}
@@ -0,0 +1,150 @@
+// src/database/users.rs
+//
+// Login accounts. Passwords are hashed by `auth::password` before they get here.
+use rusqlite::{OptionalExtension, Result, Row, params};
+use rusqlite::types::Type;
+use std::path::PathBuf;
+use uuid::Uuid;
+use crate::database::operations::{get_connection, parse_datetime_from_row};
+use crate::database::schema::{User, CreateUser, UpdateUser, UserRole};
+
+const USER_SELECT: &str =
+    "SELECT id, username, full_name, role, active, last_login_at, created_at, updated_at FROM users";
+
+fn role_to_str(role: UserRole) -> &'static str {
+    match role {
+        UserRole::Viewer => "viewer",
+        UserRole::Clerk => "clerk",
+        UserRole::Admin => "admin",
+    }
+}
+
+fn role_from_str(col_index: usize, value: &str) -> Result<UserRole> {
+    match value {
+        "viewer" => Ok(UserRole::Viewer),
+        "clerk" => Ok(UserRole::Clerk),
+        "admin" => Ok(UserRole::Admin),
+        other => Err(rusqlite::Error::FromSqlConversionFailure(
+            col_index,
+            Type::Text,
+            format!("unknown role {}", other).into(),
+        )),
+    }
+}
+
+fn map_user_row(row: &Row) -> Result<User> {
+    let role: String = row.get(3)?;
+    let last_login_at: Option<String> = row.get(5)?;
+
+    Ok(User {
+        id: row.get(0)?,
+        username: row.get(1)?,
+        full_name: row.get(2)?,
+        role: role_from_str(3, &role)?,
+        active: row.get(4)?,
+        last_login_at: last_login_at.map(|value| parse_datetime_from_row(5, value)).transpose()?,
+        created_at: parse_datetime_from_row(6, row.get(6)?)?,
+        updated_at: parse_datetime_from_row(7, row.get(7)?)?,
+    })
+}
+
+pub fn count_users(app_data_dir: &PathBuf) -> Result<i64> {
+    let conn = get_connection(app_data_dir)?;
+    conn.query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0))
+}
+
+pub fn count_active_admins(app_data_dir: &PathBuf) -> Result<i64> {
+    let conn = get_connection(app_data_dir)?;
+    conn.query_row("SELECT COUNT(*) FROM users WHERE role = 'admin' AND active = 1", [], |row| row.get(0))
+}
+
+pub fn create_user(app_data_dir: &PathBuf, data: &CreateUser, password_hash: &str) -> Result<User> {
+    let conn = get_connection(app_data_dir)?;
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        r#"
+        INSERT INTO users (id, username, full_name, password_hash, role, active, created_at, updated_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, 1, ?6, ?7)
+        "#,
+        params![id, data.username.trim(), data.full_name.trim(), password_hash, role_to_str(data.role), now, now],
+    )?;
+
+    get_user(app_data_dir, &id)
+}
+
+pub fn get_user(app_data_dir: &PathBuf, id: &str) -> Result<User> {
+    let conn = get_connection(app_data_dir)?;
+    conn.query_row(&format!("{} WHERE id = ?1", USER_SELECT), [id], map_user_row)
+}
+
+pub fn list_users(app_data_dir: &PathBuf) -> Result<Vec<User>> {
+    let conn = get_connection(app_data_dir)?;
+    let mut stmt = conn.prepare(&format!("{} ORDER BY username COLLATE NOCASE", USER_SELECT))?;
+
+    let users = stmt
+        .query_map([], map_user_row)?
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(users)
+}
+
+/// The account and password hash for a username, whether or not it is active.
+pub fn get_user_login(app_data_dir: &PathBuf, username: &str) -> Result<Option<(User, String)>> {
+    let conn = get_connection(app_data_dir)?;
+
+    conn.query_row(
+        r#"
+        SELECT id, username, full_name, role, active, last_login_at, created_at, updated_at, password_hash
+        FROM users WHERE username = ?1
+        "#,
+        [username.trim()],
+        |row| Ok((map_user_row(row)?, row.get(8)?)),
+    )
+    .optional()
+}
+
+pub fn get_password_hash(app_data_dir: &PathBuf, id: &str) -> Result<String> {
+    let conn = get_connection(app_data_dir)?;
+    conn.query_row("SELECT password_hash FROM users WHERE id = ?1", [id], |row| row.get(0))
+}
+
+pub fn record_login(app_data_dir: &PathBuf, id: &str) -> Result<User> {
+    let conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute("UPDATE users SET last_login_at = ?2 WHERE id = ?1", params![id, now])?;
+
+    get_user(app_data_dir, id)
+}
+
+pub fn update_user(app_data_dir: &PathBuf, data: &UpdateUser) -> Result<User> {
+    let conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let updated = conn.execute(
+        "UPDATE users SET full_name = ?2, role = ?3, active = ?4, updated_at = ?5 WHERE id = ?1",
+        params![data.id, data.full_name.trim(), role_to_str(data.role), data.active, now],
+    )?;
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+
+    get_user(app_data_dir, &data.id)
+}
+
+pub fn set_user_password(app_data_dir: &PathBuf, id: &str, password_hash: &str) -> Result<()> {
+    let conn = get_connection(app_data_dir)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let updated = conn.execute(
+        "UPDATE users SET password_hash = ?2, updated_at = ?3 WHERE id = ?1",
+        params![id, password_hash, now],
+    )?;
+    if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+    }
+
+    Ok(())
+}
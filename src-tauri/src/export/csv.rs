@@ -0,0 +1,128 @@
+//! CSV output.
+use super::{column_label, column_value, Cell};
+use crate::database::schema::{Staff, StaffExportColumn};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Spreadsheet apps read text starting with one of these as a formula.
+const FORMULA_PREFIXES: &[char] = &['=', '+', '-', '@'];
+
+/// `cell` as CSV text. Text that would run as a formula gets a leading `'`,
+/// so a name like `=HYPERLINK(...)` opens as typed; numbers and dates are
+/// never changed.
+fn field(cell: Cell, formatted: bool) -> String {
+    match cell {
+        Cell::Text(value) if value.starts_with(FORMULA_PREFIXES) => format!("'{}", value),
+        cell => cell.to_text(formatted),
+    }
+}
+
+/// Write `staff_list` as CSV with a header row. Fields holding commas, quotes
+/// or line breaks are quoted, so multi-part addresses stay in one column.
+pub fn write(
+    staff_list: &[Staff],
+    columns: &[StaffExportColumn],
+    format_values: bool,
+    include_bom: bool,
+) -> Result<Vec<u8>, String> {
+    let mut buffer = Vec::new();
+    if include_bom {
+        buffer.extend_from_slice(UTF8_BOM);
+    }
+
+    let mut writer = ::csv::WriterBuilder::new()
+        .terminator(::csv::Terminator::CRLF)
+        .from_writer(buffer);
+
+    writer
+        .write_record(columns.iter().map(|column| column_label(*column)))
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+
+    for staff in staff_list {
+        writer
+            .write_record(columns.iter().map(|column| field(column_value(*column, staff), format_values)))
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    }
+
+    writer
+        .into_inner()
+        .map_err(|e| format!("Failed to write CSV: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::masking::mask_for;
+    use crate::database::schema::UserRole;
+    use crate::database::test_support::{insert_staff, test_database, user};
+
+    const COLUMNS: &[StaffExportColumn] = &[
+        StaffExportColumn::FullName,
+        StaffExportColumn::Address,
+        StaffExportColumn::DateOfBirth,
+        StaffExportColumn::BasicSalary,
+    ];
+
+    fn lines(bytes: Vec<u8>) -> Vec<String> {
+        String::from_utf8(bytes).unwrap().split("\r\n").map(str::to_string).collect()
+    }
+
+    #[test]
+    fn fields_are_quoted_only_when_they_need_it() {
+        let dir = test_database();
+        let mut staff = insert_staff(&dir, 1);
+        staff.full_name = "Perera, \"Nimal\"".to_string();
+        staff.address_line3 = Some("Line\nbreak".to_string());
+
+        let lines = lines(write(&[staff], COLUMNS, false, false).unwrap());
+        assert_eq!(lines[0], "Full Name,Address,Date of Birth,Basic Salary");
+        assert_eq!(lines[1], "\"Perera, \"\"Nimal\"\"\",\"12, Station Road, Vavuniya, Line\nbreak\",1980-05-01,50000.00");
+    }
+
+    #[test]
+    fn the_byte_order_mark_is_optional() {
+        let dir = test_database();
+        let staff = [insert_staff(&dir, 1)];
+
+        assert!(write(&staff, COLUMNS, false, true).unwrap().starts_with(UTF8_BOM));
+        assert!(write(&staff, COLUMNS, false, false).unwrap().starts_with(b"Full Name"));
+    }
+
+    #[test]
+    fn formatted_values_match_the_printed_reports() {
+        let dir = test_database();
+        let staff = [insert_staff(&dir, 1)];
+        let columns = [StaffExportColumn::DateOfBirth, StaffExportColumn::BasicSalary];
+
+        assert_eq!(lines(write(&staff, &columns, false, false).unwrap())[1], "1980-05-01,50000.00");
+        assert_eq!(lines(write(&staff, &columns, true, false).unwrap())[1], "01-05-1980,\"Rs. 50,000.00\"");
+    }
+
+    #[test]
+    fn masked_rows_show_restricted() {
+        let dir = test_database();
+        let staff = mask_for(&user(UserRole::Viewer), insert_staff(&dir, 1));
+        let columns = [StaffExportColumn::NicNumber, StaffExportColumn::AddressLine1, StaffExportColumn::BasicSalary];
+
+        assert_eq!(lines(write(&[staff], &columns, true, false).unwrap())[1], "*********001,Restricted,Restricted");
+    }
+
+    #[test]
+    fn text_that_looks_like_a_formula_is_escaped() {
+        let dir = test_database();
+        let mut staff = insert_staff(&dir, 1);
+        let columns = [StaffExportColumn::FullName, StaffExportColumn::ContactNumber, StaffExportColumn::IncrementAmount];
+        staff.full_name = "=HYPERLINK(\"http://example.com\")".to_string();
+        staff.contact_number = Some("+94771234567".to_string());
+        staff.increment_amount = -5.0;
+
+        let row = lines(write(&[staff.clone()], &columns, false, false).unwrap()).remove(1);
+        assert_eq!(row, "\"'=HYPERLINK(\"\"http://example.com\"\")\",'+94771234567,-5.00");
+
+        for (name, escaped) in [("-1", "'-1"), ("@SUM(A1)", "'@SUM(A1)"), ("Nimal", "Nimal"), ("a=b", "a=b")] {
+            staff.full_name = name.to_string();
+            let output = write(&[staff.clone()], &[StaffExportColumn::FullName], false, false).unwrap();
+            assert_eq!(lines(output)[1], escaped);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Staff data exports.
+//!
+//! The column definitions here are shared by every export format; each format
+//! decides how to write the typed [`Cell`] values.
+pub mod csv;
+pub mod xlsx;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use crate::auth::masking::RESTRICTED;
+use crate::database::schema::{Staff, StaffExportColumn};
+use crate::reports::{format_address, format_currency, format_date};
+
+/// Columns used when the caller does not pick any.
+pub const DEFAULT_COLUMNS: &[StaffExportColumn] = &[
+    StaffExportColumn::AppointmentNumber,
+    StaffExportColumn::FullName,
+    StaffExportColumn::Gender,
+    StaffExportColumn::DateOfBirth,
+    StaffExportColumn::Age,
+    StaffExportColumn::NicNumber,
+    StaffExportColumn::MaritalStatus,
+    StaffExportColumn::Address,
+    StaffExportColumn::ContactNumber,
+    StaffExportColumn::Email,
+    StaffExportColumn::Designation,
+    StaffExportColumn::DateOfFirstAppointment,
+    StaffExportColumn::DateOfRetirement,
+    StaffExportColumn::IncrementDate,
+    StaffExportColumn::SalaryCode,
+    StaffExportColumn::BasicSalary,
+    StaffExportColumn::IncrementAmount,
+];
+
+pub fn columns_or_default(columns: &[StaffExportColumn]) -> &[StaffExportColumn] {
+    if columns.is_empty() {
+        DEFAULT_COLUMNS
+    } else {
+        columns
+    }
+}
+
+/// A single exported value, keeping its type for formats that have typed cells.
+pub enum Cell {
+    Empty,
+    Text(String),
+    Integer(i64),
+    Currency(f64),
+    /// Stored date string; `NaiveDate` when it parses
+    Date(String, Option<NaiveDate>),
+    DateTime(DateTime<Utc>),
+}
+
+impl Cell {
+    fn text(value: &str) -> Cell {
+        Cell::Text(value.to_string())
+    }
+
+    fn optional_text(value: &Option<String>) -> Cell {
+        match value.as_deref().map(str::trim) {
+            Some(value) if !value.is_empty() => Cell::text(value),
+            _ => Cell::Empty,
+        }
+    }
+
+    fn date(value: &str) -> Cell {
+        let value = value.trim();
+        if value.is_empty() {
+            return Cell::Empty;
+        }
+        Cell::Date(value.to_string(), parse_date(value))
+    }
+
+    /// Plain text for formats without typed cells. `formatted` selects the
+    /// display forms used in the printed reports over ISO dates and plain numbers.
+    pub fn to_text(&self, formatted: bool) -> String {
+        match self {
+            Cell::Empty => String::new(),
+            Cell::Text(value) => value.clone(),
+            Cell::Integer(value) => value.to_string(),
+            Cell::Currency(value) if formatted => format_currency(*value),
+            Cell::Currency(value) => format!("{:.2}", value),
+            Cell::Date(value, _) if formatted => format_date(value),
+            Cell::Date(value, Some(date)) if value.len() > 10 => date.format("%Y-%m-%d").to_string(),
+            Cell::Date(value, _) => value.clone(),
+            Cell::DateTime(value) if formatted => value.format("%d-%m-%Y %H:%M").to_string(),
+            Cell::DateTime(value) => value.to_rfc3339(),
+        }
+    }
+}
+
+/// Dates are stored as `YYYY-MM-DD`, though older records may hold RFC 3339 timestamps.
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .or_else(|| DateTime::parse_from_rfc3339(value).ok().map(|date| date.date_naive()))
+}
+
+pub fn column_label(column: StaffExportColumn) -> &'static str {
+    match column {
+        StaffExportColumn::AppointmentNumber => "Appointment Number",
+        StaffExportColumn::FullName => "Full Name",
+        StaffExportColumn::Gender => "Gender",
+        StaffExportColumn::DateOfBirth => "Date of Birth",
+        StaffExportColumn::Age => "Age",
+        StaffExportColumn::NicNumber => "NIC Number",
+        StaffExportColumn::NicNumberOld => "Old NIC Number",
+        StaffExportColumn::MaritalStatus => "Marital Status",
+        StaffExportColumn::Address => "Address",
+        StaffExportColumn::AddressLine1 => "Address Line 1",
+        StaffExportColumn::AddressLine2 => "Address Line 2",
+        StaffExportColumn::AddressLine3 => "Address Line 3",
+        StaffExportColumn::ContactNumber => "Contact Number",
+        StaffExportColumn::Email => "Email",
+        StaffExportColumn::Designation => "Designation",
+        StaffExportColumn::DateOfFirstAppointment => "Date of First Appointment",
+        StaffExportColumn::DateOfRetirement => "Date of Retirement",
+        StaffExportColumn::IncrementDate => "Increment Date",
+        StaffExportColumn::SalaryCode => "Salary Code",
+        StaffExportColumn::BasicSalary => "Basic Salary",
+        StaffExportColumn::IncrementAmount => "Increment Amount",
+        StaffExportColumn::CreatedAt => "Date Added",
+        StaffExportColumn::UpdatedAt => "Last Updated",
+    }
+}
+
+fn is_sensitive(column: StaffExportColumn) -> bool {
+    matches!(
+        column,
+        StaffExportColumn::AddressLine1
+            | StaffExportColumn::AddressLine2
+            | StaffExportColumn::AddressLine3
+            | StaffExportColumn::BasicSalary
+            | StaffExportColumn::IncrementAmount
+    )
+}
+
+pub fn column_value(column: StaffExportColumn, staff: &Staff) -> Cell {
+    // NIC numbers are already partly hidden; the rest have nothing left to show
+    if staff.masked && is_sensitive(column) {
+        return Cell::text(RESTRICTED);
+    }
+
+    match column {
+        StaffExportColumn::AppointmentNumber => Cell::text(&staff.appointment_number),
+        StaffExportColumn::FullName => Cell::text(&staff.full_name),
+        StaffExportColumn::Gender => Cell::text(&staff.gender),
+        StaffExportColumn::DateOfBirth => Cell::date(&staff.date_of_birth),
+        StaffExportColumn::Age => Cell::Integer(staff.age as i64),
+        StaffExportColumn::NicNumber => Cell::text(&staff.nic_number),
+        StaffExportColumn::NicNumberOld => Cell::optional_text(&staff.nic_number_old),
+        StaffExportColumn::MaritalStatus => Cell::text(&staff.marital_status),
+        StaffExportColumn::Address => Cell::optional_text(&Some(format_address(staff))),
+        StaffExportColumn::AddressLine1 => Cell::optional_text(&staff.address_line1),
+        StaffExportColumn::AddressLine2 => Cell::optional_text(&staff.address_line2),
+        StaffExportColumn::AddressLine3 => Cell::optional_text(&staff.address_line3),
+        StaffExportColumn::ContactNumber => Cell::optional_text(&staff.contact_number),
+        StaffExportColumn::Email => Cell::optional_text(&staff.email),
+        StaffExportColumn::Designation => Cell::text(&staff.designation),
+        StaffExportColumn::DateOfFirstAppointment => Cell::date(&staff.date_of_first_appointment),
+        StaffExportColumn::DateOfRetirement => Cell::date(&staff.date_of_retirement),
+        StaffExportColumn::IncrementDate => staff.increment_date.as_deref().map(Cell::date).unwrap_or(Cell::Empty),
+        StaffExportColumn::SalaryCode => Cell::text(&staff.salary_code),
+        StaffExportColumn::BasicSalary => Cell::Currency(staff.basic_salary),
+        StaffExportColumn::IncrementAmount => Cell::Currency(staff.increment_amount),
+        StaffExportColumn::CreatedAt => Cell::DateTime(staff.created_at),
+        StaffExportColumn::UpdatedAt => Cell::DateTime(staff.updated_at),
+    }
+}
@@ -0,0 +1,307 @@
+//! Excel workbook output.
+use std::collections::{BTreeMap, HashSet};
+use rust_xlsxwriter::{Color, Format, FormatBorder, Workbook, Worksheet, XlsxError};
+use super::{column_label, column_value, Cell};
+use crate::database::schema::{Staff, StaffExportColumn};
+
+const CURRENCY_FORMAT: &str = "\"Rs.\" #,##0.00";
+const DATE_FORMAT: &str = "dd-mm-yyyy";
+const DATETIME_FORMAT: &str = "dd-mm-yyyy hh:mm";
+
+// Autofit limit in pixels, so a long address doesn't produce a huge column
+const MAX_COLUMN_WIDTH: u32 = 400;
+
+// Excel limit on sheet name length
+const MAX_SHEET_NAME: usize = 31;
+
+struct Formats {
+    header: Format,
+    title: Format,
+    currency: Format,
+    date: Format,
+    datetime: Format,
+    total: Format,
+    total_currency: Format,
+}
+
+impl Formats {
+    fn new() -> Self {
+        let header = Format::new()
+            .set_bold()
+            .set_background_color(Color::RGB(0xE0E0E0))
+            .set_border(FormatBorder::Thin);
+
+        Self {
+            header,
+            title: Format::new().set_bold().set_font_size(12),
+            currency: Format::new().set_num_format(CURRENCY_FORMAT),
+            date: Format::new().set_num_format(DATE_FORMAT),
+            datetime: Format::new().set_num_format(DATETIME_FORMAT),
+            total: Format::new().set_bold().set_border_top(FormatBorder::Thin),
+            total_currency: Format::new()
+                .set_bold()
+                .set_border_top(FormatBorder::Thin)
+                .set_num_format(CURRENCY_FORMAT),
+        }
+    }
+}
+
+fn xlsx_error(e: XlsxError) -> String {
+    format!("Failed to write Excel file: {}", e)
+}
+
+/// Build a workbook of `staff_list`, either on one "Staff" sheet or on one
+/// sheet per designation, optionally followed by a summary sheet with counts
+/// and salary totals by designation and salary code.
+pub fn write(
+    staff_list: &[Staff],
+    columns: &[StaffExportColumn],
+    sheet_per_designation: bool,
+    include_summary: bool,
+) -> Result<Vec<u8>, String> {
+    let mut workbook = Workbook::new();
+    let formats = Formats::new();
+    let mut sheet_names = HashSet::new();
+
+    if sheet_per_designation {
+        // Keeps the export's sort order within each sheet
+        let mut by_designation: BTreeMap<&str, Vec<&Staff>> = BTreeMap::new();
+        for staff in staff_list {
+            by_designation.entry(staff.designation.trim()).or_default().push(staff);
+        }
+
+        for (designation, staff) in by_designation {
+            let name = sheet_name(designation, &mut sheet_names);
+            write_staff_sheet(workbook.add_worksheet(), &name, columns, &staff, &formats).map_err(xlsx_error)?;
+        }
+    } else {
+        let staff: Vec<&Staff> = staff_list.iter().collect();
+        let name = sheet_name("Staff", &mut sheet_names);
+        write_staff_sheet(workbook.add_worksheet(), &name, columns, &staff, &formats).map_err(xlsx_error)?;
+    }
+
+    if include_summary {
+        let name = sheet_name("Summary", &mut sheet_names);
+        write_summary_sheet(workbook.add_worksheet(), &name, staff_list, &formats).map_err(xlsx_error)?;
+    }
+
+    workbook.save_to_buffer().map_err(xlsx_error)
+}
+
+fn write_staff_sheet(
+    worksheet: &mut Worksheet,
+    name: &str,
+    columns: &[StaffExportColumn],
+    staff_list: &[&Staff],
+    formats: &Formats,
+) -> Result<(), XlsxError> {
+    worksheet.set_name(name)?;
+
+    for (col, column) in columns.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, column_label(*column), &formats.header)?;
+    }
+
+    for (index, staff) in staff_list.iter().enumerate() {
+        let row = index as u32 + 1;
+
+        for (col, column) in columns.iter().enumerate() {
+            let col = col as u16;
+            match column_value(*column, staff) {
+                Cell::Empty => {}
+                Cell::Text(value) => {
+                    worksheet.write_string(row, col, value)?;
+                }
+                Cell::Integer(value) => {
+                    worksheet.write_number(row, col, value as f64)?;
+                }
+                Cell::Currency(value) => {
+                    worksheet.write_number_with_format(row, col, value, &formats.currency)?;
+                }
+                Cell::Date(_, Some(date)) => {
+                    worksheet.write_datetime_with_format(row, col, date, &formats.date)?;
+                }
+                // Keep whatever was stored rather than dropping an unparseable date
+                Cell::Date(value, None) => {
+                    worksheet.write_string(row, col, value)?;
+                }
+                Cell::DateTime(value) => {
+                    worksheet.write_datetime_with_format(row, col, value.naive_utc(), &formats.datetime)?;
+                }
+            }
+        }
+    }
+
+    worksheet.set_freeze_panes(1, 0)?;
+    if !columns.is_empty() {
+        worksheet.autofilter(0, 0, staff_list.len() as u32, columns.len() as u16 - 1)?;
+    }
+    worksheet.set_autofit_max_width(MAX_COLUMN_WIDTH).autofit();
+
+    Ok(())
+}
+
+/// Staff count and basic salary total for one group.
+#[derive(Default)]
+struct GroupTotals {
+    staff: u32,
+    basic_salary: f64,
+}
+
+fn write_summary_sheet(
+    worksheet: &mut Worksheet,
+    name: &str,
+    staff_list: &[Staff],
+    formats: &Formats,
+) -> Result<(), XlsxError> {
+    worksheet.set_name(name)?;
+
+    let mut by_designation: BTreeMap<&str, GroupTotals> = BTreeMap::new();
+    let mut by_salary_code: BTreeMap<&str, GroupTotals> = BTreeMap::new();
+    for staff in staff_list {
+        for totals in [
+            by_designation.entry(staff.designation.trim()).or_default(),
+            by_salary_code.entry(staff.salary_code.trim()).or_default(),
+        ] {
+            totals.staff += 1;
+            totals.basic_salary += staff.basic_salary;
+        }
+    }
+
+    let row = write_summary_table(worksheet, 0, "Staff by Designation", "Designation", &by_designation, formats)?;
+    write_summary_table(worksheet, row + 1, "Staff by Salary Code", "Salary Code", &by_salary_code, formats)?;
+
+    worksheet.set_autofit_max_width(MAX_COLUMN_WIDTH).autofit();
+
+    Ok(())
+}
+
+/// Write one titled table with a totals row; returns the row after it.
+fn write_summary_table(
+    worksheet: &mut Worksheet,
+    first_row: u32,
+    title: &str,
+    group_label: &str,
+    groups: &BTreeMap<&str, GroupTotals>,
+    formats: &Formats,
+) -> Result<u32, XlsxError> {
+    worksheet.write_string_with_format(first_row, 0, title, &formats.title)?;
+
+    let header_row = first_row + 1;
+    for (col, label) in [group_label, "Staff", "Total Basic Salary", "Average Basic Salary"].into_iter().enumerate() {
+        worksheet.write_string_with_format(header_row, col as u16, label, &formats.header)?;
+    }
+
+    let mut row = header_row + 1;
+    for (group, totals) in groups {
+        worksheet.write_string(row, 0, *group)?;
+        worksheet.write_number(row, 1, totals.staff)?;
+        worksheet.write_number_with_format(row, 2, totals.basic_salary, &formats.currency)?;
+        worksheet.write_number_with_format(row, 3, totals.basic_salary / totals.staff as f64, &formats.currency)?;
+        row += 1;
+    }
+
+    let staff: u32 = groups.values().map(|totals| totals.staff).sum();
+    let basic_salary: f64 = groups.values().map(|totals| totals.basic_salary).sum();
+    worksheet.write_string_with_format(row, 0, "Total", &formats.total)?;
+    worksheet.write_number_with_format(row, 1, staff, &formats.total)?;
+    worksheet.write_number_with_format(row, 2, basic_salary, &formats.total_currency)?;
+    if staff > 0 {
+        worksheet.write_number_with_format(row, 3, basic_salary / staff as f64, &formats.total_currency)?;
+    }
+
+    Ok(row + 2)
+}
+
+/// A valid sheet name for `name`: without the characters Excel rejects, at
+/// most 31 characters and not already used in this workbook (compared
+/// case-insensitively, as Excel does).
+fn sheet_name(name: &str, used: &mut HashSet<String>) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\') { '-' } else { c })
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('\'');
+    let base = if cleaned.is_empty() { "Unspecified" } else { cleaned };
+
+    let mut candidate: String = base.chars().take(MAX_SHEET_NAME).collect();
+    let mut suffix = 2;
+    while used.contains(&candidate.to_lowercase()) {
+        let tag = format!(" ({})", suffix);
+        candidate = base.chars().take(MAX_SHEET_NAME - tag.len()).collect::<String>() + &tag;
+        suffix += 1;
+    }
+
+    used.insert(candidate.to_lowercase());
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use calamine::{Data, Reader, Xlsx};
+    use chrono::NaiveDate;
+    use std::io::{Cursor, Read};
+    use crate::database::test_support::{insert_staff, test_database};
+
+    fn open(bytes: &[u8]) -> Xlsx<Cursor<Vec<u8>>> {
+        Xlsx::new(Cursor::new(bytes.to_vec())).unwrap()
+    }
+
+    #[test]
+    fn salaries_and_dates_are_typed_cells_under_a_frozen_header() {
+        let dir = test_database();
+        let staff = [insert_staff(&dir, 1)];
+        let columns = [StaffExportColumn::FullName, StaffExportColumn::DateOfBirth, StaffExportColumn::BasicSalary];
+        let bytes = write(&staff, &columns, false, false).unwrap();
+
+        let mut workbook = open(&bytes);
+        assert_eq!(workbook.sheet_names(), vec!["Staff"]);
+        let range = workbook.worksheet_range("Staff").unwrap();
+        assert_eq!(range.get((0, 1)), Some(&Data::String("Date of Birth".to_string())));
+        assert_eq!(range.get((1, 0)), Some(&Data::String("Person 1 Kumar".to_string())));
+        match range.get((1, 1)) {
+            Some(Data::DateTime(date)) => {
+                assert_eq!(date.as_datetime().unwrap().date(), NaiveDate::from_ymd_opt(1980, 5, 1).unwrap());
+            }
+            other => panic!("date of birth was written as {:?}", other),
+        }
+        assert_eq!(range.get((1, 2)), Some(&Data::Float(50000.0)));
+
+        let mut sheet = String::new();
+        zip::ZipArchive::new(Cursor::new(bytes))
+            .unwrap()
+            .by_name("xl/worksheets/sheet1.xml")
+            .unwrap()
+            .read_to_string(&mut sheet)
+            .unwrap();
+        assert!(sheet.contains(r#"<pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/>"#), "{}", sheet);
+    }
+
+    #[test]
+    fn each_designation_gets_a_valid_sheet() {
+        let dir = test_database();
+        let mut first = insert_staff(&dir, 1);
+        first.designation = "Range Forest Officer / Beat".to_string();
+        let second = insert_staff(&dir, 2);
+
+        let bytes = write(&[first, second], &[StaffExportColumn::FullName], true, true).unwrap();
+        assert_eq!(open(&bytes).sheet_names(), vec!["Development Officer", "Range Forest Officer - Beat", "Summary"]);
+    }
+
+    #[test]
+    fn sheet_names_are_cleaned_shortened_and_unique() {
+        let mut used = HashSet::new();
+        assert_eq!(sheet_name("Officer: Grade [I]*?", &mut used), "Officer- Grade -I---");
+        assert_eq!(sheet_name("  'Quoted'  ", &mut used), "Quoted");
+        assert_eq!(sheet_name("", &mut used), "Unspecified");
+
+        let long = "Assistant Conservator of Forests (Research)";
+        assert_eq!(sheet_name(long, &mut used), "Assistant Conservator of Forest");
+        assert_eq!(sheet_name(long, &mut used), "Assistant Conservator of Fo (2)");
+
+        assert_eq!(sheet_name("Staff", &mut used), "Staff");
+        assert_eq!(sheet_name("STAFF", &mut used), "STAFF (2)");
+        assert_eq!(sheet_name("staff", &mut used), "staff (3)");
+        assert!(used.iter().all(|name| name.chars().count() <= MAX_SHEET_NAME));
+    }
+}
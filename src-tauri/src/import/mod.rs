@@ -0,0 +1,698 @@
+//! Staff imports from CSV and Excel files.
+//!
+//! The file is read into text rows by [`reader`], then each row is mapped to
+//! a `CreateStaff` through the column mapping, normalised (NICs, dates,
+//! amounts) and validated with the same rules as the Add Staff form. Rows
+//! with errors, or with a NIC or appointment number that is already taken,
+//! are reported and never imported.
+pub mod reader;
+
+use chrono::{DateTime, Datelike, Months, NaiveDate};
+use std::collections::HashMap;
+use crate::database::schema::{
+    CreateStaff, ImportColumnMapping, ImportConflict, ImportReport, ImportRowReport, StaffExportColumn,
+    StaffIdentity, StaffImportField,
+};
+use crate::export::column_label;
+use crate::utils::nic::{nic_birth_date, nic_gender, normalize_nic};
+use reader::Table;
+
+pub const RETIREMENT_AGE: u32 = 60;
+
+const ALL_FIELDS: &[StaffImportField] = &[
+    StaffImportField::AppointmentNumber,
+    StaffImportField::FullName,
+    StaffImportField::Gender,
+    StaffImportField::DateOfBirth,
+    StaffImportField::NicNumber,
+    StaffImportField::NicNumberOld,
+    StaffImportField::MaritalStatus,
+    StaffImportField::AddressLine1,
+    StaffImportField::AddressLine2,
+    StaffImportField::AddressLine3,
+    StaffImportField::ContactNumber,
+    StaffImportField::Email,
+    StaffImportField::Designation,
+    StaffImportField::DateOfFirstAppointment,
+    StaffImportField::DateOfRetirement,
+    StaffImportField::IncrementDate,
+    StaffImportField::SalaryCode,
+    StaffImportField::BasicSalary,
+    StaffImportField::IncrementAmount,
+];
+
+/// Fields every mapping must include. Gender and date of birth can come from
+/// the NIC, and the retirement date from the date of birth.
+const REQUIRED_FIELDS: &[StaffImportField] = &[
+    StaffImportField::AppointmentNumber,
+    StaffImportField::FullName,
+    StaffImportField::NicNumber,
+    StaffImportField::MaritalStatus,
+    StaffImportField::Designation,
+    StaffImportField::DateOfFirstAppointment,
+];
+
+/// Fields only an administrator may import. Without them the records are
+/// added with blank salary details, as when a clerk uses the Add Staff form.
+pub const SALARY_FIELDS: &[StaffImportField] = &[
+    StaffImportField::SalaryCode,
+    StaffImportField::BasicSalary,
+    StaffImportField::IncrementAmount,
+];
+
+/// Whether a mapping brings in any salary details.
+pub fn maps_salary(columns: &[ImportColumnMapping]) -> bool {
+    columns.iter().any(|column| SALARY_FIELDS.contains(&column.field))
+}
+
+pub(crate) const MARITAL_STATUSES: &[&str] = &["Single", "Married", "Divorced", "Widowed"];
+
+/// Header spellings seen in hand-made sheets, besides the field names and export labels.
+const HEADER_ALIASES: &[(&str, StaffImportField)] = &[
+    ("name", StaffImportField::FullName),
+    ("nic", StaffImportField::NicNumber),
+    ("oldnic", StaffImportField::NicNumberOld),
+    ("dob", StaffImportField::DateOfBirth),
+    ("phone", StaffImportField::ContactNumber),
+    ("salary", StaffImportField::BasicSalary),
+];
+
+/// Import fields share their labels with the matching export columns, so an
+/// exported file maps back without any manual matching.
+fn export_column(field: StaffImportField) -> StaffExportColumn {
+    match field {
+        StaffImportField::AppointmentNumber => StaffExportColumn::AppointmentNumber,
+        StaffImportField::FullName => StaffExportColumn::FullName,
+        StaffImportField::Gender => StaffExportColumn::Gender,
+        StaffImportField::DateOfBirth => StaffExportColumn::DateOfBirth,
+        StaffImportField::NicNumber => StaffExportColumn::NicNumber,
+        StaffImportField::NicNumberOld => StaffExportColumn::NicNumberOld,
+        StaffImportField::MaritalStatus => StaffExportColumn::MaritalStatus,
+        StaffImportField::AddressLine1 => StaffExportColumn::AddressLine1,
+        StaffImportField::AddressLine2 => StaffExportColumn::AddressLine2,
+        StaffImportField::AddressLine3 => StaffExportColumn::AddressLine3,
+        StaffImportField::ContactNumber => StaffExportColumn::ContactNumber,
+        StaffImportField::Email => StaffExportColumn::Email,
+        StaffImportField::Designation => StaffExportColumn::Designation,
+        StaffImportField::DateOfFirstAppointment => StaffExportColumn::DateOfFirstAppointment,
+        StaffImportField::DateOfRetirement => StaffExportColumn::DateOfRetirement,
+        StaffImportField::IncrementDate => StaffExportColumn::IncrementDate,
+        StaffImportField::SalaryCode => StaffExportColumn::SalaryCode,
+        StaffImportField::BasicSalary => StaffExportColumn::BasicSalary,
+        StaffImportField::IncrementAmount => StaffExportColumn::IncrementAmount,
+    }
+}
+
+pub fn field_label(field: StaffImportField) -> &'static str {
+    column_label(export_column(field))
+}
+
+/// Lower case letters and digits only, so "Date of Birth", "date_of_birth"
+/// and "DATE OF BIRTH" all compare equal.
+fn header_key(header: &str) -> String {
+    header.chars().filter(char::is_ascii_alphanumeric).map(|c| c.to_ascii_lowercase()).collect()
+}
+
+fn field_for_header(header: &str) -> Option<StaffImportField> {
+    let key = header_key(header);
+    if key.is_empty() {
+        return None;
+    }
+
+    ALL_FIELDS
+        .iter()
+        .copied()
+        .find(|field| {
+            header_key(field_label(*field)) == key
+                || serde_json::to_value(field).ok().and_then(|name| name.as_str().map(header_key)) == Some(key.clone())
+        })
+        .or_else(|| HEADER_ALIASES.iter().find(|(alias, _)| *alias == key).map(|(_, field)| *field))
+}
+
+/// Map every header that names a field; the first column wins if two name the same one.
+pub fn suggest_columns(headers: &[String]) -> Vec<ImportColumnMapping> {
+    let mut columns: Vec<ImportColumnMapping> = Vec::new();
+    for header in headers {
+        if let Some(field) = field_for_header(header) {
+            if !columns.iter().any(|column| column.field == field) {
+                columns.push(ImportColumnMapping { column: header.clone(), field });
+            }
+        }
+    }
+    columns
+}
+
+/// Resolve a mapping to column positions in the file.
+fn column_indexes(headers: &[String], columns: &[ImportColumnMapping]) -> Result<HashMap<StaffImportField, usize>, String> {
+    let mut indexes = HashMap::new();
+    for mapping in columns {
+        let index = headers
+            .iter()
+            .position(|header| header.trim().eq_ignore_ascii_case(mapping.column.trim()))
+            .ok_or_else(|| format!("The file has no column named \"{}\"", mapping.column))?;
+        if indexes.insert(mapping.field, index).is_some() {
+            return Err(format!("More than one column is mapped to {}", field_label(mapping.field)));
+        }
+    }
+
+    for field in REQUIRED_FIELDS {
+        if !indexes.contains_key(field) {
+            return Err(format!("No column is mapped to {}", field_label(*field)));
+        }
+    }
+
+    Ok(indexes)
+}
+
+/// Accepts ISO dates as well as the day-first forms used locally.
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    const FORMATS: [&str; 5] = ["%Y-%m-%d", "%d-%m-%Y", "%d/%m/%Y", "%d.%m.%Y", "%Y/%m/%d"];
+
+    FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .or_else(|| DateTime::parse_from_rfc3339(value).ok().map(|date| date.date_naive()))
+}
+
+/// Amounts as plain numbers or as printed in reports ("Rs. 45,000.00").
+fn parse_amount(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let value = value
+        .strip_prefix("Rs.")
+        .or_else(|| value.strip_prefix("Rs"))
+        .unwrap_or(value);
+    let cleaned: String = value.chars().filter(|c| *c != ',' && !c.is_whitespace()).collect();
+
+    cleaned.parse::<f64>().ok().filter(|amount| amount.is_finite())
+}
+
+/// Increment dates are stored as `DD-MM`; a full date keeps its day and month.
+pub(crate) fn parse_increment_date(value: &str) -> Option<String> {
+    if let Some(date) = parse_date(value) {
+        return Some(date.format("%d-%m").to_string());
+    }
+
+    let mut parts = value.split(['-', '/', '.']);
+    let day: u32 = parts.next()?.trim().parse().ok()?;
+    let month: u32 = parts.next()?.trim().parse().ok()?;
+    if parts.next().is_some() || !(1..=31).contains(&day) || !(1..=12).contains(&month) {
+        return None;
+    }
+    // Checked against a leap year so 29-02 is allowed
+    NaiveDate::from_ymd_opt(2000, month, day)?;
+
+    Some(format!("{:02}-{:02}", day, month))
+}
+
+pub(crate) fn is_valid_email(value: &str) -> bool {
+    match value.split_once('@') {
+        Some((local, domain)) => {
+            let valid_part = |part: &str| !part.is_empty() && !part.contains('@') && !part.contains(char::is_whitespace);
+            valid_part(local)
+                && valid_part(domain)
+                && domain.char_indices().any(|(i, c)| c == '.' && i > 0 && i < domain.len() - 1)
+        }
+        None => false,
+    }
+}
+
+pub(crate) fn is_valid_phone(value: &str) -> bool {
+    value.chars().all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '+' | '(' | ')'))
+}
+
+fn canonical(value: &str, options: &[&'static str]) -> Option<&'static str> {
+    options.iter().copied().find(|option| option.eq_ignore_ascii_case(value))
+}
+
+fn parse_gender(value: &str) -> Option<&'static str> {
+    match value.to_ascii_uppercase().as_str() {
+        "M" | "MALE" => Some("Male"),
+        "F" | "FEMALE" => Some("Female"),
+        _ => None,
+    }
+}
+
+fn age_on(date_of_birth: NaiveDate, today: NaiveDate) -> i32 {
+    let mut age = today.year() - date_of_birth.year();
+    if (today.month(), today.day()) < (date_of_birth.month(), date_of_birth.day()) {
+        age -= 1;
+    }
+    age
+}
+
+struct RowValues<'a> {
+    indexes: &'a HashMap<StaffImportField, usize>,
+    values: &'a [String],
+}
+
+impl RowValues<'_> {
+    fn get(&self, field: StaffImportField) -> Option<&str> {
+        self.indexes
+            .get(&field)
+            .and_then(|index| self.values.get(*index))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn owned(&self, field: StaffImportField) -> Option<String> {
+        self.get(field).map(str::to_string)
+    }
+
+    fn maps(&self, field: StaffImportField) -> bool {
+        self.indexes.contains_key(&field)
+    }
+}
+
+/// Normalise and validate one row, collecting every problem rather than stopping at the first.
+fn build_staff(row: &RowValues, today: NaiveDate) -> Result<CreateStaff, Vec<String>> {
+    let mut errors = Vec::new();
+    let required = |field: StaffImportField, errors: &mut Vec<String>| {
+        row.owned(field).unwrap_or_else(|| {
+            errors.push(format!("{} is required", field_label(field)));
+            String::new()
+        })
+    };
+
+    let appointment_number = required(StaffImportField::AppointmentNumber, &mut errors);
+    let full_name = required(StaffImportField::FullName, &mut errors);
+    let designation = required(StaffImportField::Designation, &mut errors);
+    // Salary details are optional, but once mapped every row needs them
+    let salary_code = if row.maps(StaffImportField::SalaryCode) {
+        required(StaffImportField::SalaryCode, &mut errors)
+    } else {
+        String::new()
+    };
+
+    let nic = match row.get(StaffImportField::NicNumber).map(normalize_nic) {
+        Some(Ok(nic)) => Some(nic),
+        Some(Err(e)) => {
+            errors.push(e);
+            None
+        }
+        None => {
+            errors.push("NIC Number is required".to_string());
+            None
+        }
+    };
+
+    // An old NIC given in its own column must be the same number as the new one
+    let mut nic_number_old = nic.as_ref().and_then(|nic| nic.old_format.clone());
+    if let (Some(nic), Some(old)) = (&nic, row.get(StaffImportField::NicNumberOld)) {
+        match normalize_nic(old) {
+            Ok(old_nic) if old_nic.new_format == nic.new_format && old_nic.old_format.is_some() => {
+                nic_number_old = old_nic.old_format;
+            }
+            _ => errors.push(format!("Old NIC number \"{}\" does not match NIC number {}", old, nic.new_format)),
+        }
+    }
+
+    let gender = match row.get(StaffImportField::Gender) {
+        Some(value) => match parse_gender(value) {
+            Some(gender) => gender.to_string(),
+            None => {
+                errors.push(format!("Gender must be Male or Female, not \"{}\"", value));
+                String::new()
+            }
+        },
+        None => nic.as_ref().map(|nic| nic_gender(nic).to_string()).unwrap_or_default(),
+    };
+
+    let date = |field: StaffImportField, errors: &mut Vec<String>| -> Option<NaiveDate> {
+        let value = row.get(field)?;
+        let date = parse_date(value);
+        if date.is_none() {
+            errors.push(format!("{} \"{}\" is not a valid date", field_label(field), value));
+        }
+        date
+    };
+
+    let date_of_birth = match row.get(StaffImportField::DateOfBirth) {
+        Some(_) => date(StaffImportField::DateOfBirth, &mut errors),
+        None => match nic.as_ref().and_then(nic_birth_date) {
+            Some(date_of_birth) => Some(date_of_birth),
+            None => {
+                if nic.is_some() {
+                    errors.push("Date of Birth is required".to_string());
+                }
+                None
+            }
+        },
+    };
+
+    let date_of_first_appointment = date(StaffImportField::DateOfFirstAppointment, &mut errors);
+    if row.get(StaffImportField::DateOfFirstAppointment).is_none() {
+        errors.push("Date of First Appointment is required".to_string());
+    }
+
+    let date_of_retirement = match row.get(StaffImportField::DateOfRetirement) {
+        Some(_) => date(StaffImportField::DateOfRetirement, &mut errors),
+        None => date_of_birth.and_then(|date| date.checked_add_months(Months::new(RETIREMENT_AGE * 12))),
+    };
+
+    let marital_status = match row.get(StaffImportField::MaritalStatus) {
+        Some(value) => canonical(value, MARITAL_STATUSES).map(str::to_string).unwrap_or_else(|| {
+            errors.push(format!("Marital status must be one of {}, not \"{}\"", MARITAL_STATUSES.join(", "), value));
+            String::new()
+        }),
+        None => {
+            errors.push("Marital Status is required".to_string());
+            String::new()
+        }
+    };
+
+    let basic_salary = match row.get(StaffImportField::BasicSalary) {
+        Some(value) => match parse_amount(value) {
+            Some(amount) if amount > 0.0 => amount,
+            Some(_) => {
+                errors.push("Basic salary must be greater than 0".to_string());
+                0.0
+            }
+            None => {
+                errors.push(format!("Basic Salary \"{}\" is not a valid amount", value));
+                0.0
+            }
+        },
+        None if row.maps(StaffImportField::BasicSalary) => {
+            errors.push("Basic Salary is required".to_string());
+            0.0
+        }
+        None => 0.0,
+    };
+
+    let increment_amount = match row.get(StaffImportField::IncrementAmount) {
+        Some(value) => match parse_amount(value) {
+            Some(amount) if amount >= 0.0 => amount,
+            Some(_) => {
+                errors.push("Increment amount cannot be negative".to_string());
+                0.0
+            }
+            None => {
+                errors.push(format!("Increment Amount \"{}\" is not a valid amount", value));
+                0.0
+            }
+        },
+        None => 0.0,
+    };
+
+    let increment_date = row.get(StaffImportField::IncrementDate).and_then(|value| {
+        let increment_date = parse_increment_date(value);
+        if increment_date.is_none() {
+            errors.push(format!("Increment date \"{}\" must be in DD-MM format", value));
+        }
+        increment_date
+    });
+
+    let email = row.owned(StaffImportField::Email);
+    if let Some(email) = &email {
+        if !is_valid_email(email) {
+            errors.push(format!("Invalid email format \"{}\"", email));
+        }
+    }
+
+    let contact_number = row.owned(StaffImportField::ContactNumber);
+    if let Some(contact_number) = &contact_number {
+        if !is_valid_phone(contact_number) {
+            errors.push(format!("Invalid contact number format \"{}\"", contact_number));
+        }
+    }
+
+    if let (Some(date_of_birth), Some(first_appointment)) = (date_of_birth, date_of_first_appointment) {
+        if first_appointment <= date_of_birth {
+            errors.push("Date of first appointment must be after the date of birth".to_string());
+        }
+    }
+    if date_of_birth.is_some_and(|date| date > today) {
+        errors.push("Date of birth cannot be in the future".to_string());
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Every value used below was checked above
+    let (Some(nic), Some(date_of_birth), Some(date_of_first_appointment), Some(date_of_retirement)) =
+        (nic, date_of_birth, date_of_first_appointment, date_of_retirement)
+    else {
+        return Err(vec!["Row could not be read".to_string()]);
+    };
+
+    Ok(CreateStaff {
+        appointment_number,
+        full_name,
+        gender,
+        date_of_birth: date_of_birth.format("%Y-%m-%d").to_string(),
+        age: age_on(date_of_birth, today),
+        nic_number: nic.new_format,
+        nic_number_old,
+        marital_status,
+        address_line1: row.owned(StaffImportField::AddressLine1),
+        address_line2: row.owned(StaffImportField::AddressLine2),
+        address_line3: row.owned(StaffImportField::AddressLine3),
+        contact_number,
+        email,
+        designation,
+        date_of_first_appointment: date_of_first_appointment.format("%Y-%m-%d").to_string(),
+        date_of_retirement: date_of_retirement.format("%Y-%m-%d").to_string(),
+        increment_date,
+        salary_code,
+        basic_salary,
+        increment_amount,
+        image_data: None,
+    })
+}
+
+/// Where a NIC or appointment number was first seen.
+#[derive(Clone)]
+enum Owner {
+    Staff(String),
+    Row(usize),
+}
+
+fn conflict(field: StaffImportField, value: &str, owner: &Owner) -> ImportConflict {
+    let (existing_staff_id, existing_row) = match owner {
+        Owner::Staff(id) => (Some(id.clone()), None),
+        Owner::Row(row) => (None, Some(*row)),
+    };
+    ImportConflict { field, value: value.to_string(), existing_staff_id, existing_row }
+}
+
+/// Staff ready to insert, plus the report describing every row that was left out.
+pub struct PreparedImport {
+    pub staff: Vec<CreateStaff>,
+    pub report: ImportReport,
+}
+
+/// Map, normalise and validate every row of `table`, checking NICs and
+/// appointment numbers against `existing` staff and earlier rows of the file.
+pub fn prepare(
+    table: &Table,
+    columns: &[ImportColumnMapping],
+    existing: &[StaffIdentity],
+    dry_run: bool,
+) -> Result<PreparedImport, String> {
+    let indexes = column_indexes(&table.headers, columns)?;
+    let today = chrono::Local::now().date_naive();
+
+    // NICs are compared in the new format, appointment numbers ignoring case
+    let mut nics: HashMap<String, Owner> = HashMap::new();
+    let mut appointment_numbers: HashMap<String, Owner> = HashMap::new();
+    for identity in existing {
+        let nic = normalize_nic(&identity.nic_number)
+            .map(|nic| nic.new_format)
+            .unwrap_or_else(|_| identity.nic_number.clone());
+        nics.insert(nic, Owner::Staff(identity.id.clone()));
+        if let Some(old) = identity.nic_number_old.as_deref().and_then(|old| normalize_nic(old).ok()) {
+            nics.entry(old.new_format).or_insert(Owner::Staff(identity.id.clone()));
+        }
+        appointment_numbers.insert(identity.appointment_number.trim().to_uppercase(), Owner::Staff(identity.id.clone()));
+    }
+
+    let mut staff = Vec::new();
+    let mut rows = Vec::new();
+    for (row_number, values) in &table.rows {
+        let row = RowValues { indexes: &indexes, values };
+        let mut conflicts = Vec::new();
+
+        if let Some(appointment_number) = row.get(StaffImportField::AppointmentNumber) {
+            let key = appointment_number.to_uppercase();
+            match appointment_numbers.get(&key) {
+                Some(owner) => conflicts.push(conflict(StaffImportField::AppointmentNumber, appointment_number, owner)),
+                None => {
+                    appointment_numbers.insert(key, Owner::Row(*row_number));
+                }
+            }
+        }
+        if let Some(Ok(nic)) = row.get(StaffImportField::NicNumber).map(normalize_nic) {
+            match nics.get(&nic.new_format) {
+                Some(owner) => conflicts.push(conflict(StaffImportField::NicNumber, &nic.new_format, owner)),
+                None => {
+                    nics.insert(nic.new_format, Owner::Row(*row_number));
+                }
+            }
+        }
+
+        match build_staff(&row, today) {
+            Ok(create) if conflicts.is_empty() => staff.push(create),
+            result => rows.push(ImportRowReport {
+                row: *row_number,
+                appointment_number: row.owned(StaffImportField::AppointmentNumber),
+                full_name: row.owned(StaffImportField::FullName),
+                errors: result.err().unwrap_or_default(),
+                conflicts,
+            }),
+        }
+    }
+
+    let report = ImportReport {
+        dry_run,
+        total_rows: table.rows.len(),
+        valid_rows: staff.len(),
+        imported: 0,
+        imported_ids: Vec::new(),
+        rows,
+    };
+
+    Ok(PreparedImport { staff, report })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADERS: [&str; 8] = ["Appt", "Name", "NIC", "Status", "Post", "First", "Salary", "Code"];
+    const FIELDS: [StaffImportField; 8] = [
+        StaffImportField::AppointmentNumber,
+        StaffImportField::FullName,
+        StaffImportField::NicNumber,
+        StaffImportField::MaritalStatus,
+        StaffImportField::Designation,
+        StaffImportField::DateOfFirstAppointment,
+        StaffImportField::BasicSalary,
+        StaffImportField::SalaryCode,
+    ];
+
+    fn table(rows: &[[&str; 8]]) -> Table {
+        Table {
+            sheets: Vec::new(),
+            sheet: None,
+            headers: HEADERS.iter().map(|header| header.to_string()).collect(),
+            rows: rows
+                .iter()
+                .enumerate()
+                .map(|(index, row)| (index + 2, row.iter().map(|value| value.to_string()).collect()))
+                .collect(),
+        }
+    }
+
+    fn columns(count: usize) -> Vec<ImportColumnMapping> {
+        HEADERS
+            .iter()
+            .zip(FIELDS)
+            .take(count)
+            .map(|(header, field)| ImportColumnMapping { column: header.to_string(), field })
+            .collect()
+    }
+
+    const ROW: [&str; 8] = ["A/1", "Nimal Perera", "853400937v", "married", "Clerk", "01/02/2010", "Rs. 45,000.00", "MN-1-2016"];
+
+    #[test]
+    fn dates_amounts_and_nics_are_normalised() {
+        assert_eq!(parse_date("2010-02-01"), NaiveDate::from_ymd_opt(2010, 2, 1));
+        assert_eq!(parse_date("01/02/2010"), NaiveDate::from_ymd_opt(2010, 2, 1));
+        assert_eq!(parse_date("01.02.2010"), NaiveDate::from_ymd_opt(2010, 2, 1));
+        assert_eq!(parse_date("2010-02-01T08:00:00+05:30"), NaiveDate::from_ymd_opt(2010, 2, 1));
+        assert_eq!(parse_date("31/02/2010"), None);
+
+        assert_eq!(parse_amount("Rs. 45,000.00"), Some(45000.0));
+        assert_eq!(parse_amount(" 1 250.5 "), Some(1250.5));
+        assert_eq!(parse_amount("NaN"), None);
+        assert_eq!(parse_amount("forty"), None);
+
+        assert_eq!(parse_increment_date("2010-02-01").as_deref(), Some("01-02"));
+        assert_eq!(parse_increment_date("5/11").as_deref(), Some("05-11"));
+        assert_eq!(parse_increment_date("32-01"), None);
+
+        let prepared = prepare(&table(&[ROW]), &columns(8), &[], true).unwrap();
+        let staff = &prepared.staff[0];
+        assert_eq!(staff.nic_number, "198534000937");
+        assert_eq!(staff.nic_number_old.as_deref(), Some("853400937V"));
+        assert_eq!(staff.gender, "Male");
+        assert_eq!(staff.date_of_birth, "1985-12-05");
+        assert_eq!(staff.date_of_retirement, "2045-12-05");
+        assert_eq!(staff.date_of_first_appointment, "2010-02-01");
+        assert_eq!(staff.marital_status, "Married");
+        assert_eq!(staff.basic_salary, 45000.0);
+    }
+
+    #[test]
+    fn every_problem_on_a_row_is_reported() {
+        let bad = ["A/2", "", "12345", "engaged", "Clerk", "2010-13-01", "-5", "MN-1-2016"];
+        let prepared = prepare(&table(&[ROW, bad]), &columns(8), &[], true).unwrap();
+
+        assert_eq!(prepared.staff.len(), 1);
+        assert_eq!((prepared.report.total_rows, prepared.report.valid_rows), (2, 1));
+        let row = &prepared.report.rows[0];
+        assert_eq!(row.row, 3);
+        assert_eq!(row.appointment_number.as_deref(), Some("A/2"));
+        for expected in ["Full Name is required", "NIC", "Marital status", "Date of First Appointment \"2010-13-01\"", "Basic salary must be greater than 0"] {
+            assert!(row.errors.iter().any(|error| error.contains(expected)), "{} missing from {:?}", expected, row.errors);
+        }
+    }
+
+    #[test]
+    fn salary_columns_are_optional_but_filled_in_once_mapped() {
+        let prepared = prepare(&table(&[ROW]), &columns(6), &[], true).unwrap();
+        assert_eq!(prepared.staff[0].salary_code, "");
+        assert_eq!(prepared.staff[0].basic_salary, 0.0);
+        assert!(!maps_salary(&columns(6)));
+        assert!(maps_salary(&columns(7)));
+
+        let mut blank = ROW;
+        blank[6] = "";
+        let prepared = prepare(&table(&[blank]), &columns(8), &[], true).unwrap();
+        assert_eq!(prepared.report.rows[0].errors, vec!["Basic Salary is required".to_string()]);
+    }
+
+    #[test]
+    fn duplicates_in_the_file_and_the_database_are_left_out() {
+        let existing = [StaffIdentity {
+            id: "staff-1".to_string(),
+            appointment_number: "b/1".to_string(),
+            nic_number: "199001500123".to_string(),
+            nic_number_old: None,
+        }];
+        let mut same_nic = ROW;
+        same_nic[0] = "A/3";
+        same_nic[2] = "198534000937";
+        let mut taken_appointment = ROW;
+        taken_appointment[0] = "B/1";
+        taken_appointment[2] = "199101500123";
+        let mut taken_nic = ROW;
+        taken_nic[0] = "C/1";
+        taken_nic[2] = "900150123V";
+
+        let prepared = prepare(&table(&[ROW, same_nic, taken_appointment, taken_nic]), &columns(8), &existing, true).unwrap();
+        assert_eq!(prepared.staff.len(), 1);
+
+        let conflicts: Vec<_> = prepared.report.rows.iter().map(|row| (row.row, &row.conflicts[0])).collect();
+        assert_eq!(conflicts.len(), 3);
+        assert_eq!(conflicts[0].0, 3);
+        assert_eq!(conflicts[0].1.field, StaffImportField::NicNumber);
+        assert_eq!(conflicts[0].1.existing_row, Some(2));
+        assert_eq!(conflicts[1].1.field, StaffImportField::AppointmentNumber);
+        assert_eq!(conflicts[1].1.existing_staff_id.as_deref(), Some("staff-1"));
+        assert_eq!(conflicts[2].1.field, StaffImportField::NicNumber);
+        assert_eq!(conflicts[2].1.existing_staff_id.as_deref(), Some("staff-1"));
+    }
+
+    #[test]
+    fn a_mapping_must_name_real_and_required_columns() {
+        let error = prepare(&table(&[ROW]), &columns(5), &[], true).err().unwrap();
+        assert_eq!(error, "No column is mapped to Date of First Appointment");
+
+        let mut missing = columns(8);
+        missing[0].column = "Number".to_string();
+        let error = prepare(&table(&[ROW]), &missing, &[], true).err().unwrap();
+        assert_eq!(error, "The file has no column named \"Number\"");
+    }
+}
@@ -0,0 +1,102 @@
+//! Reading CSV and Excel files into rows of text.
+use calamine::{open_workbook_auto, Data, Reader};
+use std::path::Path;
+
+/// The contents of one CSV file or worksheet. `rows` excludes the header row
+/// and any rows that are entirely blank.
+pub struct Table {
+    pub sheets: Vec<String>,
+    pub sheet: Option<String>,
+    pub headers: Vec<String>,
+    /// Rows with their sheet row number, counting the header as row 1
+    pub rows: Vec<(usize, Vec<String>)>,
+}
+
+pub fn read(path: &Path, sheet: Option<&str>) -> Result<Table, String> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "csv" | "txt" => read_csv(path),
+        "xlsx" | "xlsm" | "xls" | "ods" => read_workbook(path, sheet),
+        _ => Err("Unsupported file type; choose a .csv or .xlsx file".to_string()),
+    }
+}
+
+fn read_csv(path: &Path) -> Result<Table, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    let mut records: Vec<(usize, Vec<String>)> = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| format!("Failed to read CSV record {}: {}", index + 1, e))?;
+        // Line numbers, which differ from record numbers once a quoted value spans lines
+        let line = record.position().map(|position| position.line() as usize).unwrap_or(index + 1);
+        records.push((line, record.iter().map(|value| value.trim().to_string()).collect()));
+    }
+
+    // Excel writes a byte order mark ahead of the first header
+    if let Some(first) = records.first_mut().and_then(|(_, row)| row.first_mut()) {
+        *first = first.trim_start_matches('\u{feff}').to_string();
+    }
+
+    Ok(into_table(Vec::new(), None, records))
+}
+
+fn read_workbook(path: &Path, sheet: Option<&str>) -> Result<Table, String> {
+    let mut workbook = open_workbook_auto(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    let sheets = workbook.sheet_names();
+    let sheet = match sheet {
+        Some(name) if sheets.iter().any(|sheet| sheet == name) => name.to_string(),
+        Some(name) => return Err(format!("The workbook has no sheet named \"{}\"", name)),
+        None => sheets.first().cloned().ok_or("The workbook has no sheets")?,
+    };
+
+    let range = workbook
+        .worksheet_range(&sheet)
+        .map_err(|e| format!("Failed to read sheet \"{}\": {}", sheet, e))?;
+
+    // Row numbers as Excel shows them, even when the data doesn't start at A1
+    let first_row = range.start().map(|(row, _)| row as usize + 1).unwrap_or(1);
+    let records = range
+        .rows()
+        .enumerate()
+        .map(|(index, row)| (first_row + index, row.iter().map(cell_text).collect()))
+        .collect();
+
+    Ok(into_table(sheets, Some(sheet), records))
+}
+
+/// Cell values as the text a clerk would have typed: whole numbers without
+/// a decimal point and dates as `YYYY-MM-DD`.
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::Empty | Data::Error(_) => String::new(),
+        Data::String(value) => value.trim().to_string(),
+        Data::Float(value) if value.fract() == 0.0 && value.abs() < 1e15 => format!("{}", *value as i64),
+        Data::DateTime(value) => match value.as_datetime() {
+            Some(datetime) => datetime.date().format("%Y-%m-%d").to_string(),
+            None => value.to_string(),
+        },
+        Data::DateTimeIso(value) => value.get(..10).unwrap_or(value).to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn into_table(sheets: Vec<String>, sheet: Option<String>, records: Vec<(usize, Vec<String>)>) -> Table {
+    let mut records = records
+        .into_iter()
+        .filter(|(_, row)| row.iter().any(|value| !value.is_empty()));
+
+    let headers = records.next().map(|(_, headers)| headers).unwrap_or_default();
+
+    Table { sheets, sheet, headers, rows: records.collect() }
+}
@@ -1,3 +1,8 @@
+pub mod auth;
+pub mod backup;
 pub mod commands;
 pub mod database;
+pub mod export;
+pub mod import;
+pub mod reports;
 pub mod utils;
\ No newline at end of file
@@ -1,12 +1,17 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod auth;
+mod backup;
 mod commands;
 mod database;
+mod export;
+mod import;
+mod reports;
 mod utils;
 
-use commands::{staff::*, print::*};
-use database::operations::initialize_database;
+use commands::{staff::*, print::*, saved_searches::*, settings::*, export::*, import::*, backup::*, encryption::*, auth::*, audit::*};
+use database::{encryption::is_encrypted, operations::initialize_database};
 use tauri_plugin_fs;
 use tauri_plugin_dialog;
 use tauri_plugin_shell;
@@ -17,6 +22,7 @@ fn main() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
+        .manage(auth::SessionState::default())
         .setup(|app| {
             let app_data_dir = app
                 .path()
@@ -26,8 +32,16 @@ fn main() {
             // Create app data directory if it doesn't exist
             std::fs::create_dir_all(&app_data_dir)?;
 
-            // Initialize database
-            initialize_database(&app_data_dir)?;
+            // Initialize database; an encrypted one is initialized once it is unlocked
+            if !is_encrypted(&app_data_dir)? {
+                initialize_database(&app_data_dir)?;
+            }
+
+            // Back up now and then on the configured schedule
+            backup::start_scheduler(app_data_dir);
+
+            // Lock the login session once it has been left idle
+            auth::start_idle_watcher(app.app_handle().clone());
 
             Ok(())
         })
@@ -36,20 +50,96 @@ fn main() {
             create_staff,
             get_all_staff,
             get_staff_by_id,
+            get_staff_photo,
             update_staff,
+            patch_staff,
+            preview_bulk_staff_update,
+            bulk_update_staff,
             delete_staff,
             search_staff,
             get_staff_by_nic,
 
+            // Saved searches and staff groups
+            create_saved_search,
+            list_saved_searches,
+            update_saved_search,
+            delete_saved_search,
+            run_saved_search,
+            get_saved_search_staff_ids,
+            print_saved_search,
+            create_staff_group,
+            list_staff_groups,
+            update_staff_group,
+            add_staff_to_group,
+            remove_staff_from_group,
+            delete_staff_group,
+            run_staff_group,
+            print_staff_group,
+
+            // Office settings
+            get_office_settings,
+            update_office_settings,
+            set_office_logo,
+            remove_office_logo,
+
+            // Accounts and audit log
+            get_auth_status,
+            create_first_admin,
+            login,
+            logout,
+            touch_session,
+            unlock_session,
+            change_password,
+            list_users,
+            create_user,
+            update_user,
+            reset_user_password,
+            get_security_settings,
+            update_security_settings,
+            list_audit_log,
+
+            // Database encryption
+            get_encryption_status,
+            unlock_database,
+            encrypt_database,
+            change_database_passphrase,
+
+            // Backups
+            get_backup_settings,
+            update_backup_settings,
+            list_backups,
+            create_backup_now,
+            restore_backup,
+
             // PDF generation commands
             generate_staff_pdf,
             generate_bulk_staff_pdf,
+            generate_bulk_staff_pdf_with_params,
             export_staff_pdf,
             open_downloads_folder,
 
+            // Data export
+            export_staff_csv,
+            export_staff_xlsx,
+            export_all,
+
+            // Data import
+            preview_staff_import,
+            import_staff,
+            create_import_mapping,
+            list_import_mappings,
+            update_import_mapping,
+            delete_import_mapping,
+            import_all,
+
             // Preview commands
             generate_staff_preview,
             generate_bulk_staff_preview,
+            generate_bulk_staff_preview_with_params,
+
+            // Report templates
+            get_report_templates_dir,
+            reset_report_templates,
 
             // Legacy print commands (now generate PDFs)
             print_staff_individual,
@@ -0,0 +1,328 @@
+//! HTML output for the report preview.
+//!
+//! Every string taken from the document is passed through [`escape`] on its
+//! way into the markup, so staff data and template output always render as
+//! text. Nothing else in the reports module produces HTML.
+use super::{Block, Document, LetterheadStyle};
+use crate::utils::pdf::{Align, Orientation};
+
+pub fn render(document: &Document) -> String {
+    let orientation = match document.orientation {
+        Orientation::Portrait => "portrait",
+        Orientation::Landscape => "landscape",
+    };
+
+    let letterhead = document.letterhead
+        .iter()
+        .map(|(text, style)| {
+            let class = match style {
+                LetterheadStyle::Title => "org-title",
+                LetterheadStyle::Subtitle => "org-subtitle",
+                LetterheadStyle::Location => "org-location",
+            };
+            format!(r#"<div class="{}">{}</div>"#, class, escape(text))
+        })
+        .collect::<Vec<_>>()
+        .join("\n            ");
+
+    let logo = match &document.logo {
+        Some(image_data) => format!(r#"<img src="data:image/jpeg;base64,{}" alt="Office Logo" class="office-logo">"#, escape(image_data)),
+        None => String::new(),
+    };
+
+    let photo = match &document.photo {
+        Some(Some(image_data)) => {
+            format!(r#"<img src="data:image/jpeg;base64,{}" alt="Staff Photo" class="staff-photo">"#, escape(image_data))
+        }
+        Some(None) => r#"<div class="staff-photo-placeholder"><div class="photo-text">PHOTO</div></div>"#.to_string(),
+        None => String::new(),
+    };
+
+    let mut content = String::new();
+    let mut after_content = String::new();
+    for block in &document.blocks {
+        match block {
+            Block::Summary(lines) => {
+                let lines = lines.iter().map(|line| format!("<div>{}</div>", escape(line))).collect::<String>();
+                content.push_str(&format!(r#"<div class="summary">{}</div>"#, lines));
+            }
+            Block::Section { title, fields } => {
+                let rows = fields
+                    .iter()
+                    .map(|(label, value)| format!(
+                        r#"<div class="field-row"><div class="field-label">{}:</div><div class="field-value">{}</div></div>"#,
+                        escape(label), escape(value)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join("\n                ");
+                content.push_str(&format!(
+                    r#"
+            <div class="section">
+                <div class="section-title">{}</div>
+                {}
+            </div>"#,
+                    escape(title), rows
+                ));
+            }
+            Block::Table { columns, rows } => {
+                let header = columns
+                    .iter()
+                    .map(|column| format!(r#"<th style="width: {:.1}%;">{}</th>"#, column.width * 100.0, escape(&column.title)))
+                    .collect::<String>();
+                let body = rows
+                    .iter()
+                    .map(|row| {
+                        let cells = row
+                            .iter()
+                            .zip(columns)
+                            .map(|(cell, column)| format!(r#"<td style="text-align: {};">{}</td>"#, css_align(column.align), escape(cell)))
+                            .collect::<String>();
+                        format!("<tr>{}</tr>", cells)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n                ");
+                content.push_str(&format!(
+                    r#"
+            <table>
+                <thead><tr>{}</tr></thead>
+                <tbody>
+                {}
+                </tbody>
+            </table>"#,
+                    header, body
+                ));
+            }
+            // Signatures span the full width, below the photo column
+            Block::Signatures(boxes) => {
+                let boxes = boxes
+                    .iter()
+                    .map(|lines| format!(
+                        r#"<div class="signature-box"><div class="signature-line">{}</div></div>"#,
+                        lines.iter().map(|line| escape(line)).collect::<Vec<_>>().join("<br>")
+                    ))
+                    .collect::<String>();
+                after_content.push_str(&format!(r#"<div class="signature-section">{}</div>"#, boxes));
+            }
+        }
+    }
+
+    let footer = document.footer.iter().map(|line| escape(line)).collect::<Vec<_>>().join("<br>\n                ");
+
+    format!(r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="UTF-8">
+    <title>{title}</title>
+    <style>
+        @page {{ size: A4 {orientation}; margin: {margin}mm; }}
+{stylesheet}
+    </style>
+</head>
+<body>
+    <div class="document {orientation}{has_photo}">
+        <div class="header">
+            {logo}
+            {letterhead}
+            <div class="document-title">{heading}</div>
+            {photo}
+        </div>
+
+        <div class="content">{content}
+        </div>
+        {after_content}
+
+        <div class="footer">
+            <div>
+                {footer}
+            </div>
+        </div>
+    </div>
+</body>
+</html>
+"#,
+        title = escape(&document.title),
+        orientation = orientation,
+        margin = document.margin,
+        stylesheet = escape_stylesheet(&document.stylesheet),
+        has_photo = if document.photo.is_some() { " has-photo" } else { "" },
+        logo = logo,
+        letterhead = letterhead,
+        heading = escape(&document.heading),
+        photo = photo,
+        content = content,
+        after_content = after_content,
+        footer = footer,
+    )
+}
+
+/// Escape text for use in element content or a quoted attribute value.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The stylesheet is raw CSS, so it cannot be entity-escaped; just make sure
+/// it cannot close the `<style>` element it is embedded in.
+fn escape_stylesheet(css: &str) -> String {
+    css.replace("</", "<\\/")
+}
+
+fn css_align(align: Align) -> &'static str {
+    match align {
+        Align::Left => "left",
+        Align::Center => "center",
+        Align::Right => "right",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::{OfficeSettings, Staff};
+    use crate::reports::{self, STYLESHEET};
+
+    const HOSTILE: &str = r#"<script>alert("x")</script> & 'quoted' <b>bold</b>"#;
+    const HOSTILE_ESCAPED: &str = "&lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt; &amp; &#39;quoted&#39; &lt;b&gt;bold&lt;/b&gt;";
+
+    fn hostile_staff() -> Staff {
+        let now = chrono::Utc::now();
+        Staff {
+            id: "1".to_string(),
+            appointment_number: HOSTILE.to_string(),
+            full_name: HOSTILE.to_string(),
+            gender: "Male".to_string(),
+            date_of_birth: HOSTILE.to_string(),
+            age: 40,
+            nic_number: HOSTILE.to_string(),
+            nic_number_old: None,
+            marital_status: "Single".to_string(),
+            address_line1: Some(HOSTILE.to_string()),
+            address_line2: Some("</div></div><div>".to_string()),
+            address_line3: None,
+            contact_number: Some(HOSTILE.to_string()),
+            email: Some(HOSTILE.to_string()),
+            designation: HOSTILE.to_string(),
+            date_of_first_appointment: "2010-01-01".to_string(),
+            date_of_retirement: "2045-01-01".to_string(),
+            increment_date: Some(HOSTILE.to_string()),
+            salary_code: HOSTILE.to_string(),
+            basic_salary: 50000.0,
+            increment_amount: 1000.0,
+            has_photo: false,
+            photo_thumbnail: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            masked: false,
+        }
+    }
+
+    fn settings(value: &str) -> OfficeSettings {
+        OfficeSettings {
+            office_name: value.to_string(),
+            division: value.to_string(),
+            address: value.to_string(),
+            phone: Some(value.to_string()),
+            logo: None,
+            certifying_officer_name: Some(value.to_string()),
+            certifying_officer_designation: value.to_string(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn assert_no_markup_injected(html: &str) {
+        assert!(!html.contains("<script"), "unescaped script tag in:\n{}", html);
+        assert!(!html.contains("<b>"), "unescaped markup in:\n{}", html);
+        assert!(!html.contains("</div></div><div>"), "unescaped closing tags in:\n{}", html);
+        assert!(html.contains(HOSTILE_ESCAPED));
+    }
+
+    #[test]
+    fn escape_replaces_markup_characters() {
+        assert_eq!(escape(HOSTILE), HOSTILE_ESCAPED);
+        assert_eq!(escape("Plain text, 123"), "Plain text, 123");
+        assert_eq!(escape("&amp;"), "&amp;amp;");
+    }
+
+    #[test]
+    fn staff_record_escapes_staff_fields() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().to_path_buf();
+        let html = render(&reports::staff_record(&dir, &settings("Office"), &hostile_staff(), None).unwrap());
+
+        assert_no_markup_injected(&html);
+        assert!(html.contains("&lt;/div&gt;&lt;/div&gt;&lt;div&gt;"));
+        assert!(html.contains(r#"<div class="staff-photo-placeholder">"#));
+    }
+
+    #[test]
+    fn staff_record_keeps_photo_data_inside_attribute() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().to_path_buf();
+        let photo = r#"abc" onerror="alert(1)"#;
+        let html = render(&reports::staff_record(&dir, &settings("Office"), &hostile_staff(), Some(photo)).unwrap());
+
+        assert!(!html.contains(r#"" onerror=""#));
+        assert!(html.contains("abc&quot; onerror=&quot;alert(1)"));
+    }
+
+    #[test]
+    fn staff_directory_escapes_rows_and_criteria() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().to_path_buf();
+        let staff = vec![hostile_staff(), hostile_staff()];
+        let criteria = format!("Filters: Search: \"{}\" | Sorted by: Full Name (ascending)", HOSTILE);
+        let html = render(&reports::staff_directory(&dir, &settings("Office"), &staff, &criteria).unwrap());
+
+        assert_no_markup_injected(&html);
+        // Six text columns in each of the two rows, plus the criteria line
+        assert_eq!(html.matches(HOSTILE_ESCAPED).count(), 2 * 6 + 1);
+    }
+
+    #[test]
+    fn office_settings_are_escaped_in_each_report() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().to_path_buf();
+        let mut settings = settings(HOSTILE);
+        settings.logo = Some(r#"abc" onerror="alert(1)"#.to_string());
+
+        let record = render(&reports::staff_record(&dir, &settings, &hostile_staff(), None).unwrap());
+        let directory = render(&reports::staff_directory(&dir, &settings, &[hostile_staff()], "All staff").unwrap());
+
+        for html in [record, directory] {
+            assert_no_markup_injected(&html);
+            assert!(html.contains(&format!("Tel: {}", HOSTILE_ESCAPED)));
+            assert!(!html.contains(r#"" onerror=""#));
+        }
+    }
+
+    #[test]
+    fn stylesheet_cannot_close_style_element() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().to_path_buf();
+        reports::install_default_templates(&dir).unwrap();
+        std::fs::write(
+            reports::templates_dir(&dir).join(STYLESHEET),
+            "body { color: red; }</style><script>alert(1)</script>",
+        )
+        .unwrap();
+
+        let html = render(&reports::staff_directory(&dir, &settings("Office"), &[hostile_staff()], "All staff").unwrap());
+
+        // Whatever the CSS holds, it stays inside the one style element
+        assert_eq!(html.matches("</style>").count(), 1);
+        let (head, body) = html.split_once("</style>").unwrap();
+        assert!(head.contains("<\\/style>"));
+        assert!(!body.contains("<script"));
+    }
+}
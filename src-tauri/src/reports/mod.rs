@@ -0,0 +1,463 @@
+//! Template driven reports.
+//!
+//! Each report is a JSON template in `<app data>/templates/` describing the
+//! letterhead, blocks and footer; its strings are minijinja expressions
+//! evaluated against the staff data and the office settings (`settings.*`).
+//! A template renders to a [`Document`], which is then written out as HTML
+//! for the preview or as a PDF for export, so both always show the same
+//! content. Missing templates are restored from
+//! the defaults shipped with the app; existing ones are never overwritten.
+pub mod html;
+pub mod pdf;
+
+use minijinja::{context, Environment, Value};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::auth::masking::RESTRICTED;
+use crate::database::schema::{OfficeSettings, Staff};
+use crate::utils::pdf::{Align, Orientation};
+
+pub const STAFF_RECORD_TEMPLATE: &str = "staff_record.json";
+pub const STAFF_DIRECTORY_TEMPLATE: &str = "staff_directory.json";
+pub const STYLESHEET: &str = "report.css";
+
+const DEFAULT_FILES: [(&str, &str); 3] = [
+    (STAFF_RECORD_TEMPLATE, include_str!("../../templates/staff_record.json")),
+    (STAFF_DIRECTORY_TEMPLATE, include_str!("../../templates/staff_directory.json")),
+    (STYLESHEET, include_str!("../../templates/report.css")),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LetterheadStyle {
+    Title,
+    #[default]
+    Subtitle,
+    Location,
+}
+
+#[derive(Debug, Deserialize)]
+struct LetterheadLine {
+    text: String,
+    #[serde(default)]
+    style: LetterheadStyle,
+}
+
+#[derive(Debug, Deserialize)]
+struct FieldTemplate {
+    label: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ColumnTemplate {
+    title: String,
+    /// Relative width; columns are scaled to fill the page.
+    width: f32,
+    #[serde(default = "default_align")]
+    align: Align,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum BlockTemplate {
+    Summary { lines: Vec<String> },
+    Section { title: String, fields: Vec<FieldTemplate> },
+    /// One row per staff member in `staff_list`
+    Table { columns: Vec<ColumnTemplate> },
+    Signatures { boxes: Vec<Vec<String>> },
+}
+
+#[derive(Debug, Deserialize)]
+struct ReportTemplate {
+    title: String,
+    orientation: Orientation,
+    /// Page margin in millimetres
+    #[serde(default = "default_margin")]
+    margin: f32,
+    #[serde(default)]
+    letterhead: Vec<LetterheadLine>,
+    heading: String,
+    #[serde(default)]
+    photo: bool,
+    #[serde(default)]
+    blocks: Vec<BlockTemplate>,
+    #[serde(default)]
+    footer: Vec<String>,
+}
+
+fn default_margin() -> f32 {
+    15.0
+}
+
+fn default_align() -> Align {
+    Align::Left
+}
+
+pub struct Column {
+    pub title: String,
+    /// Fraction of the content width
+    pub width: f32,
+    pub align: Align,
+}
+
+pub enum Block {
+    Summary(Vec<String>),
+    Section { title: String, fields: Vec<(String, String)> },
+    Table { columns: Vec<Column>, rows: Vec<Vec<String>> },
+    Signatures(Vec<Vec<String>>),
+}
+
+/// A report with every template expression evaluated, ready to be written out.
+pub struct Document {
+    pub title: String,
+    pub orientation: Orientation,
+    pub margin: f32,
+    pub letterhead: Vec<(String, LetterheadStyle)>,
+    pub heading: String,
+    /// Office logo shown beside the letterhead, base64 JPEG
+    pub logo: Option<String>,
+    /// `Some` when the template has a photo box; the inner value is the base64 JPEG, if any.
+    pub photo: Option<Option<String>>,
+    pub blocks: Vec<Block>,
+    pub footer: Vec<String>,
+    pub stylesheet: String,
+}
+
+pub fn templates_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("templates")
+}
+
+/// Write any missing default templates into the templates directory.
+pub fn install_default_templates(app_data_dir: &Path) -> Result<(), String> {
+    let dir = templates_dir(app_data_dir);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create templates directory: {}", e))?;
+
+    for (name, contents) in DEFAULT_FILES {
+        let path = dir.join(name);
+        if !path.exists() {
+            fs::write(&path, contents)
+                .map_err(|e| format!("Failed to write default template {}: {}", name, e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Overwrite the templates with the shipped defaults, discarding local edits.
+pub fn reset_templates(app_data_dir: &Path) -> Result<(), String> {
+    let dir = templates_dir(app_data_dir);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create templates directory: {}", e))?;
+
+    for (name, contents) in DEFAULT_FILES {
+        fs::write(dir.join(name), contents)
+            .map_err(|e| format!("Failed to write default template {}: {}", name, e))?;
+    }
+
+    Ok(())
+}
+
+fn read_template_file(app_data_dir: &Path, name: &str) -> Result<String, String> {
+    install_default_templates(app_data_dir)?;
+
+    let path = templates_dir(app_data_dir).join(name);
+    fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read template {}: {}", path.display(), e))
+}
+
+fn load_template(app_data_dir: &Path, name: &str) -> Result<ReportTemplate, String> {
+    let source = read_template_file(app_data_dir, name)?;
+
+    serde_json::from_str(&source)
+        .map_err(|e| format!("Invalid report template {}: {}", name, e))
+}
+
+fn environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.add_filter("date", |value: String| format_date(&value));
+    // Masked amounts arrive as text and are shown as they are
+    env.add_filter("currency", |value: Value| match f64::try_from(value.clone()) {
+        Ok(amount) => format_currency(amount),
+        Err(_) => value.to_string(),
+    });
+    env
+}
+
+/// A staff record as templates see it. Fields hidden by masking read
+/// "Restricted" rather than showing up as a zero salary or no address.
+fn staff_value(staff: &Staff) -> Value {
+    if !staff.masked {
+        return Value::from_serialize(staff);
+    }
+
+    let mut value = serde_json::to_value(staff).unwrap_or_default();
+    for field in ["basic_salary", "increment_amount", "address_line1", "address_line2", "address_line3"] {
+        value[field] = RESTRICTED.into();
+    }
+    Value::from_serialize(&value)
+}
+
+fn render(env: &Environment, source: &str, ctx: &Value) -> Result<String, String> {
+    env.render_str(source, ctx)
+        .map_err(|e| format!("Failed to render template expression \"{}\": {}", source, e))
+}
+
+fn render_all(env: &Environment, sources: &[String], ctx: &Value) -> Result<Vec<String>, String> {
+    sources.iter().map(|source| render(env, source, ctx)).collect()
+}
+
+/// Render a list of lines, leaving out the ones that come out blank (an
+/// unset phone number, say).
+fn render_lines(env: &Environment, sources: &[String], ctx: &Value) -> Result<Vec<String>, String> {
+    let mut lines = render_all(env, sources, ctx)?;
+    lines.retain(|line| !line.trim().is_empty());
+    Ok(lines)
+}
+
+/// Evaluate a template into a document. `rows` feeds table blocks, each row
+/// rendered with `index` and `staff` added to the shared context.
+fn build_document(
+    app_data_dir: &Path,
+    template: ReportTemplate,
+    settings: &OfficeSettings,
+    ctx: Value,
+    rows: &[Staff],
+    photo: Option<&str>,
+) -> Result<Document, String> {
+    let env = environment();
+    let ctx = context! { settings => settings, ..ctx };
+
+    let mut letterhead = Vec::new();
+    for line in &template.letterhead {
+        let text = render(&env, &line.text, &ctx)?;
+        if !text.trim().is_empty() {
+            letterhead.push((text, line.style));
+        }
+    }
+
+    let mut blocks = Vec::new();
+    for block in &template.blocks {
+        blocks.push(match block {
+            BlockTemplate::Summary { lines } => Block::Summary(render_all(&env, lines, &ctx)?),
+            BlockTemplate::Section { title, fields } => Block::Section {
+                title: render(&env, title, &ctx)?,
+                fields: fields
+                    .iter()
+                    .map(|field| Ok((render(&env, &field.label, &ctx)?, render(&env, &field.value, &ctx)?)))
+                    .collect::<Result<Vec<_>, String>>()?,
+            },
+            BlockTemplate::Table { columns } => {
+                let total_width: f32 = columns.iter().map(|c| c.width.max(0.0)).sum::<f32>().max(f32::EPSILON);
+                let cells = columns
+                    .iter()
+                    .map(|column| {
+                        env.template_from_str(&column.value)
+                            .map_err(|e| format!("Invalid template expression \"{}\": {}", column.value, e))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                let mut table_rows = Vec::with_capacity(rows.len());
+                for (index, staff) in rows.iter().enumerate() {
+                    let row_ctx = context! { index => index + 1, staff => staff_value(staff), ..ctx.clone() };
+                    table_rows.push(
+                        cells
+                            .iter()
+                            .map(|cell| cell.render(&row_ctx).map_err(|e| format!("Failed to render table cell: {}", e)))
+                            .collect::<Result<Vec<_>, String>>()?,
+                    );
+                }
+
+                Block::Table {
+                    columns: columns
+                        .iter()
+                        .map(|column| Ok(Column {
+                            title: render(&env, &column.title, &ctx)?,
+                            width: column.width.max(0.0) / total_width,
+                            align: column.align,
+                        }))
+                        .collect::<Result<Vec<_>, String>>()?,
+                    rows: table_rows,
+                }
+            }
+            BlockTemplate::Signatures { boxes } => Block::Signatures(
+                boxes.iter().map(|lines| render_lines(&env, lines, &ctx)).collect::<Result<Vec<_>, String>>()?,
+            ),
+        });
+    }
+
+    Ok(Document {
+        title: render(&env, &template.title, &ctx)?,
+        orientation: template.orientation,
+        margin: template.margin,
+        letterhead,
+        heading: render(&env, &template.heading, &ctx)?,
+        logo: settings.logo.clone(),
+        photo: template.photo.then(|| photo.map(str::to_string)),
+        blocks,
+        footer: render_lines(&env, &template.footer, &ctx)?,
+        stylesheet: read_template_file(app_data_dir, STYLESHEET)?,
+    })
+}
+
+fn generated_date() -> String {
+    chrono::Utc::now().format("%d-%m-%Y").to_string()
+}
+
+/// Individual staff record.
+pub fn staff_record(
+    app_data_dir: &Path,
+    settings: &OfficeSettings,
+    staff: &Staff,
+    photo: Option<&str>,
+) -> Result<Document, String> {
+    let template = load_template(app_data_dir, STAFF_RECORD_TEMPLATE)?;
+    let ctx = context! {
+        staff => staff_value(staff),
+        address => format_address(staff),
+        generated_date => generated_date(),
+    };
+
+    build_document(app_data_dir, template, settings, ctx, &[], photo)
+}
+
+/// Staff directory; `criteria` describes the filters and sort order used.
+pub fn staff_directory(
+    app_data_dir: &Path,
+    settings: &OfficeSettings,
+    staff_list: &[Staff],
+    criteria: &str,
+) -> Result<Document, String> {
+    let template = load_template(app_data_dir, STAFF_DIRECTORY_TEMPLATE)?;
+    let ctx = context! {
+        staff_count => staff_list.len(),
+        criteria => criteria,
+        generated_date => generated_date(),
+    };
+
+    build_document(app_data_dir, template, settings, ctx, staff_list, None)
+}
+
+pub fn format_currency(value: f64) -> String {
+    let formatted = format!("{:.2}", value);
+    let parts: Vec<&str> = formatted.split('.').collect();
+    let integer_part = parts[0];
+    let decimal_part = if parts.len() > 1 { parts[1] } else { "00" };
+
+    let mut result = String::new();
+    let chars: Vec<char> = integer_part.chars().collect();
+
+    for (i, c) in chars.iter().enumerate() {
+        if i > 0 && (chars.len() - i) % 3 == 0 {
+            result.push(',');
+        }
+        result.push(*c);
+    }
+
+    format!("Rs. {}.{}", result, decimal_part)
+}
+
+pub fn format_date(date_str: &str) -> String {
+    if let Ok(date) = chrono::DateTime::parse_from_rfc3339(date_str) {
+        date.format("%d-%m-%Y").to_string()
+    } else if let Ok(date) = chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+        date.format("%d-%m-%Y").to_string()
+    } else {
+        date_str.to_string()
+    }
+}
+
+/// Non-empty address lines joined with commas.
+pub fn format_address(staff: &Staff) -> String {
+    if staff.masked {
+        return RESTRICTED.to_string();
+    }
+
+    [&staff.address_line1, &staff.address_line2, &staff.address_line3]
+        .into_iter()
+        .flatten()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{insert_staff, test_database};
+
+    fn settings() -> OfficeSettings {
+        OfficeSettings {
+            office_name: "Forest Office".to_string(),
+            division: "Vavuniya Division".to_string(),
+            address: "Station Road, Vavuniya".to_string(),
+            phone: None,
+            logo: None,
+            certifying_officer_name: None,
+            certifying_officer_designation: "Divisional Forest Officer".to_string(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Change one top-level entry of an installed template.
+    fn edit_template(app_data_dir: &Path, name: &str, key: &str, value: serde_json::Value) {
+        install_default_templates(app_data_dir).unwrap();
+        let path = templates_dir(app_data_dir).join(name);
+        let mut template: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        template[key] = value;
+        fs::write(&path, template.to_string()).unwrap();
+    }
+
+    #[test]
+    fn an_edited_template_replaces_the_default() {
+        let dir = test_database();
+        let staff = insert_staff(&dir, 1);
+        let document = staff_record(dir.path(), &settings(), &staff, None).unwrap();
+        assert_eq!(document.heading, "Official Staff Record");
+
+        edit_template(dir.path(), STAFF_RECORD_TEMPLATE, "heading", "Service Record of {{ staff.full_name }}".into());
+        let document = staff_record(dir.path(), &settings(), &staff, None).unwrap();
+        assert_eq!(document.heading, "Service Record of Person 1 Kumar");
+    }
+
+    #[test]
+    fn a_broken_template_is_an_error() {
+        let dir = test_database();
+        let staff = insert_staff(&dir, 1);
+
+        edit_template(dir.path(), STAFF_RECORD_TEMPLATE, "heading", "{{ staff.full_name | no_such_filter }}".into());
+        let error = staff_record(dir.path(), &settings(), &staff, None).err().unwrap();
+        assert!(error.starts_with("Failed to render template expression"), "{}", error);
+
+        edit_template(dir.path(), STAFF_DIRECTORY_TEMPLATE, "blocks", serde_json::json!([
+            { "type": "table", "columns": [{ "title": "Name", "width": 1, "value": "{{ staff.full_name" }] }
+        ]));
+        let error = staff_directory(dir.path(), &settings(), &[staff], "All staff").err().unwrap();
+        assert!(error.starts_with("Invalid template expression"), "{}", error);
+
+        fs::write(templates_dir(dir.path()).join(STAFF_RECORD_TEMPLATE), "{ not json").unwrap();
+        let error = staff_record(dir.path(), &settings(), &insert_staff(&dir, 2), None).err().unwrap();
+        assert!(error.starts_with("Invalid report template staff_record.json"), "{}", error);
+    }
+
+    #[test]
+    fn installing_defaults_keeps_edited_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = templates_dir(temp.path());
+        install_default_templates(temp.path()).unwrap();
+
+        fs::write(dir.join(STYLESHEET), "body { color: green; }").unwrap();
+        fs::remove_file(dir.join(STAFF_DIRECTORY_TEMPLATE)).unwrap();
+        install_default_templates(temp.path()).unwrap();
+
+        assert_eq!(fs::read_to_string(dir.join(STYLESHEET)).unwrap(), "body { color: green; }");
+        assert_eq!(fs::read_to_string(dir.join(STAFF_DIRECTORY_TEMPLATE)).unwrap(), DEFAULT_FILES[1].1);
+
+        reset_templates(temp.path()).unwrap();
+        assert_eq!(fs::read_to_string(dir.join(STYLESHEET)).unwrap(), DEFAULT_FILES[2].1);
+    }
+}
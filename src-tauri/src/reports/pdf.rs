@@ -0,0 +1,206 @@
+//! Native PDF output for report export.
+use super::{Block, Document, LetterheadStyle};
+use crate::utils::pdf::{line_height, Align, Column, Orientation, PdfReport, Weight};
+
+const LABEL_WIDTH: f32 = 55.0;
+const PHOTO_WIDTH: f32 = 30.0;
+const PHOTO_HEIGHT: f32 = 40.0;
+const SIGNATURE_WIDTH: f32 = 65.0;
+const LOGO_SIZE: f32 = 22.0;
+
+pub fn render(document: &Document) -> Result<Vec<u8>, String> {
+    let mut report = PdfReport::new(&document.title, document.orientation, document.margin)?;
+    report.set_footer(document.footer.clone());
+
+    // Landscape reports are dense tables; keep their letterhead compact
+    let scale = match document.orientation {
+        Orientation::Portrait => 1.0,
+        Orientation::Landscape => 0.85,
+    };
+
+    draw_letterhead(&mut report, document, scale);
+
+    // Photo in the top right corner, with a placeholder box if missing or unreadable
+    let mut photo_bottom = 0.0;
+    if let Some(photo) = &document.photo {
+        let photo_x = report.left() + report.content_width() - PHOTO_WIDTH;
+        let photo_y = report.y;
+        let photo_drawn = photo
+            .as_deref()
+            .map(|image_data| report.image(image_data, photo_x, photo_y, PHOTO_WIDTH, PHOTO_HEIGHT).is_ok())
+            .unwrap_or(false);
+        report.rect(photo_x, photo_y, PHOTO_WIDTH, PHOTO_HEIGHT, None);
+        if !photo_drawn {
+            report.text_in("PHOTO", photo_x, PHOTO_WIDTH, photo_y + PHOTO_HEIGHT / 2.0 - 2.0, 9.0, Weight::Bold, Align::Center);
+        }
+        photo_bottom = photo_y + PHOTO_HEIGHT + 3.0;
+    }
+
+    // Text runs narrower while it is still beside the photo
+    let available_width = |report: &PdfReport| {
+        if report.y < photo_bottom {
+            report.content_width() - PHOTO_WIDTH - 5.0
+        } else {
+            report.content_width()
+        }
+    };
+
+    for block in &document.blocks {
+        match block {
+            Block::Summary(lines) => {
+                let width = available_width(&report);
+                let mut wrapped = Vec::new();
+                for (i, line) in lines.iter().enumerate() {
+                    let (size, weight) = if i == 0 { (9.0, Weight::Bold) } else { (8.0, Weight::Regular) };
+                    for part in report.wrap(line, width - 4.0, size, weight) {
+                        wrapped.push((part, size, weight));
+                    }
+                }
+                let height = wrapped.iter().map(|(_, size, _)| line_height(*size)).sum::<f32>() + 3.0;
+
+                report.ensure_space(height);
+                report.rect(report.left(), report.y, width, height, Some(0.96));
+                let mut y = report.y + 1.5;
+                for (text, size, weight) in &wrapped {
+                    report.text_in(text, report.left(), width, y, *size, *weight, Align::Center);
+                    y += line_height(*size);
+                }
+                report.y += height + 4.0;
+            }
+            Block::Section { title, fields } => {
+                let title_height = line_height(11.0) + 2.0;
+                report.ensure_space(title_height + 3.0 + line_height(10.0));
+                report.rect(report.left(), report.y, available_width(&report), title_height, Some(0.9));
+                report.text(&title.to_uppercase(), report.left() + 2.0, report.y + 1.0, 11.0, Weight::Bold);
+                report.y += title_height + 3.0;
+
+                for (label, value) in fields {
+                    let lines = report.wrap(value, available_width(&report) - LABEL_WIDTH, 10.0, Weight::Regular);
+                    report.ensure_space(lines.len() as f32 * line_height(10.0));
+
+                    report.text(&format!("{}:", label), report.left(), report.y, 10.0, Weight::Bold);
+                    for line in lines {
+                        report.text(&line, report.left() + LABEL_WIDTH, report.y, 10.0, Weight::Regular);
+                        report.y += line_height(10.0);
+                    }
+                    report.y += 1.5;
+                }
+
+                report.y += 4.0;
+            }
+            Block::Table { columns, rows } => {
+                report.y = report.y.max(photo_bottom);
+                let columns: Vec<Column> = columns
+                    .iter()
+                    .map(|column| Column { title: &column.title, width: column.width, align: column.align })
+                    .collect();
+                report.table(&columns, rows, 8.0);
+                report.y += 4.0;
+            }
+            Block::Signatures(boxes) => {
+                let lines = boxes.iter().map(Vec::len).max().unwrap_or(0) as f32;
+                report.y = report.y.max(photo_bottom);
+                report.ensure_space(20.0 + 1.5 + lines * line_height(9.0));
+                report.y += 20.0;
+
+                // Spread the boxes evenly, first and last against the margins
+                let gap = if boxes.len() > 1 {
+                    (report.content_width() - SIGNATURE_WIDTH) / (boxes.len() - 1) as f32
+                } else {
+                    0.0
+                };
+                for (i, lines) in boxes.iter().enumerate() {
+                    let x = report.left() + i as f32 * gap;
+                    report.line(x, report.y, x + SIGNATURE_WIDTH, report.y, 0.4);
+                    let mut y = report.y + 1.5;
+                    for line in lines {
+                        report.text_in(line, x, SIGNATURE_WIDTH, y, 9.0, Weight::Regular, Align::Center);
+                        y += line_height(9.0);
+                    }
+                }
+                report.y += 1.5 + lines * line_height(9.0) + 4.0;
+            }
+        }
+    }
+
+    report.finish()
+}
+
+fn draw_letterhead(report: &mut PdfReport, document: &Document, scale: f32) {
+    // Logo against the left margin, level with the first letterhead line;
+    // a logo that fails to decode is left out rather than failing the report
+    if let Some(logo) = &document.logo {
+        let _ = report.image(logo, report.left(), report.y, LOGO_SIZE * scale, LOGO_SIZE * scale);
+    }
+
+    for (text, style) in &document.letterhead {
+        let (size, weight, text) = match style {
+            LetterheadStyle::Title => (14.0, Weight::Bold, text.to_uppercase()),
+            LetterheadStyle::Subtitle => (11.0, Weight::Regular, text.clone()),
+            LetterheadStyle::Location => (10.0, Weight::Regular, text.clone()),
+        };
+        report.centered_line(&text, size * scale, weight);
+    }
+
+    report.y += 2.0 * scale;
+    report.centered_line(&document.heading.to_uppercase(), 13.0 * scale, Weight::Bold);
+    report.y += 1.5;
+    report.rule(0.6);
+    report.y += 0.8;
+    report.rule(0.6);
+    report.y += 5.0 * scale;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::OfficeSettings;
+    use crate::database::test_support::{insert_staff, test_database};
+    use crate::reports::{staff_directory, staff_record};
+    use printpdf::lopdf;
+
+    fn page_count(bytes: &[u8]) -> usize {
+        lopdf::Document::load_mem(bytes).expect("output is not a readable PDF").get_pages().len()
+    }
+
+    fn settings() -> OfficeSettings {
+        OfficeSettings {
+            office_name: "Forest Office".to_string(),
+            division: "Vavuniya Division".to_string(),
+            address: "Station Road, Vavuniya".to_string(),
+            phone: None,
+            logo: None,
+            certifying_officer_name: Some("Ελένη Παπαδοπούλου".to_string()),
+            certifying_officer_designation: "Divisional Forest Officer".to_string(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn a_staff_record_renders_to_one_page() {
+        let dir = test_database();
+        let staff = insert_staff(&dir, 1);
+
+        let document = staff_record(dir.path(), &settings(), &staff, None).unwrap();
+        assert_eq!(page_count(&render(&document).unwrap()), 1);
+    }
+
+    #[test]
+    fn a_long_directory_runs_onto_more_pages() {
+        let dir = test_database();
+        let first = insert_staff(&dir, 1);
+        let staff_list: Vec<_> = (1..=120)
+            .map(|n| {
+                let mut staff = first.clone();
+                staff.full_name = format!("Наталья Иванова {}", n);
+                staff
+            })
+            .collect();
+
+        let short = staff_directory(dir.path(), &settings(), &staff_list[..5], "All staff").unwrap();
+        assert_eq!(page_count(&render(&short).unwrap()), 1);
+
+        let long = staff_directory(dir.path(), &settings(), &staff_list, "All staff").unwrap();
+        assert!(page_count(&render(&long).unwrap()) > 2);
+    }
+}
@@ -1,4 +1,9 @@
+pub mod nic;
+pub mod pdf;
+pub mod photo;
+
 use tauri::{AppHandle, Manager};
+use std::fs;
 use std::path::PathBuf;
 
 /// Resolve the app data directory and return a friendly String error on failure.
@@ -8,3 +13,24 @@ pub fn get_app_data_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
         .app_data_dir()
         .map_err(|e| format!("Failed to resolve app data directory: {}", e))
 }
+
+/// Write an exported file to the path the user picked, adding `.{extension}` if missing.
+pub fn write_output_file(output_path: &str, extension: &str, bytes: &[u8]) -> Result<PathBuf, String> {
+    if output_path.trim().is_empty() {
+        return Err("No output file selected".to_string());
+    }
+
+    let mut file_path = PathBuf::from(output_path);
+    let has_extension = file_path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case(extension))
+        .unwrap_or(false);
+    if !has_extension {
+        file_path.as_mut_os_string().push(format!(".{}", extension));
+    }
+
+    fs::write(&file_path, bytes)
+        .map_err(|e| format!("Failed to write {} file: {}", extension.to_uppercase(), e))?;
+
+    Ok(file_path)
+}
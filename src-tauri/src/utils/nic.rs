@@ -0,0 +1,129 @@
+//! Sri Lankan NIC numbers, mirroring `src/lib/nicConverter.ts`.
+//!
+//! Old format: `YYDDDSSSCV` (9 digits and V or X). New format: `YYYYDDDSSSSC`
+//! (12 digits). DDD is the day of the year of birth, plus 500 for women.
+use chrono::{Datelike, NaiveDate};
+
+/// A validated NIC in both formats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nic {
+    pub new_format: String,
+    pub old_format: Option<String>,
+}
+
+// Checked by byte, so other characters can't split a slice mid-character
+fn is_old_format(nic: &str) -> bool {
+    let bytes = nic.as_bytes();
+    bytes.len() == 10
+        && bytes[..9].iter().all(u8::is_ascii_digit)
+        && matches!(bytes[9], b'V' | b'X')
+}
+
+fn is_new_format(nic: &str) -> bool {
+    nic.len() == 12 && nic.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Validate a NIC in either format and convert it to the new format, keeping
+/// the old form alongside. Spaces and dashes are ignored.
+pub fn normalize_nic(nic: &str) -> Result<Nic, String> {
+    let cleaned: String = nic
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_uppercase();
+
+    if is_old_format(&cleaned) {
+        let year: u32 = cleaned[..2].parse().unwrap_or_default();
+        let full_year = if year <= 30 { 2000 + year } else { 1900 + year };
+        let new_format = format!("{}{}0{}{}", full_year, &cleaned[2..5], &cleaned[5..8], &cleaned[8..9]);
+
+        return Ok(Nic { new_format, old_format: Some(cleaned) });
+    }
+
+    if is_new_format(&cleaned) {
+        // Only NICs with a zero-padded serial have an old equivalent
+        let old_format = (cleaned.as_bytes()[7] == b'0')
+            .then(|| format!("{}{}{}{}V", &cleaned[2..4], &cleaned[4..7], &cleaned[8..11], &cleaned[11..12]));
+
+        return Ok(Nic { new_format: cleaned, old_format });
+    }
+
+    Err(format!("Invalid NIC number \"{}\"", nic.trim()))
+}
+
+/// Gender encoded in a new format NIC.
+pub fn nic_gender(nic: &Nic) -> &'static str {
+    let day: u32 = nic.new_format[4..7].parse().unwrap_or_default();
+    if day > 500 { "Female" } else { "Male" }
+}
+
+/// Date of birth encoded in a new format NIC. The day count treats every
+/// year as a leap year, as the NIC numbering does.
+pub fn nic_birth_date(nic: &Nic) -> Option<NaiveDate> {
+    let year: i32 = nic.new_format[..4].parse().ok()?;
+    let mut day: u32 = nic.new_format[4..7].parse().ok()?;
+    if day > 500 {
+        day -= 500;
+    }
+    if !(1..=366).contains(&day) {
+        return None;
+    }
+
+    // Day 60 is always 29 February; non-leap years skip it
+    let leap = NaiveDate::from_ymd_opt(2000, 1, 1)?.with_ordinal(day)?;
+    match (leap.month(), leap.day()) {
+        (2, 29) if NaiveDate::from_ymd_opt(year, 2, 29).is_none() => None,
+        (month, day) => NaiveDate::from_ymd_opt(year, month, day),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_format_converts_to_new() {
+        let nic = normalize_nic("853400937v").unwrap();
+        assert_eq!(nic.new_format, "198534000937");
+        assert_eq!(nic.old_format.as_deref(), Some("853400937V"));
+
+        let nic = normalize_nic("853400937x").unwrap();
+        assert_eq!(nic.old_format.as_deref(), Some("853400937X"));
+    }
+
+    #[test]
+    fn new_format_keeps_an_old_form_only_with_a_zero_padded_serial() {
+        let nic = normalize_nic("198534000937").unwrap();
+        assert_eq!(nic.new_format, "198534000937");
+        assert_eq!(nic.old_format.as_deref(), Some("853400937V"));
+
+        assert_eq!(normalize_nic("198534010937").unwrap().old_format, None);
+    }
+
+    #[test]
+    fn spaces_and_dashes_are_ignored() {
+        assert_eq!(normalize_nic(" 8534-00937 V ").unwrap().new_format, "198534000937");
+        assert_eq!(normalize_nic("1985 3400 0937\t").unwrap().new_format, "198534000937");
+    }
+
+    #[test]
+    fn malformed_numbers_are_refused_without_panicking() {
+        for nic in ["", "12345678é", "12345678éV", "١٢٣٤٥٦٧٨٩V", "853400937", "853400937A", "19853400093", "1985340009377", "19853400093X"] {
+            assert!(normalize_nic(nic).is_err(), "{} was accepted", nic);
+        }
+    }
+
+    #[test]
+    fn gender_and_birth_date_come_from_the_day_count() {
+        let male = normalize_nic("198534000937").unwrap();
+        assert_eq!(nic_gender(&male), "Male");
+        assert_eq!(nic_birth_date(&male), NaiveDate::from_ymd_opt(1985, 12, 5));
+
+        let female = normalize_nic("199086000937").unwrap();
+        assert_eq!(nic_gender(&female), "Female");
+        assert_eq!(nic_birth_date(&female), NaiveDate::from_ymd_opt(1990, 12, 25));
+
+        // Day 60 is 29 February, which 1985 didn't have
+        assert_eq!(nic_birth_date(&normalize_nic("198506000937").unwrap()), None);
+    }
+}
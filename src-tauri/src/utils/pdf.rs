@@ -0,0 +1,452 @@
+//! Thin layout layer over printpdf used by the report commands.
+//!
+//! Coordinates are millimetres measured from the top-left corner of the page
+//! (printpdf itself works bottom-up). Text is set in DejaVu Serif, embedded in
+//! every document so output is identical whatever fonts the machine has.
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use printpdf::path::PaintMode;
+use printpdf::{
+    Color, ColorBits, ColorSpace, Greyscale, Image, ImageTransform, ImageXObject, IndirectFontRef, Line,
+    Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point, Px, Rect,
+};
+use serde::Deserialize;
+use ttf_parser::Face;
+
+static REGULAR_FONT: &[u8] = include_bytes!("../../fonts/DejaVuSerif.ttf");
+static BOLD_FONT: &[u8] = include_bytes!("../../fonts/DejaVuSerif-Bold.ttf");
+
+const A4_SHORT: f32 = 210.0;
+const A4_LONG: f32 = 297.0;
+const PT_TO_MM: f32 = 25.4 / 72.0;
+const LINE_SPACING: f32 = 1.25;
+// Resolution images are placed at before scaling to their box
+const IMAGE_DPI: f32 = 300.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weight {
+    Regular,
+    Bold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// A table column; `width` is a fraction of the content width.
+pub struct Column<'a> {
+    pub title: &'a str,
+    pub width: f32,
+    pub align: Align,
+}
+
+pub struct PdfReport {
+    doc: PdfDocumentReference,
+    pages: Vec<PdfLayerReference>,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    regular_face: Face<'static>,
+    bold_face: Face<'static>,
+    page_width: f32,
+    page_height: f32,
+    margin: f32,
+    footer: Vec<String>,
+    /// Current vertical position, from the top of the page.
+    pub y: f32,
+}
+
+impl PdfReport {
+    pub fn new(title: &str, orientation: Orientation, margin: f32) -> Result<Self, String> {
+        let (page_width, page_height) = match orientation {
+            Orientation::Portrait => (A4_SHORT, A4_LONG),
+            Orientation::Landscape => (A4_LONG, A4_SHORT),
+        };
+
+        let (doc, page, layer) = PdfDocument::new(title, Mm(page_width), Mm(page_height), "Content");
+        let regular = doc
+            .add_external_font(REGULAR_FONT)
+            .map_err(|e| format!("Failed to embed font: {}", e))?;
+        let bold = doc
+            .add_external_font(BOLD_FONT)
+            .map_err(|e| format!("Failed to embed font: {}", e))?;
+        let first_page = doc.get_page(page).get_layer(layer);
+
+        Ok(Self {
+            doc,
+            pages: vec![first_page],
+            regular,
+            bold,
+            regular_face: parse_face(REGULAR_FONT)?,
+            bold_face: parse_face(BOLD_FONT)?,
+            page_width,
+            page_height,
+            margin,
+            footer: Vec::new(),
+            y: margin,
+        })
+    }
+
+    /// Lines printed at the bottom of every page, above the page number.
+    pub fn set_footer(&mut self, lines: Vec<String>) {
+        self.footer = lines;
+    }
+
+    pub fn left(&self) -> f32 {
+        self.margin
+    }
+
+    pub fn content_width(&self) -> f32 {
+        self.page_width - 2.0 * self.margin
+    }
+
+    /// Space left above the footer on the current page.
+    pub fn remaining(&self) -> f32 {
+        self.content_bottom() - self.y
+    }
+
+    fn content_bottom(&self) -> f32 {
+        let footer_lines = self.footer.len() as f32 + 1.0;
+        self.page_height - self.margin - footer_lines * line_height(7.0) - 2.0
+    }
+
+    fn layer(&self) -> &PdfLayerReference {
+        self.pages.last().expect("a report always has a page")
+    }
+
+    pub fn new_page(&mut self) {
+        let (page, layer) = self.doc.add_page(Mm(self.page_width), Mm(self.page_height), "Content");
+        self.pages.push(self.doc.get_page(page).get_layer(layer));
+        self.y = self.margin;
+    }
+
+    /// Start a new page unless `height` still fits on this one.
+    pub fn ensure_space(&mut self, height: f32) {
+        if self.remaining() < height {
+            self.new_page();
+        }
+    }
+
+    fn face(&self, weight: Weight) -> &Face<'static> {
+        match weight {
+            Weight::Regular => &self.regular_face,
+            Weight::Bold => &self.bold_face,
+        }
+    }
+
+    fn font(&self, weight: Weight) -> &IndirectFontRef {
+        match weight {
+            Weight::Regular => &self.regular,
+            Weight::Bold => &self.bold,
+        }
+    }
+
+    pub fn text_width(&self, text: &str, size: f32, weight: Weight) -> f32 {
+        let face = self.face(weight);
+        let units: u32 = text
+            .chars()
+            .map(|c| {
+                let glyph = face.glyph_index(c).unwrap_or_default();
+                face.glyph_hor_advance(glyph).unwrap_or(0) as u32
+            })
+            .sum();
+
+        units as f32 / face.units_per_em() as f32 * size * PT_TO_MM
+    }
+
+    /// Draw a single line of text with its top edge at `y`.
+    pub fn text(&self, text: &str, x: f32, y: f32, size: f32, weight: Weight) {
+        let baseline = y + size * PT_TO_MM * 0.9;
+        self.layer().use_text(text, size, Mm(x), Mm(self.page_height - baseline), self.font(weight));
+    }
+
+    /// Draw a single line aligned inside a box of `width` starting at `x`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn text_in(&self, text: &str, x: f32, width: f32, y: f32, size: f32, weight: Weight, align: Align) {
+        let x = match align {
+            Align::Left => x,
+            Align::Center => x + (width - self.text_width(text, size, weight)) / 2.0,
+            Align::Right => x + width - self.text_width(text, size, weight),
+        };
+        self.text(text, x, y, size, weight);
+    }
+
+    /// Draw a centred line across the content width and move the cursor below it.
+    pub fn centered_line(&mut self, text: &str, size: f32, weight: Weight) {
+        self.text_in(text, self.left(), self.content_width(), self.y, size, weight, Align::Center);
+        self.y += line_height(size);
+    }
+
+    /// Break `text` into lines no wider than `width`, splitting long words if needed.
+    pub fn wrap(&self, text: &str, width: f32, size: f32, weight: Weight) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for paragraph in text.lines() {
+            let mut line = String::new();
+
+            for word in paragraph.split_whitespace() {
+                let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+                if self.text_width(&candidate, size, weight) <= width {
+                    line = candidate;
+                    continue;
+                }
+
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+
+                // A single word wider than the box is split by character
+                for c in word.chars() {
+                    let candidate = format!("{}{}", line, c);
+                    if !line.is_empty() && self.text_width(&candidate, size, weight) > width {
+                        lines.push(std::mem::replace(&mut line, c.to_string()));
+                    } else {
+                        line = candidate;
+                    }
+                }
+            }
+
+            lines.push(line);
+        }
+
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+
+        lines
+    }
+
+    pub fn line(&self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32) {
+        let layer = self.layer();
+        layer.set_outline_thickness(thickness);
+        layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(x1), Mm(self.page_height - y1)), false),
+                (Point::new(Mm(x2), Mm(self.page_height - y2)), false),
+            ],
+            is_closed: false,
+        });
+    }
+
+    /// Horizontal rule across the content width at the cursor.
+    pub fn rule(&self, thickness: f32) {
+        self.line(self.left(), self.y, self.left() + self.content_width(), self.y, thickness);
+    }
+
+    /// Outline a box, optionally filled with a grey level (0 black, 1 white).
+    pub fn rect(&self, x: f32, y: f32, width: f32, height: f32, fill: Option<f32>) {
+        let layer = self.layer();
+        let rect = Rect::new(
+            Mm(x),
+            Mm(self.page_height - y - height),
+            Mm(x + width),
+            Mm(self.page_height - y),
+        );
+
+        if let Some(grey) = fill {
+            layer.set_fill_color(Color::Greyscale(Greyscale::new(grey, None)));
+            layer.add_rect(rect.with_mode(PaintMode::FillStroke));
+            layer.set_fill_color(Color::Greyscale(Greyscale::new(0.0, None)));
+        } else {
+            layer.set_outline_thickness(0.3);
+            layer.add_rect(rect.with_mode(PaintMode::Stroke));
+        }
+    }
+
+    /// Place a base64 encoded image, stretched to the given box.
+    pub fn image(&self, image_data: &str, x: f32, y: f32, width: f32, height: f32) -> Result<(), String> {
+        let bytes = BASE64
+            .decode(image_data)
+            .map_err(|e| format!("Invalid base64 image data: {}", e))?;
+        let decoded = image::load_from_memory(&bytes)
+            .map_err(|e| format!("Failed to decode image: {}", e))?
+            .to_rgb8();
+        let (pixel_width, pixel_height) = decoded.dimensions();
+
+        let xobject = ImageXObject {
+            width: Px(pixel_width as usize),
+            height: Px(pixel_height as usize),
+            color_space: ColorSpace::Rgb,
+            bits_per_component: ColorBits::Bit8,
+            interpolate: true,
+            image_data: decoded.into_raw(),
+            image_filter: None,
+            smask: None,
+            clipping_bbox: None,
+        };
+
+        let natural_width = pixel_width as f32 / IMAGE_DPI * 25.4;
+        let natural_height = pixel_height as f32 / IMAGE_DPI * 25.4;
+
+        Image::from(xobject).add_to_layer(
+            self.layer().clone(),
+            ImageTransform {
+                translate_x: Some(Mm(x)),
+                translate_y: Some(Mm(self.page_height - y - height)),
+                scale_x: Some(width / natural_width),
+                scale_y: Some(height / natural_height),
+                dpi: Some(IMAGE_DPI),
+                ..Default::default()
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Draw a bordered table at the cursor, repeating the header row after each page break.
+    pub fn table(&mut self, columns: &[Column], rows: &[Vec<String>], size: f32) {
+        const PADDING: f32 = 1.2;
+        let widths: Vec<f32> = columns.iter().map(|c| c.width * self.content_width()).collect();
+        let header_lines: Vec<Vec<String>> = columns
+            .iter()
+            .zip(&widths)
+            .map(|(column, width)| self.wrap(column.title, width - 2.0 * PADDING, size, Weight::Bold))
+            .collect();
+        let header_height = row_height(&header_lines, size, PADDING);
+
+        let draw_header = |report: &mut PdfReport| {
+            let mut x = report.left();
+            for (lines, width) in header_lines.iter().zip(&widths) {
+                report.rect(x, report.y, *width, header_height, Some(0.88));
+                for (i, line) in lines.iter().enumerate() {
+                    let y = report.y + PADDING + i as f32 * line_height(size);
+                    report.text_in(line, x + PADDING, width - 2.0 * PADDING, y, size, Weight::Bold, Align::Center);
+                }
+                x += width;
+            }
+            report.y += header_height;
+        };
+
+        self.ensure_space(header_height + line_height(size) + 2.0 * PADDING);
+        draw_header(self);
+
+        for row in rows {
+            let cells: Vec<Vec<String>> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| self.wrap(cell, width - 2.0 * PADDING, size, Weight::Regular))
+                .collect();
+            let height = row_height(&cells, size, PADDING);
+
+            if self.remaining() < height {
+                self.new_page();
+                draw_header(self);
+            }
+
+            let mut x = self.left();
+            for ((lines, width), column) in cells.iter().zip(&widths).zip(columns) {
+                self.rect(x, self.y, *width, height, None);
+                for (i, line) in lines.iter().enumerate() {
+                    let y = self.y + PADDING + i as f32 * line_height(size);
+                    self.text_in(line, x + PADDING, width - 2.0 * PADDING, y, size, Weight::Regular, column.align);
+                }
+                x += width;
+            }
+            self.y += height;
+        }
+    }
+
+    /// Write the footer and page numbers onto every page and serialise the document.
+    pub fn finish(self) -> Result<Vec<u8>, String> {
+        let page_count = self.pages.len();
+        let size = 7.0;
+
+        for (index, layer) in self.pages.iter().enumerate() {
+            let mut y = self.page_height - self.margin - (self.footer.len() as f32 + 1.0) * line_height(size);
+            layer.set_outline_thickness(0.3);
+            layer.add_line(Line {
+                points: vec![
+                    (Point::new(Mm(self.margin), Mm(self.page_height - y + 1.0)), false),
+                    (Point::new(Mm(self.page_width - self.margin), Mm(self.page_height - y + 1.0)), false),
+                ],
+                is_closed: false,
+            });
+
+            let page_number = format!("Page {} of {}", index + 1, page_count);
+            for line in self.footer.iter().chain(std::iter::once(&page_number)) {
+                let x = (self.page_width - self.text_width(line, size, Weight::Regular)) / 2.0;
+                let baseline = y + size * PT_TO_MM * 0.9;
+                layer.use_text(line, size, Mm(x), Mm(self.page_height - baseline), &self.regular);
+                y += line_height(size);
+            }
+        }
+
+        self.doc
+            .save_to_bytes()
+            .map_err(|e| format!("Failed to write PDF: {}", e))
+    }
+}
+
+/// Height of one line of text at `size` points, in millimetres.
+pub fn line_height(size: f32) -> f32 {
+    size * PT_TO_MM * LINE_SPACING
+}
+
+fn row_height(cells: &[Vec<String>], size: f32, padding: f32) -> f32 {
+    let lines = cells.iter().map(Vec::len).max().unwrap_or(1).max(1);
+    lines as f32 * line_height(size) + 2.0 * padding
+}
+
+fn parse_face(data: &'static [u8]) -> Result<Face<'static>, String> {
+    Face::parse(data, 0).map_err(|e| format!("Failed to load font: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use printpdf::lopdf;
+
+    fn page_count(bytes: &[u8]) -> usize {
+        lopdf::Document::load_mem(bytes).expect("output is not a readable PDF").get_pages().len()
+    }
+
+    #[test]
+    fn a_long_table_breaks_across_pages() {
+        let mut report = PdfReport::new("Table", Orientation::Portrait, 15.0).unwrap();
+        let columns = [
+            Column { title: "No.", width: 0.1, align: Align::Right },
+            Column { title: "Name", width: 0.9, align: Align::Left },
+        ];
+        let rows: Vec<Vec<String>> = (1..=150).map(|n| vec![n.to_string(), format!("Person {} Kumar", n)]).collect();
+        report.table(&columns, &rows, 8.0);
+
+        let pages = report.pages.len();
+        assert!(pages > 2, "150 rows fit on {} pages", pages);
+        assert_eq!(page_count(&report.finish().unwrap()), pages);
+    }
+
+    #[test]
+    fn non_latin_text_is_set_in_the_embedded_font() {
+        let mut report = PdfReport::new("Names", Orientation::Landscape, 15.0).unwrap();
+        let name = "Ελένη Παπαδοπούλου, Наталья Иванова";
+        for c in name.chars().filter(|c| !c.is_whitespace()) {
+            assert!(report.regular_face.glyph_index(c).is_some(), "no glyph for {}", c);
+            assert!(report.bold_face.glyph_index(c).is_some(), "no bold glyph for {}", c);
+        }
+        report.centered_line(name, 12.0, Weight::Bold);
+        report.text(name, report.left(), report.y, 10.0, Weight::Regular);
+
+        assert_eq!(page_count(&report.finish().unwrap()), 1);
+    }
+
+    #[test]
+    fn wrapped_lines_fit_their_width() {
+        let report = PdfReport::new("Wrap", Orientation::Portrait, 15.0).unwrap();
+        let text = "12, Station Road, Vavuniya\nAVeryLongWordWithoutAnyBreaksThatCannotFitOnOneLine";
+        let lines = report.wrap(text, 30.0, 10.0, Weight::Regular);
+
+        assert!(lines.len() > 3, "{:?}", lines);
+        assert!(lines.iter().all(|line| report.text_width(line, 10.0, Weight::Regular) <= 30.0), "{:?}", lines);
+        assert_eq!(lines.concat().replace(' ', ""), text.replace([' ', '\n'], ""));
+        assert_eq!(report.wrap("", 30.0, 10.0, Weight::Regular), vec![String::new()]);
+    }
+}
@@ -0,0 +1,241 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits, Rgba, RgbaImage};
+use std::io::Cursor;
+
+/// Standard passport photo size (matches `IMAGE_CONSTRAINTS` in the frontend).
+pub const PHOTO_WIDTH: u32 = 240;
+pub const PHOTO_HEIGHT: u32 = 320;
+const PHOTO_QUALITY: u8 = 85;
+
+/// Largest upload accepted, measured on the decoded file bytes.
+pub const MAX_PHOTO_BYTES: usize = 5 * 1024 * 1024;
+
+/// Upper bound on source dimensions so a tiny file can't expand into a huge bitmap.
+const MAX_PHOTO_DIMENSION: u32 = 8000;
+
+/// Bounding box for list-view thumbnails (same 3:4 ratio as the passport photo).
+pub const THUMBNAIL_WIDTH: u32 = 60;
+pub const THUMBNAIL_HEIGHT: u32 = 80;
+const THUMBNAIL_QUALITY: u8 = 70;
+
+/// Side of the square the office logo is stored on.
+pub const LOGO_SIZE: u32 = 300;
+
+/// Validate an uploaded photo and normalise it to the stored format.
+///
+/// Accepts plain base64 or a `data:` URL holding a JPEG, PNG or WebP image.
+/// The image is decoded, rotated according to its EXIF orientation, cropped
+/// and resized to the passport dimensions and re-encoded as a JPEG. The
+/// re-encode drops all metadata, including any GPS location.
+pub fn process_photo(image_data: &str) -> Result<String, String> {
+    let image = decode_upload(image_data)?;
+
+    let photo = image
+        .resize_to_fill(PHOTO_WIDTH, PHOTO_HEIGHT, FilterType::Lanczos3)
+        .to_rgb8();
+
+    let mut buffer = Cursor::new(Vec::new());
+    JpegEncoder::new_with_quality(&mut buffer, PHOTO_QUALITY)
+        .encode_image(&photo)
+        .map_err(|e| format!("Failed to encode photo: {}", e))?;
+
+    Ok(BASE64.encode(buffer.into_inner()))
+}
+
+/// Validate an uploaded office logo and normalise it for the reports.
+///
+/// Takes the same input as [`process_photo`]. The logo keeps its aspect ratio,
+/// scaled to fit and centred on a white `LOGO_SIZE` square, so it can be
+/// placed in a square box without distortion; transparent areas become white.
+pub fn process_logo(image_data: &str) -> Result<String, String> {
+    let image = decode_upload(image_data)?;
+
+    let logo = image.resize(LOGO_SIZE, LOGO_SIZE, FilterType::Lanczos3).to_rgba8();
+    let mut canvas = RgbaImage::from_pixel(LOGO_SIZE, LOGO_SIZE, Rgba([255, 255, 255, 255]));
+    let x = (LOGO_SIZE - logo.width()) / 2;
+    let y = (LOGO_SIZE - logo.height()) / 2;
+    imageops::overlay(&mut canvas, &logo, x as i64, y as i64);
+
+    let mut buffer = Cursor::new(Vec::new());
+    JpegEncoder::new_with_quality(&mut buffer, PHOTO_QUALITY)
+        .encode_image(&DynamicImage::ImageRgba8(canvas).to_rgb8())
+        .map_err(|e| format!("Failed to encode logo: {}", e))?;
+
+    Ok(BASE64.encode(buffer.into_inner()))
+}
+
+/// Check the size and format of an uploaded image and decode it upright.
+fn decode_upload(image_data: &str) -> Result<DynamicImage, String> {
+    let encoded = strip_data_url(image_data);
+
+    // Reject before decoding; base64 is 4 bytes per 3
+    if encoded.len() > MAX_PHOTO_BYTES.div_ceil(3) * 4 {
+        return Err(image_too_large());
+    }
+
+    let bytes = BASE64
+        .decode(encoded)
+        .map_err(|e| format!("Invalid base64 image data: {}", e))?;
+
+    if bytes.len() > MAX_PHOTO_BYTES {
+        return Err(image_too_large());
+    }
+
+    let mut reader = ImageReader::new(Cursor::new(bytes.as_slice()))
+        .with_guessed_format()
+        .map_err(|e| format!("Failed to read image: {}", e))?;
+
+    match reader.format() {
+        Some(ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP) => {}
+        _ => return Err("Unsupported image format. Please upload a JPEG, PNG or WebP image".to_string()),
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_PHOTO_DIMENSION);
+    limits.max_image_height = Some(MAX_PHOTO_DIMENSION);
+    reader.limits(limits);
+
+    let mut decoder = reader
+        .into_decoder()
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+    let orientation = decoder
+        .orientation()
+        .map_err(|e| format!("Failed to read image orientation: {}", e))?;
+
+    let mut image = DynamicImage::from_decoder(decoder)
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+    image.apply_orientation(orientation);
+
+    Ok(image)
+}
+
+/// Build a small base64 JPEG thumbnail from a base64 encoded photo. Stored
+/// photos can come from imports and restores, so they get the same checks as
+/// an upload.
+pub fn make_thumbnail(image_data: &str) -> Result<String, String> {
+    let image = decode_upload(image_data)?;
+
+    let thumbnail = image.thumbnail(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT).to_rgb8();
+
+    let mut buffer = Cursor::new(Vec::new());
+    JpegEncoder::new_with_quality(&mut buffer, THUMBNAIL_QUALITY)
+        .encode_image(&thumbnail)
+        .map_err(|e| format!("Failed to encode thumbnail: {}", e))?;
+
+    Ok(BASE64.encode(buffer.into_inner()))
+}
+
+/// Drop a leading `data:image/...;base64,` prefix if the frontend sent one.
+fn strip_data_url(image_data: &str) -> &str {
+    let trimmed = image_data.trim();
+    match trimmed.strip_prefix("data:") {
+        Some(rest) => rest.split_once(',').map(|(_, data)| data).unwrap_or(rest),
+        None => trimmed,
+    }
+}
+
+fn image_too_large() -> String {
+    format!("Image is too large. Maximum size is {} MB", MAX_PHOTO_BYTES / (1024 * 1024))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::png::PngEncoder;
+    use image::{ImageEncoder, Rgb, RgbImage};
+
+    const RED: Rgb<u8> = Rgb([255, 0, 0]);
+    const BLUE: Rgb<u8> = Rgb([0, 0, 255]);
+
+    /// Red on the left half, blue on the right.
+    fn landscape() -> RgbImage {
+        RgbImage::from_fn(400, 300, |x, _| if x < 200 { RED } else { BLUE })
+    }
+
+    fn jpeg(image: &RgbImage) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        JpegEncoder::new_with_quality(&mut buffer, 95).encode_image(image).unwrap();
+        buffer
+    }
+
+    /// `jpeg` with an EXIF block holding `orientation` and a GPS version tag.
+    fn jpeg_with_exif(image: &RgbImage, orientation: u16) -> Vec<u8> {
+        let mut tiff = b"II*\0\x08\0\0\0".to_vec();
+        // IFD0: orientation, then a pointer to the GPS IFD at offset 38
+        tiff.extend_from_slice(&[2, 0]);
+        tiff.extend_from_slice(&[0x12, 0x01, 3, 0, 1, 0, 0, 0]);
+        tiff.extend_from_slice(&orientation.to_le_bytes());
+        tiff.extend_from_slice(&[0, 0, 0x25, 0x88, 4, 0, 1, 0, 0, 0, 38, 0, 0, 0, 0, 0, 0, 0]);
+        tiff.extend_from_slice(&[1, 0, 0, 0, 1, 0, 4, 0, 0, 0, 2, 2, 0, 0, 0, 0, 0, 0]);
+
+        let mut segment = b"Exif\0\0".to_vec();
+        segment.extend_from_slice(&tiff);
+        let mut app1 = vec![0xFF, 0xE1];
+        app1.extend_from_slice(&(segment.len() as u16 + 2).to_be_bytes());
+        app1.extend_from_slice(&segment);
+
+        let mut bytes = jpeg(image);
+        bytes.splice(2..2, app1);
+        bytes
+    }
+
+    fn decode(encoded: &str) -> RgbImage {
+        image::load_from_memory(&BASE64.decode(encoded).unwrap()).unwrap().to_rgb8()
+    }
+
+    fn is_red(pixel: &Rgb<u8>) -> bool {
+        pixel[0] > 200 && pixel[2] < 60
+    }
+
+    #[test]
+    fn photos_are_cropped_to_passport_size() {
+        let photo = decode(&process_photo(&BASE64.encode(jpeg(&landscape()))).unwrap());
+        assert_eq!(photo.dimensions(), (PHOTO_WIDTH, PHOTO_HEIGHT));
+
+        let data_url = format!("data:image/jpeg;base64,{}", BASE64.encode(jpeg(&landscape())));
+        assert_eq!(decode(&process_photo(&data_url).unwrap()).dimensions(), (PHOTO_WIDTH, PHOTO_HEIGHT));
+    }
+
+    #[test]
+    fn exif_orientation_is_applied_and_metadata_dropped() {
+        let upload = jpeg_with_exif(&landscape(), 6);
+        assert!(upload.windows(4).any(|bytes| bytes == b"Exif"));
+
+        let stored = BASE64.decode(process_photo(&BASE64.encode(upload)).unwrap()).unwrap();
+        assert!(!stored.windows(4).any(|bytes| bytes == b"Exif"));
+        assert!(!stored.windows(2).any(|bytes| bytes == [0xFF, 0xE1]));
+
+        // Turned a quarter clockwise, the red left half ends up on top
+        let photo = image::load_from_memory(&stored).unwrap().to_rgb8();
+        assert!(is_red(photo.get_pixel(200, 40)));
+        assert!(!is_red(photo.get_pixel(40, 280)));
+    }
+
+    #[test]
+    fn other_files_and_oversize_images_are_refused() {
+        let error = process_photo(&BASE64.encode(b"%PDF-1.7 not an image")).unwrap_err();
+        assert!(error.starts_with("Unsupported image format"), "{}", error);
+        assert!(process_photo("not base64!").unwrap_err().starts_with("Invalid base64"));
+
+        let too_many_bytes = "A".repeat(MAX_PHOTO_BYTES / 3 * 4 + 8);
+        assert_eq!(process_photo(&too_many_bytes).unwrap_err(), image_too_large());
+
+        // A few hundred bytes that would decode to a very wide bitmap
+        let mut png = Vec::new();
+        let pixels = vec![0u8; MAX_PHOTO_DIMENSION as usize + 1];
+        PngEncoder::new(&mut png)
+            .write_image(&pixels, MAX_PHOTO_DIMENSION + 1, 1, image::ExtendedColorType::L8)
+            .unwrap();
+        let wide = BASE64.encode(png);
+        assert!(process_photo(&wide).unwrap_err().starts_with("Failed to decode image"));
+        assert!(make_thumbnail(&wide).is_err());
+    }
+
+    #[test]
+    fn thumbnails_fit_the_list_view() {
+        let photo = process_photo(&BASE64.encode(jpeg(&landscape()))).unwrap();
+        assert_eq!(decode(&make_thumbnail(&photo).unwrap()).dimensions(), (THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT));
+    }
+}
@@ -1,13 +1,44 @@
 // import React, { useState } from 'react';
 import { Layout } from './components/layout/Layout';
+import { Login } from './components/layout/Login';
+import { SessionLocked } from './components/layout/SessionLocked';
+import { UnlockDatabase } from './components/layout/UnlockDatabase';
 import { Dashboard } from './pages/Dashboard';
+import { useAuthStatus, useSessionLock } from './hooks/useAuth';
+import { useEncryptionStatus } from './hooks/useEncryption';
 
 function App() {
+  const { data: encryption, isLoading } = useEncryptionStatus();
+  const locked = !!encryption && !encryption.unlocked;
+  // Accounts live in the database, so they can only be checked once it is unlocked
+  const { data: auth, isLoading: isLoadingAuth } = useAuthStatus({ enabled: !isLoading && !locked });
+  useSessionLock(!!auth?.user && !auth.locked);
+
+  // Wait until we know whether the database needs a passphrase
+  if (isLoading) {
+    return null;
+  }
+
+  if (locked) {
+    return <UnlockDatabase />;
+  }
+
+  if (isLoadingAuth) {
+    return null;
+  }
+
+  if (!auth?.user) {
+    return <Login hasUsers={auth?.hasUsers ?? true} />;
+  }
+
   return (
-    <Layout>
-      <Dashboard />
-    </Layout>
+    <>
+      <Layout>
+        <Dashboard />
+      </Layout>
+      {auth.locked && <SessionLocked user={auth.user} />}
+    </>
   );
 }
 
-export default App;
\ No newline at end of file
+export default App;
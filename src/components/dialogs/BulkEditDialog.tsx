@@ -0,0 +1,248 @@
+import { useEffect, useState } from 'react';
+import { Eye, Save, X } from 'lucide-react';
+import { Button } from '../ui/button';
+import { Input } from '../ui/input';
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '../ui/select';
+import { Checkbox } from '../ui/checkbox';
+import { Dialog, DialogContent, DialogHeader, DialogTitle } from '../ui/dialog';
+import { useBulkUpdateStaff, usePreviewBulkUpdate } from '../../hooks/useStaffMutations';
+import { DESIGNATIONS, SALARY_CODES } from '../../types/staff';
+import type { StaffBulkChanges, StaffBulkPreview } from '../../types/staff';
+
+interface BulkEditDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+  staffIds: string[];
+  onStaffUpdated: () => void;
+}
+
+type BulkField = keyof StaffBulkChanges;
+
+const FIELD_LABELS: Record<BulkField, string> = {
+  designation: 'Designation',
+  salaryCode: 'Salary Code',
+  incrementDate: 'Increment Date',
+  addressLine1: 'Address Line 1',
+  addressLine2: 'Address Line 2',
+  addressLine3: 'Address Line 3',
+};
+
+// Changes come back named after their columns
+const COLUMN_LABELS: Record<string, string> = {
+  designation: 'Designation',
+  salary_code: 'Salary Code',
+  increment_date: 'Increment Date',
+  address_line1: 'Address Line 1',
+  address_line2: 'Address Line 2',
+  address_line3: 'Address Line 3',
+};
+
+const formatValue = (value: unknown) => (value === null || value === undefined || value === '' ? '—' : String(value));
+
+export function BulkEditDialog({ isOpen, onClose, staffIds, onStaffUpdated }: BulkEditDialogProps) {
+  const previewBulkUpdate = usePreviewBulkUpdate();
+  const bulkUpdate = useBulkUpdateStaff();
+
+  // Only ticked fields are changed; a ticked optional field left blank is cleared
+  const [enabled, setEnabled] = useState<Set<BulkField>>(new Set());
+  const [values, setValues] = useState<Record<BulkField, string>>({
+    designation: '',
+    salaryCode: '',
+    incrementDate: '',
+    addressLine1: '',
+    addressLine2: '',
+    addressLine3: '',
+  });
+  const [preview, setPreview] = useState<StaffBulkPreview | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (isOpen) {
+      setEnabled(new Set());
+      setValues({ designation: '', salaryCode: '', incrementDate: '', addressLine1: '', addressLine2: '', addressLine3: '' });
+      setPreview(null);
+      setError(null);
+    }
+  }, [isOpen]);
+
+  const toggleField = (field: BulkField, checked: boolean) => {
+    const next = new Set(enabled);
+    if (checked) {
+      next.add(field);
+    } else {
+      next.delete(field);
+    }
+    setEnabled(next);
+    setPreview(null);
+  };
+
+  const setValue = (field: BulkField, value: string) => {
+    setValues(prev => ({ ...prev, [field]: value }));
+    setPreview(null);
+  };
+
+  const buildChanges = (): StaffBulkChanges => {
+    const changes: Record<string, string | null> = {};
+    enabled.forEach((field) => {
+      changes[field] = values[field].trim() === '' ? null : values[field];
+    });
+    return changes as StaffBulkChanges;
+  };
+
+  const handlePreview = async () => {
+    setError(null);
+    try {
+      setPreview(await previewBulkUpdate.mutateAsync({ staffIds, changes: buildChanges() }));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const handleApply = async () => {
+    if (!preview) return;
+
+    setError(null);
+    try {
+      // Only the previewed records, so nothing outside what was shown gets changed
+      await bulkUpdate.mutateAsync({
+        staffIds: preview.rows.map((row) => row.staff.id),
+        changes: buildChanges(),
+      });
+      onStaffUpdated();
+      onClose();
+    } catch (e) {
+      setError(String(e));
+      setPreview(null);
+    }
+  };
+
+  const renderInput = (field: BulkField) => {
+    const disabled = !enabled.has(field);
+
+    if (field === 'designation' || field === 'salaryCode') {
+      const options = field === 'designation' ? DESIGNATIONS : SALARY_CODES;
+      return (
+        <Select value={values[field]} onValueChange={(value) => setValue(field, value)} disabled={disabled}>
+          <SelectTrigger>
+            <SelectValue placeholder={`Select ${FIELD_LABELS[field].toLowerCase()}`} />
+          </SelectTrigger>
+          <SelectContent>
+            {options.map((option) => (
+              <SelectItem key={option} value={option}>
+                {option}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      );
+    }
+
+    return (
+      <Input
+        value={values[field]}
+        onChange={(e) => setValue(field, e.target.value)}
+        placeholder={field === 'incrementDate' ? 'DD-MM, or blank to clear' : 'Blank to clear'}
+        disabled={disabled}
+      />
+    );
+  };
+
+  const isPending = previewBulkUpdate.isPending || bulkUpdate.isPending;
+
+  return (
+    <Dialog open={isOpen} onOpenChange={onClose}>
+      <DialogContent className="max-w-4xl max-h-[95vh] overflow-auto">
+        <DialogHeader>
+          <DialogTitle>Bulk Edit - {staffIds.length} staff selected</DialogTitle>
+        </DialogHeader>
+
+        <div className="space-y-6 py-4">
+          <div className="space-y-3">
+            {(Object.keys(FIELD_LABELS) as BulkField[]).map((field) => (
+              <div key={field} className="grid grid-cols-3 items-center gap-4">
+                <label className="flex items-center space-x-2 text-sm font-medium text-slate-700">
+                  <Checkbox
+                    checked={enabled.has(field)}
+                    onCheckedChange={(checked) => toggleField(field, checked === true)}
+                  />
+                  <span>{FIELD_LABELS[field]}</span>
+                </label>
+                <div className="col-span-2">{renderInput(field)}</div>
+              </div>
+            ))}
+          </div>
+
+          {error && (
+            <div className="bg-red-50 border border-red-200 rounded-md p-3">
+              <p className="text-red-600 text-sm">{error}</p>
+            </div>
+          )}
+
+          {preview && (
+            <div className="space-y-2">
+              <p className="text-sm text-slate-600">
+                {preview.rows.length} record{preview.rows.length === 1 ? '' : 's'} will change
+                {preview.unchanged > 0 && `; ${preview.unchanged} already have these values`}
+              </p>
+              {preview.rows.length > 0 && (
+                <div className="max-h-80 overflow-auto border rounded-md">
+                  <table className="w-full text-sm">
+                    <thead className="bg-slate-50 sticky top-0">
+                      <tr>
+                        <th className="text-left px-3 py-2 font-medium text-slate-700">Staff</th>
+                        <th className="text-left px-3 py-2 font-medium text-slate-700">Changes</th>
+                      </tr>
+                    </thead>
+                    <tbody>
+                      {preview.rows.map((row) => (
+                        <tr key={row.staff.id} className="border-t">
+                          <td className="px-3 py-2 align-top">
+                            <div className="font-medium text-slate-900">{row.staff.fullName}</div>
+                            <div className="text-xs text-slate-500">{row.staff.appointmentNumber}</div>
+                          </td>
+                          <td className="px-3 py-2 space-y-1">
+                            {row.changes.map((change) => (
+                              <div key={change.field} className="text-slate-700">
+                                <span className="font-medium">{COLUMN_LABELS[change.field] ?? change.field}:</span>{' '}
+                                <span className="line-through text-slate-400">{formatValue(change.oldValue)}</span>{' '}
+                                → {formatValue(change.newValue)}
+                              </div>
+                            ))}
+                          </td>
+                        </tr>
+                      ))}
+                    </tbody>
+                  </table>
+                </div>
+              )}
+            </div>
+          )}
+        </div>
+
+        <div className="flex justify-end space-x-3 pt-4 border-t">
+          <Button type="button" variant="outline" onClick={onClose} disabled={isPending}>
+            <X className="h-4 w-4 mr-2" />
+            Cancel
+          </Button>
+          <Button
+            type="button"
+            variant="outline"
+            onClick={handlePreview}
+            disabled={isPending || enabled.size === 0}
+          >
+            <Eye className="h-4 w-4 mr-2" />
+            Preview
+          </Button>
+          <Button
+            type="button"
+            onClick={handleApply}
+            disabled={isPending || !preview || preview.rows.length === 0}
+          >
+            <Save className="h-4 w-4 mr-2" />
+            {bulkUpdate.isPending ? 'Saving...' : 'Apply Changes'}
+          </Button>
+        </div>
+      </DialogContent>
+    </Dialog>
+  );
+}
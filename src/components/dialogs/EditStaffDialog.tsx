@@ -9,10 +9,11 @@ import { DatePicker } from '../forms/DatePicker';
 import { NICInput } from '../forms/NICInput';
 import { ImageUpload } from '../forms/ImageUpload';
 import { useStaffById } from '../../hooks/useStaff';
-import { useUpdateStaff } from '../../hooks/useStaffMutations';
+import { usePatchStaff } from '../../hooks/useStaffMutations';
+import { useCurrentUser } from '../../hooks/useAuth';
 import { calculateAge, calculateRetirementDate } from '../../lib/nicConverter';
 import { DESIGNATIONS, SALARY_CODES, MARITAL_STATUSES } from '../../types/staff';
-import type { UpdateStaffRequest } from '../../types/staff';
+import type { StaffPatchRequest, UpdateStaffRequest } from '../../types/staff';
 
 interface EditStaffDialogProps {
   isOpen: boolean;
@@ -23,15 +24,18 @@ interface EditStaffDialogProps {
 
 export function EditStaffDialog({ isOpen, onClose, staffId, onStaffUpdated }: EditStaffDialogProps) {
   const { data: existingStaff, isLoading: isLoadingStaff } = useStaffById(staffId || undefined);
-  const updateStaff = useUpdateStaff();
+  const patchStaff = usePatchStaff();
+  const canEditSalary = useCurrentUser()?.role === 'admin';
 
   const [formData, setFormData] = useState<UpdateStaffRequest | null>(null);
+  // The form as loaded, so only the fields the user changed are sent
+  const [initialData, setInitialData] = useState<UpdateStaffRequest | null>(null);
   const [errors, setErrors] = useState<Record<string, string>>({});
 
   // Load existing staff data when dialog opens
   useEffect(() => {
     if (existingStaff && isOpen) {
-      setFormData({
+      const loaded: UpdateStaffRequest = {
         id: existingStaff.id,
         appointmentNumber: existingStaff.appointmentNumber,
         fullName: existingStaff.fullName,
@@ -54,7 +58,10 @@ export function EditStaffDialog({ isOpen, onClose, staffId, onStaffUpdated }: Ed
         basicSalary: existingStaff.basicSalary,
         incrementAmount: existingStaff.incrementAmount,
         imageData: existingStaff.imageData,
-      });
+        version: existingStaff.version,
+      };
+      setFormData(loaded);
+      setInitialData(loaded);
       setErrors({});
     }
   }, [existingStaff, isOpen]);
@@ -127,9 +134,11 @@ export function EditStaffDialog({ isOpen, onClose, staffId, onStaffUpdated }: Ed
     if (!formData.maritalStatus) newErrors.maritalStatus = 'Marital status is required';
     if (!formData.designation) newErrors.designation = 'Designation is required';
     if (!formData.dateOfFirstAppointment) newErrors.dateOfFirstAppointment = 'Date of first appointment is required';
-    if (!formData.salaryCode) newErrors.salaryCode = 'Salary code is required';
-    if (formData.basicSalary <= 0) newErrors.basicSalary = 'Basic salary must be greater than 0';
-    if (formData.incrementAmount < 0) newErrors.incrementAmount = 'Increment amount cannot be negative';
+    if (canEditSalary) {
+      if (!formData.salaryCode) newErrors.salaryCode = 'Salary code is required';
+      if (formData.basicSalary <= 0) newErrors.basicSalary = 'Basic salary must be greater than 0';
+      if (formData.incrementAmount < 0) newErrors.incrementAmount = 'Increment amount cannot be negative';
+    }
 
     // Email validation
     if (formData.email && !/^[^\s@]+@[^\s@]+\.[^\s@]+$/.test(formData.email)) {
@@ -153,21 +162,36 @@ export function EditStaffDialog({ isOpen, onClose, staffId, onStaffUpdated }: Ed
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
 
-    if (!formData || !validateForm()) {
+    if (!formData || !initialData || !validateForm()) {
+      return;
+    }
+
+    const patch: Record<string, unknown> = { id: formData.id, version: formData.version };
+    for (const key of Object.keys(formData) as (keyof UpdateStaffRequest)[]) {
+      if (key === 'id' || key === 'version' || formData[key] === initialData[key]) {
+        continue;
+      }
+      // A cleared optional field is sent as null so the backend clears it too
+      patch[key] = formData[key] === '' ? null : formData[key];
+    }
+
+    if (Object.keys(patch).length === 2) {
+      onClose();
       return;
     }
 
     try {
-      await updateStaff.mutateAsync(formData);
+      await patchStaff.mutateAsync(patch as StaffPatchRequest);
       onStaffUpdated();
       onClose();
     } catch (error) {
       console.error('Error updating staff:', error);
-      setErrors({ submit: 'Failed to update staff record. Please try again.' });
+      // Show the backend's reason, such as someone else having changed the record
+      setErrors({ submit: String(error) || 'Failed to update staff record. Please try again.' });
     }
   };
 
-  const isPending = updateStaff.isPending;
+  const isPending = patchStaff.isPending;
 
   if (!staffId) return null;
 
@@ -479,12 +503,16 @@ export function EditStaffDialog({ isOpen, onClose, staffId, onStaffUpdated }: Ed
                 <span className="font-semibold text-slate-800">Salary Information</span>
               </div>
 
+              {!canEditSalary && (
+                <p className="text-sm text-slate-500 mb-4">Only an administrator can change salary details.</p>
+              )}
+
               <div className="space-y-4">
                 <div>
                   <label className="block text-sm font-medium text-slate-700 mb-2">
                     Salary Code <span className="text-red-500">*</span>
                   </label>
-                  <Select value={formData.salaryCode} onValueChange={(value) => handleInputChange('salaryCode', value)}>
+                  <Select value={formData.salaryCode} onValueChange={(value) => handleInputChange('salaryCode', value)} disabled={!canEditSalary}>
                     <SelectTrigger className={errors.salaryCode ? 'border-red-500' : ''}>
                       <SelectValue placeholder="Select salary code" />
                     </SelectTrigger>
@@ -514,6 +542,7 @@ export function EditStaffDialog({ isOpen, onClose, staffId, onStaffUpdated }: Ed
                       onFocus={() => handleCurrencyFocus('basicSalary')}
                       onBlur={(e) => handleCurrencyBlur('basicSalary', e.target.value)}
                       onChange={(e) => handleInputChange('basicSalary', e.target.value)}
+                      disabled={!canEditSalary}
                       placeholder="Enter basic salary"
                       className={errors.basicSalary ? 'border-red-500' : ''}
                     />
@@ -534,6 +563,7 @@ export function EditStaffDialog({ isOpen, onClose, staffId, onStaffUpdated }: Ed
                       onFocus={() => handleCurrencyFocus('incrementAmount')}
                       onBlur={(e) => handleCurrencyBlur('incrementAmount', e.target.value)}
                       onChange={(e) => handleInputChange('incrementAmount', e.target.value)}
+                      disabled={!canEditSalary}
                       placeholder="Enter increment amount"
                       className={errors.incrementAmount ? 'border-red-500' : ''}
                     />
@@ -2,8 +2,8 @@ import { User, Briefcase, DollarSign, Calendar, Phone, Mail, MapPin, Hash, Credi
 import { Button } from '../ui/button';
 import { Dialog, DialogContent, DialogHeader, DialogTitle } from '../ui/dialog';
 import { useStaffById } from '../../hooks/useStaff';
-import { useExportToPDF, useOpenDownloadsFolder } from '../../hooks/usePrint';
-import { formatDate, formatCurrency } from '../../lib/utils';
+import { useExportToPDF } from '../../hooks/usePrint';
+import { formatDate, formatSalary, RESTRICTED } from '../../lib/utils';
 
 interface ViewStaffDialogProps {
   isOpen: boolean;
@@ -14,7 +14,6 @@ interface ViewStaffDialogProps {
 export function ViewStaffDialog({ isOpen, onClose, staffId }: ViewStaffDialogProps) {
   const { data: staff, isLoading } = useStaffById(staffId || undefined);
   const exportToPDF = useExportToPDF();
-  const openDownloads = useOpenDownloadsFolder();
 
   const handleExportPDF = async () => {
     if (staff) {
@@ -23,13 +22,6 @@ export function ViewStaffDialog({ isOpen, onClose, staffId }: ViewStaffDialogPro
           staffIds: [staff.id],
           isBulk: false
         });
-
-        // Show option to open downloads folder after a short delay
-        setTimeout(() => {
-          if (window.confirm('PDF exported successfully! Would you like to open the Downloads folder?')) {
-            openDownloads.mutate();
-          }
-        }, 1000);
       } catch (error) {
         console.error('Export failed:', error);
       }
@@ -167,7 +159,7 @@ export function ViewStaffDialog({ isOpen, onClose, staffId }: ViewStaffDialogPro
                         {staff.addressLine2 && <div>{staff.addressLine2}</div>}
                         {staff.addressLine3 && <div>{staff.addressLine3}</div>}
                         {!staff.addressLine1 && !staff.addressLine2 && !staff.addressLine3 && (
-                          <span className="text-slate-500 italic">No address provided</span>
+                          <span className="text-slate-500 italic">{staff.masked ? RESTRICTED : 'No address provided'}</span>
                         )}
                       </div>
                     </div>
@@ -233,14 +225,14 @@ export function ViewStaffDialog({ isOpen, onClose, staffId }: ViewStaffDialogPro
                   <div>
                     <label className="text-slate-600 font-medium">Basic Salary</label>
                     <p className="text-base font-semibold text-green-600 mt-1">
-                      {formatCurrency(staff.basicSalary)}
+                      {formatSalary(staff.basicSalary, staff.masked)}
                     </p>
                   </div>
 
                   <div>
                     <label className="text-slate-600 font-medium">Increment Amount</label>
                     <p className="text-base font-semibold text-green-600 mt-1">
-                      {formatCurrency(staff.incrementAmount)}
+                      {formatSalary(staff.incrementAmount, staff.masked)}
                     </p>
                   </div>
                 </div>
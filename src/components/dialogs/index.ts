@@ -1,2 +1,2 @@
 export { ViewStaffDialog } from './ViewStaffDialog';
-export { EditStaffDialog } from './EditStaffDialog';
\ No newline at end of file
+export { EditStaffDialog } from './EditStaffDialog';export { BulkEditDialog } from './BulkEditDialog';
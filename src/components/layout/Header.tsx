@@ -1,37 +1,72 @@
+import { LogOut } from 'lucide-react';
+import { useOfficeSettings } from '../../hooks/useSettings';
+import { useCurrentUser, useLogout } from '../../hooks/useAuth';
+import { USER_ROLES } from '../../types/staff';
+
 interface HeaderProps {
   className?: string;
 }
 
 export function Header({ className }: HeaderProps) {
+  const { data: settings } = useOfficeSettings();
+  const user = useCurrentUser();
+  const logout = useLogout();
+
   return (
     <header className={`bg-gradient-to-r from-slate-800 to-slate-900 text-white shadow-lg ${className}`}>
       <div className="container mx-auto px-6 py-4">
         <div className="flex items-center justify-between">
           <div className="flex items-center space-x-4">
             <div className="flex items-center space-x-2">
-
+              {settings?.logo && (
+                <img
+                  src={`data:image/jpeg;base64,${settings.logo}`}
+                  alt="Office logo"
+                  className="h-12 w-12 rounded bg-white"
+                />
+              )}
             </div>
             <div>
               <h1 className="text-xl font-bold tracking-tight">
-                Divisional Forest Office
+                {settings?.officeName}
               </h1>
               <p className="text-sm text-slate-300">
-                Vavuniya, Sri Lanka - Staff Management System
+                {settings ? `${settings.division} - ` : ''}Staff Management System
               </p>
             </div>
           </div>
-          <div className="text-right">
-            <p className="text-sm text-slate-300">
-              {new Date().toLocaleDateString('en-GB', {
-                weekday: 'long',
-                year: 'numeric',
-                month: 'long',
-                day: 'numeric'
-              })}
-            </p>
-            <p className="text-xs text-slate-400">
-              Version 1.0.0
-            </p>
+          <div className="flex items-center space-x-6">
+            {user && (
+              <div className="flex items-center space-x-3 border-r border-slate-700 pr-6">
+                <div className="text-right">
+                  <p className="text-sm font-medium">{user.fullName}</p>
+                  <p className="text-xs text-slate-400">
+                    {USER_ROLES.find((role) => role.value === user.role)?.label}
+                  </p>
+                </div>
+                <button
+                  onClick={() => logout.mutate()}
+                  disabled={logout.isPending}
+                  className="rounded p-2 text-slate-300 hover:bg-slate-700 hover:text-white"
+                  title="Log out"
+                >
+                  <LogOut className="h-4 w-4" />
+                </button>
+              </div>
+            )}
+            <div className="text-right">
+              <p className="text-sm text-slate-300">
+                {new Date().toLocaleDateString('en-GB', {
+                  weekday: 'long',
+                  year: 'numeric',
+                  month: 'long',
+                  day: 'numeric'
+                })}
+              </p>
+              <p className="text-xs text-slate-400">
+                Version 1.0.0
+              </p>
+            </div>
           </div>
         </div>
       </div>
@@ -0,0 +1,99 @@
+import React, { useState } from 'react';
+import { LogIn, UserPlus } from 'lucide-react';
+import { Button } from '../ui/button';
+import { Input } from '../ui/input';
+import { useCreateFirstAdmin, useLogin } from '../../hooks/useAuth';
+
+interface LoginProps {
+  // False on a fresh install, where the first administrator is created instead
+  hasUsers: boolean;
+}
+
+export function Login({ hasUsers }: LoginProps) {
+  const login = useLogin();
+  const createFirstAdmin = useCreateFirstAdmin();
+  const [username, setUsername] = useState('');
+  const [fullName, setFullName] = useState('');
+  const [password, setPassword] = useState('');
+  const [confirmPassword, setConfirmPassword] = useState('');
+  const [formError, setFormError] = useState('');
+
+  const mutation = hasUsers ? login : createFirstAdmin;
+  const error = formError || (mutation.error ? String(mutation.error) : '');
+
+  const handleSubmit = (e: React.FormEvent) => {
+    e.preventDefault();
+    setFormError('');
+
+    if (hasUsers) {
+      login.mutate({ username, password }, { onError: () => setPassword('') });
+      return;
+    }
+
+    if (password !== confirmPassword) {
+      setFormError('Passwords do not match');
+      return;
+    }
+    createFirstAdmin.mutate({ username, fullName, password, role: 'admin' });
+  };
+
+  return (
+    <div className="min-h-screen flex items-center justify-center bg-gradient-to-br from-slate-50 to-slate-100">
+      <form
+        onSubmit={handleSubmit}
+        className="w-full max-w-sm space-y-4 rounded-lg bg-white p-8 shadow-lg"
+      >
+        <div className="flex items-center space-x-3">
+          {hasUsers ? <LogIn className="h-6 w-6 text-slate-700" /> : <UserPlus className="h-6 w-6 text-slate-700" />}
+          <div>
+            <h1 className="text-lg font-semibold text-slate-900">
+              {hasUsers ? 'Log In' : 'Create Administrator'}
+            </h1>
+            <p className="text-sm text-slate-500">
+              {hasUsers
+                ? 'Staff Management System'
+                : 'Set up the first account; it can add everyone else'}
+            </p>
+          </div>
+        </div>
+
+        <Input
+          autoFocus
+          value={username}
+          onChange={(e) => setUsername(e.target.value)}
+          placeholder="Username"
+        />
+
+        {!hasUsers && (
+          <Input
+            value={fullName}
+            onChange={(e) => setFullName(e.target.value)}
+            placeholder="Full name"
+          />
+        )}
+
+        <Input
+          type="password"
+          value={password}
+          onChange={(e) => setPassword(e.target.value)}
+          placeholder="Password"
+        />
+
+        {!hasUsers && (
+          <Input
+            type="password"
+            value={confirmPassword}
+            onChange={(e) => setConfirmPassword(e.target.value)}
+            placeholder="Confirm password"
+          />
+        )}
+
+        {error && <p className="text-sm text-red-600">{error}</p>}
+
+        <Button type="submit" className="w-full" disabled={!username || !password || mutation.isPending}>
+          {mutation.isPending ? 'Please wait...' : hasUsers ? 'Log In' : 'Create and Log In'}
+        </Button>
+      </form>
+    </div>
+  );
+}
@@ -0,0 +1,64 @@
+import React, { useState } from 'react';
+import { Lock } from 'lucide-react';
+import { Button } from '../ui/button';
+import { Input } from '../ui/input';
+import { useLogout, useUnlockSession } from '../../hooks/useAuth';
+import type { User } from '../../types/staff';
+
+interface SessionLockedProps {
+  user: User;
+}
+
+// Covers the app, without unmounting it, so unsaved work is still there after unlocking
+export function SessionLocked({ user }: SessionLockedProps) {
+  const unlock = useUnlockSession();
+  const logout = useLogout();
+  const [password, setPassword] = useState('');
+
+  const handleSubmit = (e: React.FormEvent) => {
+    e.preventDefault();
+    unlock.mutate(password, { onSettled: () => setPassword('') });
+  };
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-slate-900/60 backdrop-blur-sm">
+      <form
+        onSubmit={handleSubmit}
+        className="w-full max-w-sm space-y-4 rounded-lg bg-white p-8 shadow-lg"
+      >
+        <div className="flex items-center space-x-3">
+          <Lock className="h-6 w-6 text-slate-700" />
+          <div>
+            <h1 className="text-lg font-semibold text-slate-900">Session Locked</h1>
+            <p className="text-sm text-slate-500">
+              Locked after inactivity. Enter the password for {user.fullName} to continue.
+            </p>
+          </div>
+        </div>
+
+        <Input
+          autoFocus
+          type="password"
+          value={password}
+          onChange={(e) => setPassword(e.target.value)}
+          placeholder="Password"
+        />
+
+        {unlock.error && <p className="text-sm text-red-600">{String(unlock.error)}</p>}
+
+        <Button type="submit" className="w-full" disabled={!password || unlock.isPending}>
+          {unlock.isPending ? 'Please wait...' : 'Unlock'}
+        </Button>
+        <Button
+          type="button"
+          variant="outline"
+          className="w-full"
+          disabled={logout.isPending}
+          onClick={() => logout.mutate()}
+        >
+          Log Out
+        </Button>
+      </form>
+    </div>
+  );
+}
@@ -0,0 +1,53 @@
+import React, { useState } from 'react';
+import { Lock } from 'lucide-react';
+import { Button } from '../ui/button';
+import { Input } from '../ui/input';
+import { useUnlockDatabase } from '../../hooks/useEncryption';
+
+// Shown at startup in place of the app while an encrypted database is locked
+export function UnlockDatabase() {
+  const unlockDatabase = useUnlockDatabase();
+  const [passphrase, setPassphrase] = useState('');
+
+  const handleSubmit = (e: React.FormEvent) => {
+    e.preventDefault();
+    if (passphrase) {
+      unlockDatabase.mutate(passphrase, {
+        onError: () => setPassphrase(''),
+      });
+    }
+  };
+
+  return (
+    <div className="min-h-screen flex items-center justify-center bg-gradient-to-br from-slate-50 to-slate-100">
+      <form
+        onSubmit={handleSubmit}
+        className="w-full max-w-sm space-y-4 rounded-lg bg-white p-8 shadow-lg"
+      >
+        <div className="flex items-center space-x-3">
+          <Lock className="h-6 w-6 text-slate-700" />
+          <div>
+            <h1 className="text-lg font-semibold text-slate-900">Database Locked</h1>
+            <p className="text-sm text-slate-500">Enter the passphrase to open the staff database</p>
+          </div>
+        </div>
+
+        <Input
+          type="password"
+          autoFocus
+          value={passphrase}
+          onChange={(e) => setPassphrase(e.target.value)}
+          placeholder="Passphrase"
+        />
+
+        {unlockDatabase.error && (
+          <p className="text-sm text-red-600">{String(unlockDatabase.error)}</p>
+        )}
+
+        <Button type="submit" className="w-full" disabled={!passphrase || unlockDatabase.isPending}>
+          {unlockDatabase.isPending ? 'Unlocking...' : 'Unlock'}
+        </Button>
+      </form>
+    </div>
+  );
+}
@@ -2,7 +2,7 @@ import { useState, useEffect } from 'react';
 import { Printer, Download, X, FileText, Eye, ZoomIn, ZoomOut } from 'lucide-react';
 import { Button } from '../ui/button';
 import { Dialog, DialogContent, DialogHeader, DialogTitle } from '../ui/dialog';
-import { useExportToPDF, useOpenDownloadsFolder } from '../../hooks/usePrint';
+import { useExportToPDF } from '../../hooks/usePrint';
 import { invoke } from '@tauri-apps/api/core';
 
 interface ProfessionalPrintPreviewProps {
@@ -26,7 +26,6 @@ export function ProfessionalPrintPreview({
   const [error, setError] = useState<string>('');
 
   const exportToPDF = useExportToPDF();
-  const openDownloads = useOpenDownloadsFolder();
 
   // Load preview content when dialog opens
   useEffect(() => {
@@ -67,13 +66,6 @@ export function ProfessionalPrintPreview({
         staffIds,
         isBulk: isBulk || staffIds.length > 1
       });
-
-      // Show option to open downloads folder
-      setTimeout(() => {
-        if (window.confirm('PDF saved successfully! Would you like to open the Downloads folder?')) {
-          openDownloads.mutate();
-        }
-      }, 1000);
     } catch (error) {
       console.error('Export failed:', error);
     }
@@ -2,22 +2,24 @@
 import { Eye, Edit, Trash2, Users, User, Phone, Mail } from 'lucide-react';
 import { Button } from '../ui/button';
 import { Checkbox } from '../ui/checkbox';
-import { formatCurrency } from '../../lib/utils';
-import type { Staff } from '../../types/staff';
+import { formatSalary } from '../../lib/utils';
+import type { StaffSummary } from '../../types/staff';
 
 interface StaffListProps {
-  staff: Staff[];
+  staff: StaffSummary[];
+  total?: number;
   selectedStaff: Set<string>;
   onSelectStaff: (staffId: string) => void;
   onSelectAll: () => void;
   onView: (staffId: string) => void;
   onEdit: (staffId: string) => void;
-  onDelete: (staff: Staff) => void;
+  onDelete: (staff: StaffSummary) => void;
   isLoading?: boolean;
 }
 
 export function StaffList({
   staff,
+  total,
   selectedStaff,
   onSelectStaff,
   onSelectAll,
@@ -54,7 +56,7 @@ export function StaffList({
       <div className="flex items-center justify-between pb-4 border-b border-slate-200">
         <div className="flex items-center space-x-4">
           <h3 className="text-lg font-semibold text-slate-800">Staff Directory</h3>
-          <span className="text-sm text-slate-600">{staff.length} staff found</span>
+          <span className="text-sm text-slate-600">{total ?? staff.length} staff found</span>
         </div>
 
         <div className="flex items-center space-x-2">
@@ -81,9 +83,9 @@ export function StaffList({
 
               {/* Photo */}
               <div className="flex-shrink-0">
-                {member.imageData ? (
+                {member.photoThumbnail ? (
                   <img
-                    src={`data:image/jpeg;base64,${member.imageData}`}
+                    src={`data:image/jpeg;base64,${member.photoThumbnail}`}
                     alt={member.fullName}
                     className="w-16 h-20 object-cover rounded border"
                   />
@@ -114,7 +116,7 @@ export function StaffList({
                         <span className="font-medium">NIC:</span> {member.nicNumber}
                       </div>
                       <div>
-                        <span className="font-medium">Salary:</span> {formatCurrency(member.basicSalary)} ({member.salaryCode})
+                        <span className="font-medium">Salary:</span> {formatSalary(member.basicSalary, member.masked)} ({member.salaryCode})
                       </div>
                     </div>
 
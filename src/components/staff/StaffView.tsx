@@ -1,6 +1,6 @@
 // import React from 'react';
 import { User, Briefcase, DollarSign, Calendar, Phone, Mail, MapPin, Hash, CreditCard } from 'lucide-react';
-import { formatDate, formatCurrency } from '../../lib/utils';
+import { formatDate, formatSalary, RESTRICTED } from '../../lib/utils';
 import type { Staff } from '../../types/staff';
 
 interface StaffViewProps {
@@ -99,7 +99,7 @@ export function StaffView({ staff }: StaffViewProps) {
                   {staff.addressLine2 && <div>{staff.addressLine2}</div>}
                   {staff.addressLine3 && <div>{staff.addressLine3}</div>}
                   {!staff.addressLine1 && !staff.addressLine2 && !staff.addressLine3 && (
-                    <span className="text-slate-500 italic">No address provided</span>
+                    <span className="text-slate-500 italic">{staff.masked ? RESTRICTED : 'No address provided'}</span>
                   )}
                 </div>
               </div>
@@ -169,14 +169,14 @@ export function StaffView({ staff }: StaffViewProps) {
             <div>
               <label className="text-sm font-medium text-slate-600">Basic Salary</label>
               <p className="text-lg font-semibold text-green-600 mt-1">
-                {formatCurrency(staff.basicSalary)}
+                {formatSalary(staff.basicSalary, staff.masked)}
               </p>
             </div>
 
             <div>
               <label className="text-sm font-medium text-slate-600">Increment Amount</label>
               <p className="text-lg font-semibold text-green-600 mt-1">
-                {formatCurrency(staff.incrementAmount)}
+                {formatSalary(staff.incrementAmount, staff.masked)}
               </p>
             </div>
           </div>
@@ -0,0 +1,186 @@
+import { useEffect } from 'react';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { listen } from '@tauri-apps/api/event';
+import { authService } from '../lib/database';
+import type {
+  AuditLogParams,
+  AuthStatus,
+  CreateUserRequest,
+  UpdateSecuritySettingsRequest,
+  UpdateUserRequest,
+} from '../types/staff';
+
+// Tell the backend about activity in the window at most this often
+const ACTIVITY_INTERVAL_MS = 60_000;
+const ACTIVITY_EVENTS = ['mousemove', 'mousedown', 'keydown', 'wheel', 'touchstart'] as const;
+
+export function useAuthStatus({ enabled = true }: { enabled?: boolean } = {}) {
+  return useQuery({
+    queryKey: ['auth'],
+    queryFn: () => authService.getStatus(),
+    staleTime: Infinity,
+    enabled,
+  });
+}
+
+// The logged in user, once there is one
+export function useCurrentUser() {
+  return useAuthStatus().data?.user;
+}
+
+export function useLogin() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ username, password }: { username: string; password: string }) =>
+      authService.login(username, password),
+    // A wrong password should be retyped, not retried
+    retry: false,
+    onSuccess: (user) => {
+      queryClient.setQueryData(['auth'], { hasUsers: true, user, locked: false });
+      // Anything fetched before logging in was refused; load it again
+      queryClient.invalidateQueries({ predicate: (query) => query.queryKey[0] !== 'auth' });
+    },
+  });
+}
+
+export function useCreateFirstAdmin() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (data: CreateUserRequest) => authService.createFirstAdmin(data),
+    retry: false,
+    onSuccess: (user) => {
+      queryClient.setQueryData(['auth'], { hasUsers: true, user, locked: false });
+      queryClient.invalidateQueries({ predicate: (query) => query.queryKey[0] !== 'auth' });
+    },
+  });
+}
+
+// Follow the backend locking an idle session, and keep it unlocked while
+// someone is using the window
+export function useSessionLock(active: boolean) {
+  const queryClient = useQueryClient();
+
+  useEffect(() => {
+    const unlisten = listen<{ locked: boolean }>('session-lock-changed', (event) => {
+      queryClient.setQueryData<AuthStatus>(['auth'], (status) =>
+        status ? { ...status, locked: event.payload.locked } : status);
+    });
+
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, [queryClient]);
+
+  useEffect(() => {
+    if (!active) return;
+
+    let lastSent = 0;
+    const onActivity = () => {
+      const now = Date.now();
+      if (now - lastSent < ACTIVITY_INTERVAL_MS) return;
+      lastSent = now;
+      authService.touch().catch(() => undefined);
+    };
+
+    ACTIVITY_EVENTS.forEach((name) => window.addEventListener(name, onActivity, { passive: true }));
+    return () => {
+      ACTIVITY_EVENTS.forEach((name) => window.removeEventListener(name, onActivity));
+    };
+  }, [active]);
+}
+
+export function useUnlockSession() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (password: string) => authService.unlockSession(password),
+    retry: false,
+    onSuccess: (user) => {
+      queryClient.setQueryData(['auth'], { hasUsers: true, user, locked: false });
+      // Requests refused while locked can go through now
+      queryClient.invalidateQueries({ predicate: (query) => query.queryKey[0] !== 'auth' });
+    },
+  });
+}
+
+export function useLogout() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: () => authService.logout(),
+    onSuccess: () => {
+      // Drop everything the previous user could see
+      queryClient.clear();
+      queryClient.setQueryData(['auth'], { hasUsers: true, user: undefined, locked: false });
+    },
+  });
+}
+
+export function useChangePassword() {
+  return useMutation({
+    mutationFn: ({ currentPassword, newPassword }: { currentPassword: string; newPassword: string }) =>
+      authService.changePassword(currentPassword, newPassword),
+    retry: false,
+  });
+}
+
+export function useUsers() {
+  return useQuery({
+    queryKey: ['users'],
+    queryFn: () => authService.listUsers(),
+  });
+}
+
+function useUserMutation<T, R>(mutationFn: (value: T) => Promise<R>) {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn,
+    retry: false,
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['users'] });
+      // Editing your own account changes the session's copy of it
+      queryClient.invalidateQueries({ queryKey: ['auth'] });
+    },
+  });
+}
+
+export function useCreateUser() {
+  return useUserMutation((data: CreateUserRequest) => authService.createUser(data));
+}
+
+export function useUpdateUser() {
+  return useUserMutation((data: UpdateUserRequest) => authService.updateUser(data));
+}
+
+export function useResetUserPassword() {
+  return useUserMutation(({ id, newPassword }: { id: string; newPassword: string }) =>
+    authService.resetPassword(id, newPassword));
+}
+
+export function useAuditLog(params?: AuditLogParams) {
+  return useQuery({
+    queryKey: ['audit-log', params],
+    queryFn: () => authService.listAuditLog(params),
+  });
+}
+
+export function useSecuritySettings() {
+  return useQuery({
+    queryKey: ['security-settings'],
+    queryFn: () => authService.getSecuritySettings(),
+  });
+}
+
+export function useUpdateSecuritySettings() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (data: UpdateSecuritySettingsRequest) => authService.updateSecuritySettings(data),
+    onSuccess: (settings) => {
+      queryClient.setQueryData(['security-settings'], settings);
+    },
+  });
+}
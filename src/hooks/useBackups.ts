@@ -0,0 +1,66 @@
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { open } from '@tauri-apps/plugin-dialog';
+import { backupService } from '../lib/database';
+import type { UpdateBackupSettingsRequest } from '../types/staff';
+
+// Ask the user for a backup file to restore; null if they cancel
+export async function chooseBackupFile(defaultPath?: string): Promise<string | null> {
+  const path = await open({
+    multiple: false,
+    defaultPath,
+    filters: [{ name: 'Database Backups', extensions: ['db'] }],
+  });
+
+  return typeof path === 'string' ? path : null;
+}
+
+export function useBackupSettings() {
+  return useQuery({
+    queryKey: ['backups', 'settings'],
+    queryFn: () => backupService.getSettings(),
+  });
+}
+
+export function useUpdateBackupSettings() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (data: UpdateBackupSettingsRequest) => backupService.updateSettings(data),
+    onSuccess: (settings) => {
+      queryClient.setQueryData(['backups', 'settings'], settings);
+      // A new folder means a different list of backups
+      queryClient.invalidateQueries({ queryKey: ['backups', 'list'] });
+    },
+  });
+}
+
+export function useBackups() {
+  return useQuery({
+    queryKey: ['backups', 'list'],
+    queryFn: () => backupService.list(),
+  });
+}
+
+export function useCreateBackupNow() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: () => backupService.createNow(),
+    // Refresh the last backup time or error as well as the list
+    onSettled: () => {
+      queryClient.invalidateQueries({ queryKey: ['backups'] });
+    },
+  });
+}
+
+// Replace the database with a backup; everything cached is stale afterwards
+export function useRestoreBackup() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (path: string) => backupService.restore(path),
+    onSuccess: () => {
+      queryClient.invalidateQueries();
+    },
+  });
+}
@@ -0,0 +1,45 @@
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { encryptionService } from '../lib/database';
+
+export function useEncryptionStatus() {
+  return useQuery({
+    queryKey: ['encryption'],
+    queryFn: () => encryptionService.getStatus(),
+    staleTime: Infinity,
+  });
+}
+
+export function useUnlockDatabase() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (passphrase: string) => encryptionService.unlock(passphrase),
+    // A wrong passphrase should be retyped, not retried
+    retry: false,
+    onSuccess: (status) => {
+      queryClient.setQueryData(['encryption'], status);
+      // Anything fetched while locked failed; load it again
+      queryClient.invalidateQueries({ predicate: (query) => query.queryKey[0] !== 'encryption' });
+    },
+  });
+}
+
+export function useEncryptDatabase() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (passphrase: string) => encryptionService.encrypt(passphrase),
+    retry: false,
+    onSuccess: (status) => {
+      queryClient.setQueryData(['encryption'], status);
+    },
+  });
+}
+
+export function useChangeDatabasePassphrase() {
+  return useMutation({
+    mutationFn: ({ currentPassphrase, newPassphrase }: { currentPassphrase: string; newPassphrase: string }) =>
+      encryptionService.changePassphrase(currentPassphrase, newPassphrase),
+    retry: false,
+  });
+}
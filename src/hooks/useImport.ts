@@ -0,0 +1,92 @@
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { open } from '@tauri-apps/plugin-dialog';
+import { importService } from '../lib/database';
+import type { CreateImportMappingRequest, ImportAllParams, ImportStaffParams, UpdateImportMappingRequest } from '../types/staff';
+
+// Ask the user for a CSV or Excel file; null if they cancel
+export async function chooseImportFile(): Promise<string | null> {
+  const path = await open({
+    multiple: false,
+    filters: [
+      { name: 'Spreadsheets', extensions: ['csv', 'xlsx', 'xls', 'ods'] },
+      { name: 'CSV Files', extensions: ['csv'] },
+      { name: 'Excel Files', extensions: ['xlsx', 'xls'] },
+    ],
+  });
+
+  return typeof path === 'string' ? path : null;
+}
+
+// Ask the user for a database export written by export_all
+export async function chooseDatabaseExportFile(): Promise<string | null> {
+  const path = await open({
+    multiple: false,
+    filters: [{ name: 'Database Export', extensions: ['json'] }],
+  });
+
+  return typeof path === 'string' ? path : null;
+}
+
+export function useImportPreview() {
+  return useMutation({
+    mutationFn: ({ path, sheet }: { path: string; sheet?: string }) => importService.preview(path, sheet),
+  });
+}
+
+// Validate a file (dryRun) or import its valid rows; the report lists every skipped row
+export function useImportStaff() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (params: ImportStaffParams) => importService.importStaff(params),
+    onSuccess: (report) => {
+      if (report.imported > 0) {
+        queryClient.invalidateQueries({ queryKey: ['staff'] });
+      }
+    },
+  });
+}
+
+export function useImportMappings() {
+  return useQuery({
+    queryKey: ['import-mappings'],
+    queryFn: () => importService.listMappings(),
+  });
+}
+
+function useImportMappingMutation<T, R>(mutationFn: (value: T) => Promise<R>) {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn,
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['import-mappings'] });
+    },
+  });
+}
+
+export function useCreateImportMapping() {
+  return useImportMappingMutation((data: CreateImportMappingRequest) => importService.createMapping(data));
+}
+
+export function useUpdateImportMapping() {
+  return useImportMappingMutation((data: UpdateImportMappingRequest) => importService.updateMapping(data));
+}
+
+export function useDeleteImportMapping() {
+  return useImportMappingMutation((id: string) => importService.deleteMapping(id));
+}
+
+// Load a whole-database export; run with dryRun first to review the conflicts
+export function useImportAll() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (params: ImportAllParams) => importService.importAll(params),
+    onSuccess: (report) => {
+      if (!report.dryRun) {
+        queryClient.invalidateQueries();
+      }
+    },
+  });
+}
@@ -1,5 +1,8 @@
 import { useMutation } from '@tanstack/react-query';
 import { invoke } from '@tauri-apps/api/core';
+import { save } from '@tauri-apps/plugin-dialog';
+import { exportService } from '../lib/database';
+import type { ExportStaffCsvParams, ExportStaffXlsxParams } from '../types/staff';
 
 // Show notification function
 function showNotification(message: string, type: 'loading' | 'success' | 'error' = 'loading') {
@@ -33,7 +36,7 @@ function showNotification(message: string, type: 'loading' | 'success' | 'error'
             animation: spin 1s linear infinite;
           "></div>
         ` : icon}
-        ${message}
+        <span data-message></span>
       </div>
     </div>
     ${type === 'loading' ? `
@@ -45,6 +48,9 @@ function showNotification(message: string, type: 'loading' | 'success' | 'error'
     ` : ''}
   `;
 
+  // Messages carry backend errors and file paths; set them as text, never markup
+  notification.querySelector('[data-message]')!.textContent = message;
+
   document.body.appendChild(notification);
 
   // Auto-remove after delay (except for loading notifications)
@@ -67,6 +73,23 @@ function removeNotification(notification: HTMLElement) {
   }
 }
 
+// Ask where to save a PDF; resolves to null if the user cancels
+const SAVE_FORMATS = {
+  pdf: { name: 'PDF Document', extensions: ['pdf'] },
+  csv: { name: 'CSV (Comma delimited)', extensions: ['csv'] },
+  xlsx: { name: 'Excel Workbook', extensions: ['xlsx'] },
+  json: { name: 'Database Export', extensions: ['json'] },
+};
+
+async function chooseSavePath(defaultName: string, format: keyof typeof SAVE_FORMATS = 'pdf'): Promise<string | null> {
+  const timestamp = new Date().toISOString().slice(0, 10);
+  const filter = SAVE_FORMATS[format];
+  return await save({
+    defaultPath: `${defaultName}_${timestamp}.${filter.extensions[0]}`,
+    filters: [filter],
+  });
+}
+
 // Generate preview HTML for individual staff
 export function useGenerateStaffPreview() {
   return useMutation({
@@ -109,14 +132,15 @@ export function useGenerateBulkStaffPreview() {
 export function usePrintIndividual() {
   return useMutation({
     mutationFn: async (staffId: string) => {
+      const outputPath = await chooseSavePath('Staff_Record');
+      if (!outputPath) return null;
+
       const loadingNotification = showNotification('Generating PDF for staff record...', 'loading');
 
       try {
-        const result = await invoke<string>('generate_staff_pdf', { staffId });
+        const result = await invoke<string>('generate_staff_pdf', { staffId, outputPath });
         removeNotification(loadingNotification);
-
-        // Show success message with option to open Downloads folder
-        showNotification('PDF file opened in browser for printing! Click "Open Downloads" to view files.', 'success');
+        showNotification(result, 'success');
 
         return result;
       } catch (error) {
@@ -136,17 +160,18 @@ export function usePrintIndividual() {
 export function usePrintBulk() {
   return useMutation({
     mutationFn: async (staffIds: string[]) => {
+      const outputPath = await chooseSavePath('Staff_Directory');
+      if (!outputPath) return null;
+
       const loadingNotification = showNotification(
         `Generating PDF for ${staffIds.length} staff records...`,
         'loading'
       );
 
       try {
-        const result = await invoke<string>('generate_bulk_staff_pdf', { staffIds });
+        const result = await invoke<string>('generate_bulk_staff_pdf', { staffIds, outputPath });
         removeNotification(loadingNotification);
-
-        // Show success message with option to open Downloads folder
-        showNotification('Staff directory PDF opened in browser for printing! Click "Open Downloads" to view files.', 'success');
+        showNotification(result, 'success');
 
         return result;
       } catch (error) {
@@ -167,24 +192,22 @@ export function useExportToPDF() {
   return useMutation({
     mutationFn: async ({ staffIds, isBulk }: { staffIds: string[]; isBulk: boolean }) => {
       const isMultiple = isBulk || staffIds.length > 1;
+      const outputPath = await chooseSavePath(isMultiple ? 'Staff_Directory' : 'Staff_Record');
+      if (!outputPath) return null;
+
       const loadingNotification = showNotification(
-        `Generating professional PDF for ${isMultiple ? `${staffIds.length} staff records` : 'staff record'}...`,
+        `Generating PDF for ${isMultiple ? `${staffIds.length} staff records` : 'staff record'}...`,
         'loading'
       );
 
       try {
         const result = await invoke<string>('export_staff_pdf', {
           staffIds,
-          isBulk: isMultiple
+          isBulk: isMultiple,
+          outputPath,
         });
         removeNotification(loadingNotification);
-
-        // Show success message
-        const message = isMultiple
-          ? 'Staff directory PDF opened in browser for printing! Click "Open Downloads" to view files.'
-          : 'Staff details PDF opened in browser for printing! Click "Open Downloads" to view files.';
-
-        showNotification(message, 'success');
+        showNotification(result, 'success');
 
         return result;
       } catch (error) {
@@ -200,6 +223,90 @@ export function useExportToPDF() {
   });
 }
 
+// Export staff to a CSV file chosen by the user
+export function useExportStaffCsv() {
+  return useMutation({
+    mutationFn: async (params: ExportStaffCsvParams) => {
+      const outputPath = await chooseSavePath('Staff_Export', 'csv');
+      if (!outputPath) return null;
+
+      const loadingNotification = showNotification('Exporting staff to CSV...', 'loading');
+
+      try {
+        const result = await exportService.exportCsv(params, outputPath);
+        removeNotification(loadingNotification);
+        showNotification(result, 'success');
+
+        return result;
+      } catch (error) {
+        removeNotification(loadingNotification);
+        const errorMessage = error instanceof Error ? error.message : String(error);
+        showNotification(`Failed to export CSV: ${errorMessage}`, 'error');
+        throw error;
+      }
+    },
+    onError: (error) => {
+      console.error('CSV export failed:', error);
+    }
+  });
+}
+
+// Export staff to an Excel workbook chosen by the user
+export function useExportStaffXlsx() {
+  return useMutation({
+    mutationFn: async (params: ExportStaffXlsxParams) => {
+      const outputPath = await chooseSavePath('Staff_Export', 'xlsx');
+      if (!outputPath) return null;
+
+      const loadingNotification = showNotification('Exporting staff to Excel...', 'loading');
+
+      try {
+        const result = await exportService.exportXlsx(params, outputPath);
+        removeNotification(loadingNotification);
+        showNotification(result, 'success');
+
+        return result;
+      } catch (error) {
+        removeNotification(loadingNotification);
+        const errorMessage = error instanceof Error ? error.message : String(error);
+        showNotification(`Failed to export Excel file: ${errorMessage}`, 'error');
+        throw error;
+      }
+    },
+    onError: (error) => {
+      console.error('Excel export failed:', error);
+    }
+  });
+}
+
+// Export the whole database to a JSON file for loading on another machine
+export function useExportAll() {
+  return useMutation({
+    mutationFn: async () => {
+      const outputPath = await chooseSavePath('Staff_Database', 'json');
+      if (!outputPath) return null;
+
+      const loadingNotification = showNotification('Exporting database...', 'loading');
+
+      try {
+        const result = await exportService.exportAll(outputPath);
+        removeNotification(loadingNotification);
+        showNotification(result, 'success');
+
+        return result;
+      } catch (error) {
+        removeNotification(loadingNotification);
+        const errorMessage = error instanceof Error ? error.message : String(error);
+        showNotification(`Failed to export database: ${errorMessage}`, 'error');
+        throw error;
+      }
+    },
+    onError: (error) => {
+      console.error('Database export failed:', error);
+    }
+  });
+}
+
 // Open Downloads folder
 export function useOpenDownloadsFolder() {
   return useMutation({
@@ -0,0 +1,34 @@
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { settingsService } from '../lib/database';
+import type { OfficeSettings, UpdateOfficeSettingsRequest } from '../types/staff';
+
+export function useOfficeSettings() {
+  return useQuery({
+    queryKey: ['settings', 'office'],
+    queryFn: () => settingsService.get(),
+    staleTime: Infinity, // Only changes through the mutations below
+  });
+}
+
+function useSettingsMutation<T>(mutationFn: (value: T) => Promise<OfficeSettings>) {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn,
+    onSuccess: (settings) => {
+      queryClient.setQueryData(['settings', 'office'], settings);
+    },
+  });
+}
+
+export function useUpdateOfficeSettings() {
+  return useSettingsMutation((data: UpdateOfficeSettingsRequest) => settingsService.update(data));
+}
+
+export function useSetOfficeLogo() {
+  return useSettingsMutation((imageData: string) => settingsService.setLogo(imageData));
+}
+
+export function useRemoveOfficeLogo() {
+  return useSettingsMutation<void>(() => settingsService.removeLogo());
+}
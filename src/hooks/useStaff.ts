@@ -1,11 +1,11 @@
 import { useQuery } from '@tanstack/react-query';
 import { staffDatabase } from '../lib/database';
-import type { StaffSearchParams } from '../types/staff';
+import type { StaffSearchParams, StaffPageParams } from '../types/staff';
 
-export function useStaffList() {
+export function useStaffList(page: StaffPageParams = {}) {
   return useQuery({
-    queryKey: ['staff', 'list'],
-    queryFn: () => staffDatabase.getAll(),
+    queryKey: ['staff', 'list', page],
+    queryFn: () => staffDatabase.getAll(page),
     staleTime: 5 * 60 * 1000, // 5 minutes
   });
 }
@@ -19,13 +19,10 @@ export function useStaffById(id: string | undefined) {
   });
 }
 
-export function useStaffSearch(params: StaffSearchParams) {
+export function useStaffSearch(params: StaffSearchParams, page: StaffPageParams = {}) {
   return useQuery({
-    queryKey: ['staff', 'search', params],
-    queryFn: () => staffDatabase.search(params),
-    enabled: Object.values(params).some(value =>
-      value !== undefined && value !== null && value !== ''
-    ),
+    queryKey: ['staff', 'search', params, page],
+    queryFn: () => staffDatabase.search(params, page),
     staleTime: 1 * 60 * 1000, // 1 minute for search results
   });
 }
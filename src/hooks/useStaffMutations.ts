@@ -1,6 +1,11 @@
 import { useMutation, useQueryClient } from '@tanstack/react-query';
 import { staffDatabase } from '../lib/database';
-import type { CreateStaffRequest, UpdateStaffRequest } from '../types/staff';
+import type {
+  CreateStaffRequest,
+  StaffBulkUpdateRequest,
+  StaffPatchRequest,
+  UpdateStaffRequest,
+} from '../types/staff';
 
 export function useCreateStaff() {
   const queryClient = useQueryClient();
@@ -34,6 +39,38 @@ export function useUpdateStaff() {
   });
 }
 
+export function usePatchStaff() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (patch: StaffPatchRequest) => staffDatabase.patch(patch),
+    onSuccess: (updatedStaff) => {
+      // The response has no full-size photo, so refetch the detail rather than replace it
+      queryClient.invalidateQueries({ queryKey: ['staff', 'detail', updatedStaff.id] });
+      queryClient.invalidateQueries({ queryKey: ['staff', 'list'] });
+      queryClient.invalidateQueries({ queryKey: ['staff', 'search'] });
+    },
+  });
+}
+
+// A preview changes nothing, so it's a mutation only to run it on demand
+export function usePreviewBulkUpdate() {
+  return useMutation({
+    mutationFn: (request: StaffBulkUpdateRequest) => staffDatabase.previewBulkUpdate(request),
+  });
+}
+
+export function useBulkUpdateStaff() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (request: StaffBulkUpdateRequest) => staffDatabase.bulkUpdate(request),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ['staff'] });
+    },
+  });
+}
+
 export function useDeleteStaff() {
   const queryClient = useQueryClient();
 
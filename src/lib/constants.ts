@@ -1,6 +1,5 @@
 export const APP_NAME = 'Forest Office Staff Management';
 export const APP_VERSION = '1.0.0';
-export const ORGANIZATION = 'Divisional Forest Office - Vavuniya, Sri Lanka';
 
 export const DATE_FORMATS = {
   DISPLAY: 'dd-MM-yyyy',
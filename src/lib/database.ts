@@ -1,9 +1,47 @@
 import { invoke } from '@tauri-apps/api/core';
 import type {
   Staff,
+  StaffSummary,
+  StaffPage,
+  StaffPageParams,
   CreateStaffRequest,
   UpdateStaffRequest,
-  StaffSearchParams
+  StaffPatchRequest,
+  StaffBulkUpdateRequest,
+  StaffBulkPreview,
+  StaffBulkUpdateResult,
+  StaffSearchParams,
+  SavedSearch,
+  CreateSavedSearchRequest,
+  UpdateSavedSearchRequest,
+  StaffGroup,
+  CreateStaffGroupRequest,
+  UpdateStaffGroupRequest,
+  PrintStaffBulkParams,
+  OfficeSettings,
+  UpdateOfficeSettingsRequest,
+  ExportStaffCsvParams,
+  ExportStaffXlsxParams,
+  ImportMapping,
+  CreateImportMappingRequest,
+  UpdateImportMappingRequest,
+  ImportPreview,
+  ImportStaffParams,
+  ImportReport,
+  ImportAllParams,
+  TransferReport,
+  BackupSettings,
+  UpdateBackupSettingsRequest,
+  BackupInfo,
+  EncryptionStatus,
+  AuthStatus,
+  User,
+  CreateUserRequest,
+  UpdateUserRequest,
+  AuditEntry,
+  AuditLogParams,
+  SecuritySettings,
+  UpdateSecuritySettingsRequest
 } from '../types/staff';
 
 // Convert between frontend (camelCase) and backend (snake_case) formats
@@ -37,14 +75,21 @@ export const staffDatabase = {
     return fromBackendFormat(result);
   },
 
-  async getAll(): Promise<Staff[]> {
-    const result = await invoke<any[]>('get_all_staff');
-    return result.map(fromBackendFormat);
+  async getAll(page: StaffPageParams = {}): Promise<StaffPage<StaffSummary>> {
+    const result = await invoke<StaffPage<any>>('get_all_staff', { page: toBackendFormat(page) });
+    return { ...result, items: result.items.map(fromBackendFormat) };
   },
 
   async getById(id: string): Promise<Staff> {
-    const result = await invoke<any>('get_staff_by_id', { id });
-    return fromBackendFormat(result);
+    const [result, imageData] = await Promise.all([
+      invoke<any>('get_staff_by_id', { id }),
+      invoke<string | null>('get_staff_photo', { staffId: id }),
+    ]);
+    return { ...fromBackendFormat(result), imageData: imageData ?? undefined };
+  },
+
+  async getPhoto(staffId: string): Promise<string | null> {
+    return await invoke<string | null>('get_staff_photo', { staffId });
   },
 
   async update(staffData: UpdateStaffRequest): Promise<Staff> {
@@ -53,14 +98,38 @@ export const staffDatabase = {
     return fromBackendFormat(result);
   },
 
+  async patch(patch: StaffPatchRequest): Promise<Staff> {
+    const result = await invoke<any>('patch_staff', { patch: toBackendFormat(patch) });
+    return fromBackendFormat(result);
+  },
+
+  async previewBulkUpdate(request: StaffBulkUpdateRequest): Promise<StaffBulkPreview> {
+    const result = await invoke<any>('preview_bulk_staff_update', { params: bulkUpdateToBackend(request) });
+    return {
+      unchanged: result.unchanged,
+      rows: result.rows.map((row: any) => ({
+        staff: fromBackendFormat(row.staff),
+        changes: row.changes.map(fromBackendFormat),
+      })),
+    };
+  },
+
+  async bulkUpdate(request: StaffBulkUpdateRequest): Promise<StaffBulkUpdateResult> {
+    const result = await invoke<any>('bulk_update_staff', { params: bulkUpdateToBackend(request) });
+    return fromBackendFormat(result);
+  },
+
   async delete(id: string): Promise<void> {
     await invoke('delete_staff', { id });
   },
 
-  async search(params: StaffSearchParams): Promise<Staff[]> {
+  async search(params: StaffSearchParams, page: StaffPageParams = {}): Promise<StaffPage<StaffSummary>> {
     const backendParams = toBackendFormat(params);
-    const result = await invoke<any[]>('search_staff', { params: backendParams });
-    return result.map(fromBackendFormat);
+    const result = await invoke<StaffPage<any>>('search_staff', {
+      params: backendParams,
+      page: toBackendFormat(page),
+    });
+    return { ...result, items: result.items.map(fromBackendFormat) };
   },
 
   async getByNIC(nic: string): Promise<Staff | null> {
@@ -69,6 +138,250 @@ export const staffDatabase = {
   },
 };
 
+function bulkUpdateToBackend(request: StaffBulkUpdateRequest): any {
+  return {
+    staff_ids: request.staffIds ?? [],
+    filters: request.filters ? toBackendFormat(request.filters) : undefined,
+    changes: toBackendFormat(request.changes),
+  };
+}
+
+// Saved searches keep their params in backend (snake_case) form
+function savedSearchFromBackend(result: any): SavedSearch {
+  const savedSearch = fromBackendFormat(result);
+  return { ...savedSearch, params: fromBackendFormat(savedSearch.params) };
+}
+
+function savedSearchToBackend(data: CreateSavedSearchRequest | UpdateSavedSearchRequest): any {
+  return { ...data, params: toBackendFormat(data.params) };
+}
+
+// Saved search and staff group operations
+export const savedSearchService = {
+  async create(data: CreateSavedSearchRequest): Promise<SavedSearch> {
+    const result = await invoke<any>('create_saved_search', { data: savedSearchToBackend(data) });
+    return savedSearchFromBackend(result);
+  },
+
+  async list(): Promise<SavedSearch[]> {
+    const result = await invoke<any[]>('list_saved_searches');
+    return result.map(savedSearchFromBackend);
+  },
+
+  async update(data: UpdateSavedSearchRequest): Promise<SavedSearch> {
+    const result = await invoke<any>('update_saved_search', { data: savedSearchToBackend(data) });
+    return savedSearchFromBackend(result);
+  },
+
+  async delete(id: string): Promise<void> {
+    await invoke('delete_saved_search', { id });
+  },
+
+  async run(id: string, page: StaffPageParams = {}): Promise<StaffPage<StaffSummary>> {
+    const result = await invoke<StaffPage<any>>('run_saved_search', { id, page: toBackendFormat(page) });
+    return { ...result, items: result.items.map(fromBackendFormat) };
+  },
+
+  async getStaffIds(id: string): Promise<string[]> {
+    return await invoke<string[]>('get_saved_search_staff_ids', { id });
+  },
+
+  async print(id: string, outputPath: string): Promise<string> {
+    return await invoke<string>('print_saved_search', { id, outputPath });
+  },
+};
+
+export const staffGroupService = {
+  async create(data: CreateStaffGroupRequest): Promise<StaffGroup> {
+    const result = await invoke<any>('create_staff_group', { data: toBackendFormat(data) });
+    return fromBackendFormat(result);
+  },
+
+  async list(): Promise<StaffGroup[]> {
+    const result = await invoke<any[]>('list_staff_groups');
+    return result.map(fromBackendFormat);
+  },
+
+  async update(data: UpdateStaffGroupRequest): Promise<StaffGroup> {
+    const result = await invoke<any>('update_staff_group', { data: toBackendFormat(data) });
+    return fromBackendFormat(result);
+  },
+
+  async addStaff(groupId: string, staffIds: string[]): Promise<StaffGroup> {
+    const result = await invoke<any>('add_staff_to_group', { groupId, staffIds });
+    return fromBackendFormat(result);
+  },
+
+  async removeStaff(groupId: string, staffIds: string[]): Promise<StaffGroup> {
+    const result = await invoke<any>('remove_staff_from_group', { groupId, staffIds });
+    return fromBackendFormat(result);
+  },
+
+  async delete(id: string): Promise<void> {
+    await invoke('delete_staff_group', { id });
+  },
+
+  async run(id: string): Promise<StaffSummary[]> {
+    const result = await invoke<any[]>('run_staff_group', { id });
+    return result.map(fromBackendFormat);
+  },
+
+  async print(id: string, outputPath: string): Promise<string> {
+    return await invoke<string>('print_staff_group', { id, outputPath });
+  },
+};
+
+// Office letterhead and signatory settings
+export const settingsService = {
+  async get(): Promise<OfficeSettings> {
+    const result = await invoke<any>('get_office_settings');
+    return fromBackendFormat(result);
+  },
+
+  async update(data: UpdateOfficeSettingsRequest): Promise<OfficeSettings> {
+    const result = await invoke<any>('update_office_settings', { data: toBackendFormat(data) });
+    return fromBackendFormat(result);
+  },
+
+  async setLogo(imageData: string): Promise<OfficeSettings> {
+    const result = await invoke<any>('set_office_logo', { imageData });
+    return fromBackendFormat(result);
+  },
+
+  async removeLogo(): Promise<OfficeSettings> {
+    const result = await invoke<any>('remove_office_logo');
+    return fromBackendFormat(result);
+  },
+};
+
+// Login session and user accounts
+export const authService = {
+  async getStatus(): Promise<AuthStatus> {
+    const result = await invoke<any>('get_auth_status');
+    const status = fromBackendFormat(result);
+    return { ...status, user: status.user ? fromBackendFormat(status.user) : undefined };
+  },
+
+  async createFirstAdmin(data: CreateUserRequest): Promise<User> {
+    const result = await invoke<any>('create_first_admin', { data: toBackendFormat(data) });
+    return fromBackendFormat(result);
+  },
+
+  async login(username: string, password: string): Promise<User> {
+    const result = await invoke<any>('login', { username, password });
+    return fromBackendFormat(result);
+  },
+
+  async logout(): Promise<void> {
+    return invoke('logout');
+  },
+
+  async touch(): Promise<void> {
+    return invoke('touch_session');
+  },
+
+  async unlockSession(password: string): Promise<User> {
+    const result = await invoke<any>('unlock_session', { password });
+    return fromBackendFormat(result);
+  },
+
+  async changePassword(currentPassword: string, newPassword: string): Promise<void> {
+    return invoke('change_password', { currentPassword, newPassword });
+  },
+
+  async listUsers(): Promise<User[]> {
+    const result = await invoke<any[]>('list_users');
+    return result.map(fromBackendFormat);
+  },
+
+  async createUser(data: CreateUserRequest): Promise<User> {
+    const result = await invoke<any>('create_user', { data: toBackendFormat(data) });
+    return fromBackendFormat(result);
+  },
+
+  async updateUser(data: UpdateUserRequest): Promise<User> {
+    const result = await invoke<any>('update_user', { data: toBackendFormat(data) });
+    return fromBackendFormat(result);
+  },
+
+  async resetPassword(id: string, newPassword: string): Promise<void> {
+    return invoke('reset_user_password', { id, newPassword });
+  },
+
+  async getSecuritySettings(): Promise<SecuritySettings> {
+    const result = await invoke<any>('get_security_settings');
+    return fromBackendFormat(result);
+  },
+
+  async updateSecuritySettings(data: UpdateSecuritySettingsRequest): Promise<SecuritySettings> {
+    const result = await invoke<any>('update_security_settings', { data: toBackendFormat(data) });
+    return fromBackendFormat(result);
+  },
+
+  async listAuditLog(params?: AuditLogParams): Promise<AuditEntry[]> {
+    const result = await invoke<any[]>('list_audit_log', { params: params ? toBackendFormat(params) : null });
+    return result.map((entry) => {
+      const converted = fromBackendFormat(entry);
+      return { ...converted, changes: converted.changes.map(fromBackendFormat) };
+    });
+  },
+};
+
+// Optional encryption of the database file
+export const encryptionService = {
+  async getStatus(): Promise<EncryptionStatus> {
+    return invoke<EncryptionStatus>('get_encryption_status');
+  },
+
+  async unlock(passphrase: string): Promise<EncryptionStatus> {
+    return invoke<EncryptionStatus>('unlock_database', { passphrase });
+  },
+
+  async encrypt(passphrase: string): Promise<EncryptionStatus> {
+    return invoke<EncryptionStatus>('encrypt_database', { passphrase });
+  },
+
+  async changePassphrase(currentPassphrase: string, newPassphrase: string): Promise<void> {
+    return invoke('change_database_passphrase', { currentPassphrase, newPassphrase });
+  },
+};
+
+// Automatic and manual database backups
+export const backupService = {
+  async getSettings(): Promise<BackupSettings> {
+    const result = await invoke<any>('get_backup_settings');
+    return fromBackendFormat(result);
+  },
+
+  async updateSettings(data: UpdateBackupSettingsRequest): Promise<BackupSettings> {
+    const result = await invoke<any>('update_backup_settings', { data: toBackendFormat(data) });
+    return fromBackendFormat(result);
+  },
+
+  async list(): Promise<BackupInfo[]> {
+    const result = await invoke<any[]>('list_backups');
+    return result.map(fromBackendFormat);
+  },
+
+  async createNow(): Promise<BackupInfo> {
+    const result = await invoke<any>('create_backup_now');
+    return fromBackendFormat(result);
+  },
+
+  // Returns the backup taken of the database that was replaced
+  async restore(path: string): Promise<BackupInfo> {
+    const result = await invoke<any>('restore_backup', { path });
+    return fromBackendFormat(result);
+  },
+};
+
+function bulkParamsToBackend(params: PrintStaffBulkParams): any {
+  return {
+    ...toBackendFormat(params),
+    filters: params.filters ? toBackendFormat(params.filters) : undefined,
+  };
+}
+
 // Print operations
 export const printService = {
 
@@ -80,15 +393,94 @@ export const printService = {
     return await invoke<string>('generate_bulk_staff_preview', { staffIds });
   },
 
-  async printIndividual(staffId: string): Promise<string> {
-    return await invoke<string>('print_staff_individual', { staffId });
+  async generateBulkStaffPreviewWithParams(params: PrintStaffBulkParams): Promise<string> {
+    return await invoke<string>('generate_bulk_staff_preview_with_params', { params: bulkParamsToBackend(params) });
+  },
+
+  async printBulkWithParams(params: PrintStaffBulkParams, outputPath: string): Promise<string> {
+    return await invoke<string>('generate_bulk_staff_pdf_with_params', {
+      params: bulkParamsToBackend(params),
+      outputPath,
+    });
+  },
+
+  async printIndividual(staffId: string, outputPath: string): Promise<string> {
+    return await invoke<string>('print_staff_individual', { staffId, outputPath });
+  },
+
+  async printBulk(staffIds: string[], outputPath: string): Promise<string> {
+    return await invoke<string>('print_staff_bulk', { staffIds, outputPath });
+  },
+
+  async exportToPDF(staffIds: string[], outputPath: string, isBulk: boolean = false): Promise<string> {
+    return await invoke<string>('export_staff_pdf', { staffIds, isBulk, outputPath });
   },
 
-  async printBulk(staffIds: string[]): Promise<string> {
-    return await invoke<string>('print_staff_bulk', { staffIds });
+  async getReportTemplatesDir(): Promise<string> {
+    return await invoke<string>('get_report_templates_dir');
   },
 
-  async exportToPDF(staffIds: string[], isBulk: boolean = false): Promise<string> {
-    return await invoke<string>('export_staff_pdf', { staffIds, isBulk });
+  async resetReportTemplates(): Promise<string> {
+    return await invoke<string>('reset_report_templates');
+  },
+};
+
+export const exportService = {
+  async exportCsv(params: ExportStaffCsvParams, outputPath: string): Promise<string> {
+    return await invoke<string>('export_staff_csv', { params: bulkParamsToBackend(params), outputPath });
   },
-};
\ No newline at end of file
+
+  async exportXlsx(params: ExportStaffXlsxParams, outputPath: string): Promise<string> {
+    return await invoke<string>('export_staff_xlsx', { params: bulkParamsToBackend(params), outputPath });
+  },
+
+  async exportAll(outputPath: string): Promise<string> {
+    return await invoke<string>('export_all', { outputPath });
+  },
+};
+
+function importReportFromBackend(result: any): ImportReport {
+  const report = fromBackendFormat(result);
+  return {
+    ...report,
+    rows: report.rows.map((row: any) => ({ ...fromBackendFormat(row), conflicts: row.conflicts.map(fromBackendFormat) })),
+  };
+}
+
+// Staff import from CSV and Excel files
+export const importService = {
+  async preview(path: string, sheet?: string): Promise<ImportPreview> {
+    const result = await invoke<any>('preview_staff_import', { path, sheet });
+    return fromBackendFormat(result);
+  },
+
+  async importStaff(params: ImportStaffParams): Promise<ImportReport> {
+    const result = await invoke<any>('import_staff', { params: toBackendFormat(params) });
+    return importReportFromBackend(result);
+  },
+
+  async createMapping(data: CreateImportMappingRequest): Promise<ImportMapping> {
+    const result = await invoke<any>('create_import_mapping', { data });
+    return fromBackendFormat(result);
+  },
+
+  async listMappings(): Promise<ImportMapping[]> {
+    const result = await invoke<any[]>('list_import_mappings');
+    return result.map(fromBackendFormat);
+  },
+
+  async updateMapping(data: UpdateImportMappingRequest): Promise<ImportMapping> {
+    const result = await invoke<any>('update_import_mapping', { data });
+    return fromBackendFormat(result);
+  },
+
+  async deleteMapping(id: string): Promise<void> {
+    await invoke('delete_import_mapping', { id });
+  },
+
+  async importAll(params: ImportAllParams): Promise<TransferReport> {
+    const result = await invoke<any>('import_all', { params: toBackendFormat(params) });
+    const report = fromBackendFormat(result);
+    return { ...report, conflicts: report.conflicts.map(fromBackendFormat) };
+  },
+};
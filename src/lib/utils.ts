@@ -56,6 +56,16 @@ export function formatCurrency(amount: number): string {
   }).format(amount);
 }
 
+// Shown in place of details hidden from the current user's role
+export const RESTRICTED = 'Restricted';
+
+/**
+ * Format a salary amount, unless it was hidden from the current user
+ */
+export function formatSalary(amount: number, masked?: boolean): string {
+  return masked ? RESTRICTED : formatCurrency(amount);
+}
+
 /**
  * Debounce function
  */
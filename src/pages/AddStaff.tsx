@@ -8,6 +8,7 @@ import { DatePicker } from '../components/forms/DatePicker';
 import { NICInput } from '../components/forms/NICInput';
 import { ImageUpload } from '../components/forms/ImageUpload';
 import { useCreateStaff } from '../hooks/useStaffMutations';
+import { useCurrentUser } from '../hooks/useAuth';
 import { calculateAge, calculateRetirementDate } from '../lib/nicConverter';
 import { DESIGNATIONS, SALARY_CODES, MARITAL_STATUSES, FORM_DEFAULTS } from '../types/staff';
 import type { CreateStaffRequest } from '../types/staff';
@@ -19,6 +20,8 @@ interface AddStaffProps {
 
 export function AddStaff({ onStaffCreated, onCancel }: AddStaffProps) {
   const createStaff = useCreateStaff();
+  // Salary details are entered by an administrator once the record exists
+  const canEditSalary = useCurrentUser()?.role === 'admin';
 
   const [formData, setFormData] = useState<CreateStaffRequest>({
     appointmentNumber: '',
@@ -38,7 +41,7 @@ export function AddStaff({ onStaffCreated, onCancel }: AddStaffProps) {
     dateOfFirstAppointment: '',
     dateOfRetirement: '',
     incrementDate: '',
-    salaryCode: FORM_DEFAULTS.SALARY_CODE,
+    salaryCode: canEditSalary ? FORM_DEFAULTS.SALARY_CODE : '',
     basicSalary: 0,
     incrementAmount: 0,
     imageData: undefined,
@@ -110,9 +113,11 @@ export function AddStaff({ onStaffCreated, onCancel }: AddStaffProps) {
     if (!formData.maritalStatus) newErrors.maritalStatus = 'Marital status is required';
     if (!formData.designation) newErrors.designation = 'Designation is required';
     if (!formData.dateOfFirstAppointment) newErrors.dateOfFirstAppointment = 'Date of first appointment is required';
-    if (!formData.salaryCode) newErrors.salaryCode = 'Salary code is required';
-    if (formData.basicSalary <= 0) newErrors.basicSalary = 'Basic salary must be greater than 0';
-    if (formData.incrementAmount < 0) newErrors.incrementAmount = 'Increment amount cannot be negative';
+    if (canEditSalary) {
+      if (!formData.salaryCode) newErrors.salaryCode = 'Salary code is required';
+      if (formData.basicSalary <= 0) newErrors.basicSalary = 'Basic salary must be greater than 0';
+      if (formData.incrementAmount < 0) newErrors.incrementAmount = 'Increment amount cannot be negative';
+    }
 
     // Email validation
     if (formData.email && !/^[^\s@]+@[^\s@]+\.[^\s@]+$/.test(formData.email)) {
@@ -456,12 +461,16 @@ export function AddStaff({ onStaffCreated, onCancel }: AddStaffProps) {
             <span>Salary Information</span>
           </div>
 
+          {!canEditSalary && (
+            <p className="text-sm text-slate-500 mb-4">An administrator enters salary details after the record is saved.</p>
+          )}
+
           <div className="space-y-4">
             <div className="field-group">
               <label className="block text-sm font-medium text-slate-700 mb-2">
                 Salary Code <span className="text-red-500">*</span>
               </label>
-              <Select value={formData.salaryCode} onValueChange={(value) => handleInputChange('salaryCode', value)}>
+              <Select value={formData.salaryCode} onValueChange={(value) => handleInputChange('salaryCode', value)} disabled={!canEditSalary}>
                 <SelectTrigger className={errors.salaryCode ? 'border-red-500' : ''}>
                   <SelectValue placeholder="Select salary code" />
                 </SelectTrigger>
@@ -491,6 +500,7 @@ export function AddStaff({ onStaffCreated, onCancel }: AddStaffProps) {
                   onFocus={() => handleCurrencyFocus('basicSalary')}
                   onBlur={(e) => handleCurrencyBlur('basicSalary', e.target.value)}
                   onChange={(e) => handleInputChange('basicSalary', e.target.value)}
+                  disabled={!canEditSalary}
                   placeholder="Enter basic salary"
                   className={errors.basicSalary ? 'border-red-500' : ''}
                 />
@@ -511,6 +521,7 @@ export function AddStaff({ onStaffCreated, onCancel }: AddStaffProps) {
                   onFocus={() => handleCurrencyFocus('incrementAmount')}
                   onBlur={(e) => handleCurrencyBlur('incrementAmount', e.target.value)}
                   onChange={(e) => handleInputChange('incrementAmount', e.target.value)}
+                  disabled={!canEditSalary}
                   placeholder="Enter increment amount"
                   className={errors.incrementAmount ? 'border-red-500' : ''}
                 />
@@ -1,90 +1,43 @@
 import { useState, useMemo } from 'react';
-import { Search, Filter, Eye, Edit, Trash2, Users, Download, Plus } from 'lucide-react';
+import { Search, Filter, Eye, Edit, Trash2, Users, Download, Plus, ChevronLeft, ChevronRight } from 'lucide-react';
 import { Button } from '../components/ui/button';
 import { Input } from '../components/ui/input';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '../components/ui/select';
 import { Dialog, DialogContent, DialogDescription, DialogFooter, DialogHeader, DialogTitle } from '../components/ui/dialog';
 import { Checkbox } from '../components/ui/checkbox';
-import { ViewStaffDialog, EditStaffDialog } from '../components/dialogs';
-import { useStaffList } from '../hooks/useStaff';
+import { ViewStaffDialog, EditStaffDialog, BulkEditDialog } from '../components/dialogs';
+import { useStaffSearch } from '../hooks/useStaff';
 import { useDeleteStaff } from '../hooks/useStaffMutations';
-import { useExportToPDF, useOpenDownloadsFolder } from '../hooks/usePrint';
-import { formatCurrency, debounce } from '../lib/utils';
+import { useExportToPDF, useExportStaffCsv, useExportStaffXlsx } from '../hooks/usePrint';
+import { debounce, formatSalary } from '../lib/utils';
 import { DESIGNATIONS, SALARY_CODES } from '../types/staff';
-import type { Staff, StaffSearchParams } from '../types/staff';
+import type { StaffSummary, StaffSearchParams } from '../types/staff';
+
+const PAGE_SIZE = 50;
 
 export function SearchStaff() {
   const [searchParams, setSearchParams] = useState<StaffSearchParams>({});
   const [selectedStaff, setSelectedStaff] = useState<Set<string>>(new Set());
   const [showFilters, setShowFilters] = useState(false);
-  const [deleteConfirm, setDeleteConfirm] = useState<{ open: boolean; staff?: Staff }>({ open: false });
+  const [deleteConfirm, setDeleteConfirm] = useState<{ open: boolean; staff?: StaffSummary }>({ open: false });
   const [viewDialog, setViewDialog] = useState<{ open: boolean; staffId: string | null }>({ open: false, staffId: null });
   const [editDialog, setEditDialog] = useState<{ open: boolean; staffId: string | null }>({ open: false, staffId: null });
+  const [bulkEditOpen, setBulkEditOpen] = useState(false);
+  const [pageIndex, setPageIndex] = useState(0);
 
-  const { data: allStaff = [], isLoading: isLoadingAll } = useStaffList();
+  const { data: staffPage, isLoading: isLoadingAll } = useStaffSearch(searchParams, {
+    limit: PAGE_SIZE,
+    offset: pageIndex * PAGE_SIZE,
+  });
   const deleteStaff = useDeleteStaff();
   const exportToPDF = useExportToPDF();
-  const openDownloads = useOpenDownloadsFolder();
-
-  // Filter staff based on search parameters (Frontend filtering)
-  const filteredStaff = useMemo(() => {
-    let filtered = allStaff;
-
-    // Search term filter
-    if (searchParams.searchTerm) {
-      const searchLower = searchParams.searchTerm.toLowerCase();
-      filtered = filtered.filter(staff =>
-        staff.fullName.toLowerCase().includes(searchLower) ||
-        staff.appointmentNumber.toLowerCase().includes(searchLower) ||
-        staff.nicNumber.toLowerCase().includes(searchLower) ||
-        (staff.nicNumberOld && staff.nicNumberOld.toLowerCase().includes(searchLower))
-      );
-    }
-
-    // Designation filter
-    if (searchParams.designation) {
-      filtered = filtered.filter(staff => staff.designation === searchParams.designation);
-    }
-
-    // Gender filter - Fixed logic
-    if (searchParams.gender) {
-      filtered = filtered.filter(staff => {
-        console.log(`Filtering: Staff ${staff.fullName} has gender "${staff.gender}", looking for "${searchParams.gender}"`);
-        return staff.gender === searchParams.gender;
-      });
-    }
-
-    // Age range filter
-    if (searchParams.ageMin !== undefined && searchParams.ageMin !== null) {
-      const minAge = typeof searchParams.ageMin === 'string' ? parseInt(searchParams.ageMin) : searchParams.ageMin;
-      if (!isNaN(minAge)) {
-        filtered = filtered.filter(staff => staff.age >= minAge);
-      }
-    }
+  const exportCsv = useExportStaffCsv();
+  const exportXlsx = useExportStaffXlsx();
 
-    if (searchParams.ageMax !== undefined && searchParams.ageMax !== null) {
-      const maxAge = typeof searchParams.ageMax === 'string' ? parseInt(searchParams.ageMax) : searchParams.ageMax;
-      if (!isNaN(maxAge)) {
-        filtered = filtered.filter(staff => staff.age <= maxAge);
-      }
-    }
-
-    // NIC number filter
-    if (searchParams.nicNumber) {
-      const nicLower = searchParams.nicNumber.toLowerCase();
-      filtered = filtered.filter(staff =>
-        staff.nicNumber.toLowerCase().includes(nicLower) ||
-        (staff.nicNumberOld && staff.nicNumberOld.toLowerCase().includes(nicLower))
-      );
-    }
-
-    // Salary code filter
-    if (searchParams.salaryCode) {
-      filtered = filtered.filter(staff => staff.salaryCode === searchParams.salaryCode);
-    }
-
-    return filtered;
-  }, [allStaff, searchParams]);
+  // Filtering, sorting and paging happen in the backend
+  const filteredStaff = staffPage?.items ?? [];
+  const totalStaff = staffPage?.total ?? 0;
+  const pageCount = Math.max(1, Math.ceil(totalStaff / PAGE_SIZE));
 
   // Debounced search function
   const debouncedSearch = useMemo(
@@ -94,6 +47,7 @@ export function SearchStaff() {
         ...prev,
         [field]: value || undefined
       }));
+      setPageIndex(0);
     }, 300),
     []
   );
@@ -121,6 +75,7 @@ export function SearchStaff() {
 
   const handleClearFilters = () => {
     setSearchParams({});
+    setPageIndex(0);
     setSelectedStaff(new Set());
   };
 
@@ -172,19 +127,39 @@ export function SearchStaff() {
           staffIds: Array.from(selectedStaff),
           isBulk: true
         });
-
-        // Show option to open downloads folder after a short delay
-        setTimeout(() => {
-          if (window.confirm('PDF exported successfully! Would you like to open the Downloads folder?')) {
-            openDownloads.mutate();
-          }
-        }, 1000);
       } catch (error) {
         console.error('Bulk export failed:', error);
       }
     }
   };
 
+  const handleCsvExport = async () => {
+    if (selectedStaff.size > 0) {
+      try {
+        await exportCsv.mutateAsync({
+          staffIds: Array.from(selectedStaff),
+          formatValues: true,
+          includeBom: true,
+        });
+      } catch (error) {
+        console.error('CSV export failed:', error);
+      }
+    }
+  };
+
+  const handleXlsxExport = async () => {
+    if (selectedStaff.size > 0) {
+      try {
+        await exportXlsx.mutateAsync({
+          staffIds: Array.from(selectedStaff),
+          includeSummary: true,
+        });
+      } catch (error) {
+        console.error('Excel export failed:', error);
+      }
+    }
+  };
+
   const handleStaffUpdated = () => {
     setEditDialog({ open: false, staffId: null });
   };
@@ -204,7 +179,7 @@ export function SearchStaff() {
             <div className="relative">
               <Search className="absolute left-3 top-1/2 transform -translate-y-1/2 h-4 w-4 text-slate-400" />
               <Input
-                placeholder="Search by name, appointment number, or NIC..."
+                placeholder="Search by name, appointment number, NIC, designation, address or email..."
                 className="pl-10"
                 onChange={(e) => handleSearch('searchTerm', e.target.value)}
                 value={searchParams.searchTerm || ''}
@@ -329,6 +304,15 @@ export function SearchStaff() {
               {selectedStaff.size} staff selected
             </span>
             <div className="flex space-x-2">
+              <Button
+                size="sm"
+                variant="outline"
+                onClick={() => setBulkEditOpen(true)}
+                className="flex items-center space-x-2"
+              >
+                <Edit className="h-4 w-4" />
+                <span>Bulk Edit</span>
+              </Button>
               <Button
                 size="sm"
                 variant="default"
@@ -342,6 +326,26 @@ export function SearchStaff() {
                 <Download className="h-4 w-4" />
                 <span>Export PDF</span>
               </Button>
+              <Button
+                size="sm"
+                variant="outline"
+                onClick={handleCsvExport}
+                disabled={exportCsv.isPending}
+                className="flex items-center space-x-2"
+              >
+                <Download className="h-4 w-4" />
+                <span>Export CSV</span>
+              </Button>
+              <Button
+                size="sm"
+                variant="outline"
+                onClick={handleXlsxExport}
+                disabled={exportXlsx.isPending}
+                className="flex items-center space-x-2"
+              >
+                <Download className="h-4 w-4" />
+                <span>Export Excel</span>
+              </Button>
             </div>
           </div>
         </div>
@@ -356,8 +360,7 @@ export function SearchStaff() {
                 Staff Directory
               </h2>
               <span className="text-sm text-slate-600">
-                {isLoadingAll ? 'Loading...' : `${filteredStaff.length} staff found`}
-                {hasActiveFilters && ` (filtered from ${allStaff.length} total)`}
+                {isLoadingAll ? 'Loading...' : `${totalStaff} staff found`}
               </span>
             </div>
 
@@ -412,9 +415,9 @@ export function SearchStaff() {
                       onCheckedChange={() => handleSelectStaff(staff.id)}
                     />
 
-                    {staff.imageData ? (
+                    {staff.photoThumbnail ? (
                       <img
-                        src={`data:image/jpeg;base64,${staff.imageData}`}
+                        src={`data:image/jpeg;base64,${staff.photoThumbnail}`}
                         alt={staff.fullName}
                         className="w-16 h-20 object-cover rounded border"
                       />
@@ -435,8 +438,21 @@ export function SearchStaff() {
                             Appointment No: {staff.appointmentNumber} | Age: {staff.age} | Gender: {staff.gender} | NIC: {staff.nicNumber}
                           </p>
                           <p className="text-sm text-slate-500">
-                            Salary: {formatCurrency(staff.basicSalary)} ({staff.salaryCode})
+                            Salary: {formatSalary(staff.basicSalary, staff.masked)} ({staff.salaryCode})
                           </p>
+                          {staff.snippet && (
+                            <p className="text-xs text-slate-500 mt-1">
+                              {staff.snippet.map((segment, index) =>
+                                segment.highlighted ? (
+                                  <mark key={index} className="bg-yellow-100 text-slate-800 rounded px-0.5">
+                                    {segment.text}
+                                  </mark>
+                                ) : (
+                                  <span key={index}>{segment.text}</span>
+                                )
+                              )}
+                            </p>
+                          )}
                         </div>
 
                         <div className="flex items-center space-x-2">
@@ -476,6 +492,35 @@ export function SearchStaff() {
             </div>
           )}
         </div>
+
+        {totalStaff > PAGE_SIZE && (
+          <div className="px-6 py-4 border-t border-slate-200 flex items-center justify-between">
+            <span className="text-sm text-slate-600">
+              Showing {pageIndex * PAGE_SIZE + 1}-{Math.min((pageIndex + 1) * PAGE_SIZE, totalStaff)} of {totalStaff}
+            </span>
+            <div className="flex items-center space-x-2">
+              <Button
+                size="sm"
+                variant="outline"
+                onClick={() => setPageIndex(pageIndex - 1)}
+                disabled={pageIndex === 0}
+              >
+                <ChevronLeft className="h-4 w-4" />
+              </Button>
+              <span className="text-sm text-slate-600">
+                Page {pageIndex + 1} of {pageCount}
+              </span>
+              <Button
+                size="sm"
+                variant="outline"
+                onClick={() => setPageIndex(pageIndex + 1)}
+                disabled={pageIndex + 1 >= pageCount}
+              >
+                <ChevronRight className="h-4 w-4" />
+              </Button>
+            </div>
+          </div>
+        )}
       </div>
 
       {/* View Staff Dialog */}
@@ -493,6 +538,14 @@ export function SearchStaff() {
         onStaffUpdated={handleStaffUpdated}
       />
 
+      {/* Bulk Edit Dialog */}
+      <BulkEditDialog
+        isOpen={bulkEditOpen}
+        onClose={() => setBulkEditOpen(false)}
+        staffIds={Array.from(selectedStaff)}
+        onStaffUpdated={() => setBulkEditOpen(false)}
+      />
+
       {/* Delete Confirmation Dialog */}
       <Dialog open={deleteConfirm.open} onOpenChange={(open) => setDeleteConfirm({ open })}>
         <DialogContent>
@@ -3,7 +3,7 @@ import { ArrowLeft, Edit, Printer, Download, User, Briefcase, DollarSign, Calend
 import { Button } from '../components/ui/button';
 import { useStaffById } from '../hooks/useStaff';
 import { usePrintIndividual, useExportToPDF } from '../hooks/usePrint';
-import { formatDate, formatCurrency } from '../lib/utils';
+import { formatDate, formatSalary, RESTRICTED } from '../lib/utils';
 
 interface ViewStaffProps {
   staffId: string;
@@ -194,7 +194,7 @@ export function ViewStaff({ staffId, onEdit, onBack }: ViewStaffProps) {
                     {staff.addressLine2 && <div>{staff.addressLine2}</div>}
                     {staff.addressLine3 && <div>{staff.addressLine3}</div>}
                     {!staff.addressLine1 && !staff.addressLine2 && !staff.addressLine3 && (
-                      <span className="text-slate-500 italic">No address provided</span>
+                      <span className="text-slate-500 italic">{staff.masked ? RESTRICTED : 'No address provided'}</span>
                     )}
                   </div>
                 </div>
@@ -264,14 +264,14 @@ export function ViewStaff({ staffId, onEdit, onBack }: ViewStaffProps) {
               <div>
                 <label className="text-sm font-medium text-slate-600">Basic Salary</label>
                 <p className="text-lg font-semibold text-green-600 mt-1">
-                  {formatCurrency(staff.basicSalary)}
+                  {formatSalary(staff.basicSalary, staff.masked)}
                 </p>
               </div>
 
               <div>
                 <label className="text-sm font-medium text-slate-600">Increment Amount</label>
                 <p className="text-lg font-semibold text-green-600 mt-1">
-                  {formatCurrency(staff.incrementAmount)}
+                  {formatSalary(staff.incrementAmount, staff.masked)}
                 </p>
               </div>
             </div>
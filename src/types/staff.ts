@@ -27,12 +27,18 @@ export interface Staff {
   basicSalary: number;
   incrementAmount: number;
 
-  // Image
+  // Photo (thumbnail only; the full image is loaded by getById)
+  hasPhoto: boolean;
+  photoThumbnail?: string;
   imageData?: string;
 
   // Timestamps
   createdAt: string;
   updatedAt: string;
+  version: number; // Bumped on every save; sent back with updates
+
+  // Salaries and the address are blanked and the NIC partly hidden for viewers
+  masked?: boolean;
 }
 
 export interface CreateStaffRequest {
@@ -66,8 +72,52 @@ export interface CreateStaffRequest {
   imageData?: string;
 }
 
-export interface UpdateStaffRequest extends CreateStaffRequest {
+export interface UpdateStaffRequest extends Omit<CreateStaffRequest, 'imageData'> {
+  id: string;
+  // Left out keeps the stored photo; null or '' removes it
+  imageData?: string | null;
+  // The version the edit started from; saving fails if someone else saved in between
+  version: number;
+}
+
+// Only the fields to change: leave a field out to keep it, or send null to clear it
+export type StaffPatchRequest = {
   id: string;
+  version: number;
+} & {
+  [K in keyof CreateStaffRequest]?: CreateStaffRequest[K] | null;
+};
+
+// Changes a bulk edit makes to every chosen record; leave a field out to keep it, or send null to clear it
+export interface StaffBulkChanges {
+  designation?: string;
+  salaryCode?: string;
+  incrementDate?: string | null; // DD-MM
+  addressLine1?: string | null;
+  addressLine2?: string | null;
+  addressLine3?: string | null;
+}
+
+export interface StaffBulkUpdateRequest {
+  staffIds?: string[]; // When given, exactly these records; empty means everyone matching the filters
+  filters?: StaffSearchParams;
+  changes: StaffBulkChanges;
+}
+
+export interface StaffBulkPreviewRow {
+  staff: StaffSummary;
+  changes: FieldChange[];
+}
+
+export interface StaffBulkPreview {
+  rows: StaffBulkPreviewRow[];
+  unchanged: number; // Chosen, but already have the new values
+}
+
+export interface StaffBulkUpdateResult {
+  batchId: string;
+  updated: number;
+  unchanged: number;
 }
 
 export interface StaffSearchParams {
@@ -78,11 +128,377 @@ export interface StaffSearchParams {
   ageMax?: number;
   nicNumber?: string;
   salaryCode?: string;
+  maritalStatus?: string;
+  designations?: string[];
+  salaryCodes?: string[];
+  basicSalaryMin?: number;
+  basicSalaryMax?: number;
+  // YYYY-MM-DD, inclusive
+  appointmentDateFrom?: string;
+  appointmentDateTo?: string;
+  retirementDateFrom?: string;
+  retirementDateTo?: string;
+  // 1-12
+  incrementMonth?: number;
+  hasPhoto?: boolean;
+  hasEmail?: boolean;
+}
+
+export interface StaffSummary {
+  id: string;
+  appointmentNumber: string;
+  fullName: string;
+  gender: 'Male' | 'Female';
+  age: number;
+  nicNumber: string;
+  nicNumberOld?: string;
+  designation: string;
+  contactNumber?: string;
+  email?: string;
+  salaryCode: string;
+  basicSalary: number;
+  hasPhoto: boolean;
+  photoThumbnail?: string;
+  // Only present for full-text searches
+  snippet?: SnippetSegment[];
+  masked?: boolean;
+}
+
+export interface SnippetSegment {
+  text: string;
+  highlighted: boolean;
+}
+
+export type StaffSortColumn =
+  | 'full_name'
+  | 'appointment_number'
+  | 'designation'
+  | 'age'
+  | 'nic_number'
+  | 'salary_code'
+  | 'basic_salary'
+  | 'date_of_first_appointment'
+  | 'date_of_retirement'
+  | 'created_at'
+  | 'updated_at';
+
+export type SortDirection = 'asc' | 'desc';
+
+export interface StaffPageParams {
+  limit?: number;
+  offset?: number;
+  sortBy?: StaffSortColumn;
+  sortDirection?: SortDirection;
+}
+
+export type StaffExportColumn =
+  | 'appointment_number'
+  | 'full_name'
+  | 'gender'
+  | 'date_of_birth'
+  | 'age'
+  | 'nic_number'
+  | 'nic_number_old'
+  | 'marital_status'
+  | 'address'
+  | 'address_line1'
+  | 'address_line2'
+  | 'address_line3'
+  | 'contact_number'
+  | 'email'
+  | 'designation'
+  | 'date_of_first_appointment'
+  | 'date_of_retirement'
+  | 'increment_date'
+  | 'salary_code'
+  | 'basic_salary'
+  | 'increment_amount'
+  | 'created_at'
+  | 'updated_at';
+
+export interface ExportStaffCsvParams extends PrintStaffBulkParams {
+  // In output order; empty or missing means the default set
+  columns?: StaffExportColumn[];
+  // Dates as dd-mm-yyyy and amounts as "Rs. 1,234.00"
+  formatValues?: boolean;
+  // UTF-8 byte order mark so Excel reads Sinhala and Tamil text correctly
+  includeBom?: boolean;
+}
+
+export interface ExportStaffXlsxParams extends PrintStaffBulkParams {
+  columns?: StaffExportColumn[];
+  // One sheet per designation instead of a single "Staff" sheet
+  sheetPerDesignation?: boolean;
+  // Extra sheet with counts and salary totals by designation and salary code
+  includeSummary?: boolean;
+}
+
+// CreateStaff fields an import column can fill; age is always computed
+export type StaffImportField = Exclude<StaffExportColumn, 'age' | 'address' | 'created_at' | 'updated_at'>;
+
+export interface ImportColumnMapping {
+  column: string; // Header text in the file
+  field: StaffImportField;
+}
+
+export interface ImportMapping {
+  id: string;
+  name: string;
+  columns: ImportColumnMapping[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CreateImportMappingRequest {
+  name: string;
+  columns: ImportColumnMapping[];
+}
+
+export interface UpdateImportMappingRequest extends CreateImportMappingRequest {
+  id: string;
+}
+
+export interface ImportPreview {
+  sheets: string[]; // Empty for CSV files
+  sheet?: string;
+  headers: string[];
+  sampleRows: string[][];
+  totalRows: number;
+  suggestedColumns: ImportColumnMapping[];
+}
+
+export interface ImportStaffParams {
+  path: string;
+  sheet?: string;
+  // A saved mapping, used instead of columns when given
+  mappingId?: string;
+  columns?: ImportColumnMapping[];
+  dryRun?: boolean;
+}
+
+export interface ImportConflict {
+  field: StaffImportField;
+  value: string;
+  existingStaffId?: string; // Already in the database
+  existingRow?: number; // Earlier in the same file
+}
+
+export interface ImportRowReport {
+  row: number; // Counting the header as row 1
+  appointmentNumber?: string;
+  fullName?: string;
+  errors: string[];
+  conflicts: ImportConflict[];
+}
+
+export interface ImportReport {
+  dryRun: boolean;
+  totalRows: number;
+  validRows: number;
+  imported: number;
+  importedIds: string[];
+  // Only rows with errors or conflicts; these are never imported
+  rows: ImportRowReport[];
+}
+
+export interface EncryptionStatus {
+  encrypted: boolean;
+  unlocked: boolean; // False until the passphrase is entered for an encrypted database
+}
+
+// Viewers look up staff and print; clerks also add and edit staff, apart from
+// salary details; admins also change salaries, delete staff and manage users
+export type UserRole = 'viewer' | 'clerk' | 'admin';
+
+export const USER_ROLES: { value: UserRole; label: string }[] = [
+  { value: 'viewer', label: 'Viewer' },
+  { value: 'clerk', label: 'Clerk' },
+  { value: 'admin', label: 'Administrator' },
+];
+
+export interface User {
+  id: string;
+  username: string;
+  fullName: string;
+  role: UserRole;
+  active: boolean;
+  lastLoginAt?: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CreateUserRequest {
+  username: string;
+  fullName: string;
+  password: string;
+  role: UserRole;
+}
+
+export type UpdateUserRequest = Pick<User, 'id' | 'fullName' | 'role' | 'active'>;
+
+export interface AuthStatus {
+  hasUsers: boolean; // False on a fresh install until the first administrator is created
+  user?: User;
+  locked: boolean; // Idle too long; the password unlocks it again
+}
+
+export interface SecuritySettings {
+  idleTimeoutMinutes: number; // 0 means the session never locks
+  updatedAt: string;
+}
+
+export type UpdateSecuritySettingsRequest = Omit<SecuritySettings, 'updatedAt'>;
+
+export interface FieldChange {
+  field: string;
+  oldValue: unknown;
+  newValue: unknown;
+}
+
+export interface AuditEntry {
+  id: number;
+  userId?: string;
+  username: string;
+  action: string;
+  entityType?: string;
+  entityId?: string;
+  entityVersion?: number; // The staff version the change produced
+  description?: string;
+  changes: FieldChange[];
+  batchId?: string; // Shared by every entry from the same bulk edit
+  createdAt: string;
+}
+
+export interface AuditLogParams {
+  entityType?: string;
+  entityId?: string;
+  userId?: string;
+  batchId?: string;
+  limit?: number;
+  offset?: number;
+}
+
+// Automatic backup schedule and retention
+export interface BackupSettings {
+  enabled: boolean;
+  folder?: string; // Full path; unset means the app data backups folder
+  intervalHours: number;
+  keepDaily: number;
+  keepWeekly: number;
+  keepMonthly: number;
+  lastBackupAt?: string;
+  lastError?: string;
+  updatedAt: string;
+}
+
+export type UpdateBackupSettingsRequest = Omit<BackupSettings, 'lastBackupAt' | 'lastError' | 'updatedAt'>;
+
+export interface BackupInfo {
+  fileName: string;
+  path: string;
+  sizeBytes: number;
+  createdAt: string;
+}
+
+// Loading a file written by export_all
+export interface ImportAllParams {
+  path: string;
+  // skip keeps the local record; replace swaps it for the incoming one
+  onConflict?: 'skip' | 'replace';
+  includeOfficeSettings?: boolean;
+  dryRun?: boolean;
+}
+
+export interface TransferCounts {
+  inserted: number;
+  updated: number;
+  skipped: number;
+}
+
+export interface TransferConflict {
+  table: 'staff' | 'saved_search' | 'staff_group' | 'import_mapping';
+  id: string;
+  name: string;
+  field: string; // nic_number, appointment_number, name, or updated_at when the local copy is newer
+  value: string;
+  existingId: string;
+  resolution: 'skip' | 'replace';
+}
+
+export interface TransferReport {
+  dryRun: boolean;
+  staff: TransferCounts;
+  savedSearches: TransferCounts;
+  staffGroups: TransferCounts;
+  importMappings: TransferCounts;
+  officeSettingsUpdated: boolean;
+  conflicts: TransferConflict[];
+}
+
+// Letterhead and signatory details printed on every report
+export interface OfficeSettings {
+  officeName: string;
+  division: string;
+  address: string;
+  phone?: string;
+  logo?: string; // base64 JPEG
+  certifyingOfficerName?: string;
+  certifyingOfficerDesignation: string;
+  updatedAt: string;
+}
+
+export type UpdateOfficeSettingsRequest = Omit<OfficeSettings, 'logo' | 'updatedAt'>;
+
+export interface StaffPage<T> {
+  items: T[];
+  total: number;
+  limit: number;
+  offset: number;
+}
+
+export interface SavedSearch {
+  id: string;
+  name: string;
+  params: StaffSearchParams;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CreateSavedSearchRequest {
+  name: string;
+  params: StaffSearchParams;
+}
+
+export interface UpdateSavedSearchRequest extends CreateSavedSearchRequest {
+  id: string;
+}
+
+export interface StaffGroup {
+  id: string;
+  name: string;
+  description?: string;
+  staffIds: string[];
+  createdAt: string;
+  updatedAt: string;
+  skippedStaffIds?: string[]; // Ids left out of a create, update or add because they aren't staff records
+}
+
+export interface CreateStaffGroupRequest {
+  name: string;
+  description?: string;
+  staffIds: string[];
+}
+
+export interface UpdateStaffGroupRequest extends CreateStaffGroupRequest {
+  id: string;
 }
 
 export interface PrintStaffBulkParams {
+  // Empty means everyone matching the filters
   staffIds: string[];
   filters?: StaffSearchParams;
+  sortBy?: StaffSortColumn;
+  sortDirection?: SortDirection;
 }
 
 export const DESIGNATIONS = [